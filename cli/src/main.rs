@@ -1,12 +1,15 @@
 use clap::{Parser, Subcommand};
-use anyhow::Result;
+use anyhow::{bail, Result};
 use std::net::TcpStream;
-use std::io::{Read, Write};
+use std::io::{self, BufRead, BufReader, Write};
 
 #[derive(Parser)]
 #[command(name = "mudb")]
 #[command(about = "A CLI for muDB", long_about = None)]
 struct Cli {
+    /// Print replies as JSON instead of the human-readable format
+    #[arg(long, global = true)]
+    json: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -15,21 +18,24 @@ struct Cli {
 enum Commands {
     /// Open a connection to muDB
     Open {
-        #[arg(short, long, default_value = "127.0.0.1")]
+        #[arg(long, default_value = "127.0.0.1")]
         host: String,
         #[arg(short, long, default_value = "6380")]
         port: u16,
     },
     /// Send a PING command
     Ping {
-        #[arg(short, long, default_value = "127.0.0.1")]
+        #[arg(long, default_value = "127.0.0.1")]
         host: String,
         #[arg(short, long, default_value = "6380")]
         port: u16,
+        /// Send `PING <message>` and fail unless the server echoes the message back
+        #[arg(long)]
+        message: Option<String>,
     },
     /// Set a key-value pair
     Set {
-        #[arg(short, long, default_value = "127.0.0.1")]
+        #[arg(long, default_value = "127.0.0.1")]
         host: String,
         #[arg(short, long, default_value = "6380")]
         port: u16,
@@ -38,7 +44,7 @@ enum Commands {
     },
     /// Get a value by key
     Get {
-        #[arg(short, long, default_value = "127.0.0.1")]
+        #[arg(long, default_value = "127.0.0.1")]
         host: String,
         #[arg(short, long, default_value = "6380")]
         port: u16,
@@ -46,7 +52,7 @@ enum Commands {
     },
     /// LPUSH to a list
     Lpush {
-        #[arg(short, long, default_value = "127.0.0.1")]
+        #[arg(long, default_value = "127.0.0.1")]
         host: String,
         #[arg(short, long, default_value = "6380")]
         port: u16,
@@ -55,7 +61,7 @@ enum Commands {
     },
     /// LRANGE on a list
     Lrange {
-        #[arg(short, long, default_value = "127.0.0.1")]
+        #[arg(long, default_value = "127.0.0.1")]
         host: String,
         #[arg(short, long, default_value = "6380")]
         port: u16,
@@ -63,94 +69,365 @@ enum Commands {
         start: i64,
         stop: i64,
     },
+    /// Send an arbitrary command, e.g. `cmd -- DEL mykey`
+    Cmd {
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        #[arg(short, long, default_value = "6380")]
+        port: u16,
+        /// Command name followed by its arguments
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Start an interactive session over a single connection
+    Repl {
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        #[arg(short, long, default_value = "6380")]
+        port: u16,
+    },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let json = cli.json;
     match cli.command {
         Commands::Open { host, port } => {
             println!("Connecting to muDB at {}:{}...", host, port);
             let _stream = TcpStream::connect((host, port))?;
             println!("Connected!");
         }
-        Commands::Ping { host, port } => {
+        Commands::Ping { host, port, message: None } => {
             let mut stream = TcpStream::connect((host, port))?;
             let ping_cmd = "*1\r\n$4\r\nPING\r\n";
             stream.write_all(ping_cmd.as_bytes())?;
-            let mut buf = [0; 1024];
-            let n = stream.read(&mut buf)?;
-            print_resp(&buf[..n]);
+            let reply = read_reply(&mut BufReader::new(&stream))?;
+            print_reply(&reply, json);
+        }
+        Commands::Ping { host, port, message: Some(message) } => {
+            let mut stream = TcpStream::connect((host, port))?;
+            stream.write_all(&encode_command(&[String::from("PING"), message.clone()]))?;
+            let reply = read_reply(&mut BufReader::new(&stream))?;
+            print_reply(&reply, json);
+            // the server must echo the message back as a bulk string
+            match &reply {
+                Reply::BulkString(echoed) if *echoed == message => {}
+                _ => bail!("PING reply does not match the message {:?}", message),
+            }
         }
         Commands::Set { host, port, key, value } => {
             let mut stream = TcpStream::connect((host, port))?;
             let cmd = format!("*3\r\n$3\r\nSET\r\n${}\r\n{}\r\n${}\r\n{}\r\n", key.len(), key, value.len(), value);
             stream.write_all(cmd.as_bytes())?;
-            let mut buf = [0; 1024];
-            let n = stream.read(&mut buf)?;
-            print_resp(&buf[..n]);
+            let reply = read_reply(&mut BufReader::new(&stream))?;
+            print_reply(&reply, json);
         }
         Commands::Get { host, port, key } => {
             let mut stream = TcpStream::connect((host, port))?;
             let cmd = format!("*2\r\n$3\r\nGET\r\n${}\r\n{}\r\n", key.len(), key);
             stream.write_all(cmd.as_bytes())?;
-            let mut buf = [0; 1024];
-            let n = stream.read(&mut buf)?;
-            print_resp(&buf[..n]);
+            let reply = read_reply(&mut BufReader::new(&stream))?;
+            print_reply(&reply, json);
         }
         Commands::Lpush { host, port, list, value } => {
             let mut stream = TcpStream::connect((host, port))?;
             let cmd = format!("*3\r\n$5\r\nLPUSH\r\n${}\r\n{}\r\n${}\r\n{}\r\n", list.len(), list, value.len(), value);
             stream.write_all(cmd.as_bytes())?;
-            let mut buf = [0; 1024];
-            let n = stream.read(&mut buf)?;
-            print_resp(&buf[..n]);
+            let reply = read_reply(&mut BufReader::new(&stream))?;
+            print_reply(&reply, json);
         }
         Commands::Lrange { host, port, list, start, stop } => {
             let mut stream = TcpStream::connect((host, port))?;
             let cmd = format!("*4\r\n$6\r\nLRANGE\r\n${}\r\n{}\r\n${}\r\n{}\r\n${}\r\n{}\r\n", list.len(), list, start.to_string().len(), start, stop.to_string().len(), stop);
             stream.write_all(cmd.as_bytes())?;
-            let mut buf = [0; 2048];
-            let n = stream.read(&mut buf)?;
-            print_resp(&buf[..n]);
+            let reply = read_reply(&mut BufReader::new(&stream))?;
+            print_reply(&reply, json);
+        }
+        Commands::Cmd { host, port, args } => {
+            let mut stream = TcpStream::connect((host, port))?;
+            stream.write_all(&encode_command(&args))?;
+            let reply = read_reply(&mut BufReader::new(&stream))?;
+            print_reply(&reply, json);
+        }
+        Commands::Repl { host, port } => repl(&host, port, json)?,
+    }
+    Ok(())
+}
+
+/// Runs an interactive session: each line read from stdin is sent as a command
+/// over the same connection, until `quit`/`exit` or EOF.
+fn repl(host: &str, port: u16, json: bool) -> Result<()> {
+    let mut stream = TcpStream::connect((host, port))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let stdin = io::stdin();
+    let mut line = String::new();
+    loop {
+        print!("{}:{}> ", host, port);
+        io::stdout().flush()?;
+
+        line.clear();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            // EOF (Ctrl-D)
+            println!();
+            break;
         }
+
+        let args = match split_args(&line) {
+            Some(args) => args,
+            None => {
+                eprintln!("Error: unbalanced quotes in command");
+                continue;
+            }
+        };
+        if args.is_empty() {
+            continue;
+        }
+        if args.len() == 1 && matches!(args[0].to_lowercase().as_str(), "quit" | "exit") {
+            break;
+        }
+
+        stream.write_all(&encode_command(&args))?;
+        let reply = read_reply(&mut reader)?;
+        print_reply(&reply, json);
     }
     Ok(())
 }
 
-fn print_resp(resp: &[u8]) {
-    let s = String::from_utf8_lossy(resp);
-    let mut lines = s.split("\r\n").filter(|l| !l.is_empty());
-    if let Some(first) = lines.next() {
-        match first.chars().next() {
-            Some('+') => println!("{}", &first[1..]), // Simple string
-            Some('-') => eprintln!("Error: {}", &first[1..]), // Error
-            Some(':') => println!("(integer) {}", &first[1..]), // Integer
-            Some('$') => {
-                // Bulk string
-                if let Some(val) = lines.next() {
-                    println!("{}", val);
-                } else {
-                    println!("(nil)");
+/// Splits a command line into arguments on whitespace. Arguments can be wrapped in
+/// single or double quotes to include spaces. Returns `None` if a quote is left open.
+fn split_args(line: &str) -> Option<Vec<String>> {
+    let mut args = vec![];
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        if chars.peek().is_none() {
+            return Some(args);
+        }
+
+        let mut arg = String::new();
+        let mut quote: Option<char> = None;
+        while let Some(c) = chars.next() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some('"') if c == '\\' => match chars.next() {
+                    Some('n') => arg.push('\n'),
+                    Some('r') => arg.push('\r'),
+                    Some('t') => arg.push('\t'),
+                    Some(other) => arg.push(other),
+                    None => return None,
+                },
+                Some(_) => arg.push(c),
+                None if c == '"' || c == '\'' => quote = Some(c),
+                None if c.is_whitespace() => break,
+                None => arg.push(c),
+            }
+        }
+        if quote.is_some() {
+            return None;
+        }
+        args.push(arg);
+    }
+}
+
+/// A RESP reply decoded from the server.
+#[derive(Debug)]
+enum Reply {
+    SimpleString(String),
+    Error(String),
+    Integer(i64),
+    BulkString(String),
+    Nil,
+    Array(Vec<Reply>),
+}
+
+/// Reads one complete RESP reply from the connection.
+/// Bulk strings and arrays spanning several TCP reads are read until they are complete.
+fn read_reply<R: BufRead>(reader: &mut R) -> Result<Reply> {
+    let mut line = vec![];
+    if reader.read_until(b'\n', &mut line)? == 0 {
+        bail!("connection closed by server");
+    }
+    if line.len() < 3 || !line.ends_with(b"\r\n") {
+        bail!("invalid RESP reply");
+    }
+
+    let value = String::from_utf8_lossy(&line[1..line.len() - 2]).to_string();
+    let reply = match line[0] {
+        b'+' => Reply::SimpleString(value),
+        b'-' => Reply::Error(value),
+        b':' => Reply::Integer(value.parse()?),
+        b'$' => {
+            let len: i64 = value.parse()?;
+            if len < 0 {
+                Reply::Nil
+            } else {
+                // string value followed by CRLF
+                let mut buf = vec![0; len as usize + 2];
+                reader.read_exact(&mut buf)?;
+                buf.truncate(len as usize);
+                Reply::BulkString(String::from_utf8_lossy(&buf).to_string())
+            }
+        }
+        b'*' => {
+            let len: i64 = value.parse()?;
+            if len < 0 {
+                Reply::Nil
+            } else {
+                let mut items = vec![];
+                for _ in 0..len {
+                    items.push(read_reply(reader)?);
                 }
+                Reply::Array(items)
             }
-            Some('*') => {
-                // Array
-                let count: usize = first[1..].parse().unwrap_or(0);
-                for _ in 0..count {
-                    if let Some(len_line) = lines.next() {
-                        if len_line.starts_with('$') {
-                            if let Some(val_line) = lines.next() {
-                                println!("- {}", val_line);
-                            } else {
-                                println!("- (nil)");
-                            }
-                        }
+        }
+        _ => bail!("invalid RESP reply"),
+    };
+    Ok(reply)
+}
+
+/// Encodes the given arguments as a RESP array of bulk strings.
+fn encode_command(args: &[String]) -> Vec<u8> {
+    let mut cmd = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        cmd.extend(format!("${}\r\n", arg.len()).into_bytes());
+        cmd.extend(arg.as_bytes());
+        cmd.extend(b"\r\n");
+    }
+    cmd
+}
+
+/// Prints the reply as JSON if `json` is set, else in the human-readable format.
+fn print_reply(reply: &Reply, json: bool) {
+    if json {
+        println!("{}", to_json(reply));
+    } else {
+        print_resp(reply, 0);
+    }
+}
+
+/// Prints the reply in a human-readable format. Array items are printed one per line,
+/// and nested arrays are indented by `depth`.
+fn print_resp(reply: &Reply, depth: usize) {
+    match reply {
+        Reply::SimpleString(s) => println!("{}", s),
+        Reply::Error(e) => eprintln!("Error: {}", e),
+        Reply::Integer(i) => println!("(integer) {}", i),
+        Reply::BulkString(s) => println!("{}", s),
+        Reply::Nil => println!("(nil)"),
+        Reply::Array(items) => {
+            let indent = "  ".repeat(depth);
+            for item in items {
+                match item {
+                    Reply::Array(_) => {
+                        println!("{}-", indent);
+                        print_resp(item, depth + 1);
                     }
+                    Reply::BulkString(s) | Reply::SimpleString(s) => println!("{}- {}", indent, s),
+                    Reply::Integer(i) => println!("{}- (integer) {}", indent, i),
+                    Reply::Error(e) => println!("{}- (error) {}", indent, e),
+                    Reply::Nil => println!("{}- (nil)", indent),
                 }
             }
-            _ => println!("{}", s),
         }
-    } else {
-        println!("(empty response)");
+    }
+}
+
+/// Serializes the reply into JSON. Errors are serialized as `{"error": "..."}`.
+fn to_json(reply: &Reply) -> String {
+    match reply {
+        Reply::SimpleString(s) | Reply::BulkString(s) => json_string(s),
+        Reply::Error(e) => format!("{{\"error\": {}}}", json_string(e)),
+        Reply::Integer(i) => i.to_string(),
+        Reply::Nil => String::from("null"),
+        Reply::Array(items) => {
+            let items: Vec<String> = items.iter().map(to_json).collect();
+            format!("[{}]", items.join(","))
+        }
+    }
+}
+
+/// Quotes and escapes a string as a JSON string literal.
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    /// A reader which hands out at most `chunk` bytes per read, like a TCP stream receiving
+    /// a large reply over many packets.
+    struct Chunked {
+        data: Vec<u8>,
+        pos: usize,
+        chunk: usize,
+    }
+
+    impl Read for Chunked {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.chunk).min(self.data.len() - self.pos);
+            buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+            self.pos += n;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn read_reply_reads_a_large_list_spanning_many_reads() {
+        let mut data = b"*5000\r\n".to_vec();
+        for i in 0..5000 {
+            let item = format!("element-{}", i);
+            data.extend(format!("${}\r\n{}\r\n", item.len(), item).into_bytes());
+        }
+        // a second reply right after the first one must be left for the next call
+        data.extend(b"+OK\r\n");
+
+        let mut reader = BufReader::with_capacity(
+            64,
+            Chunked {
+                data,
+                pos: 0,
+                chunk: 7,
+            },
+        );
+        match read_reply(&mut reader).unwrap() {
+            Reply::Array(items) => {
+                assert_eq!(items.len(), 5000);
+                for (i, item) in items.iter().enumerate() {
+                    assert!(matches!(item, Reply::BulkString(s) if *s == format!("element-{}", i)));
+                }
+            }
+            _ => panic!("expected an array"),
+        }
+        assert!(matches!(
+            read_reply(&mut reader).unwrap(),
+            Reply::SimpleString(s) if s == "OK"
+        ));
+    }
+
+    #[test]
+    fn read_reply_fails_on_a_truncated_reply() {
+        let mut reader = BufReader::new(Chunked {
+            data: b"*2\r\n$3\r\nfoo\r\n$3\r\nba".to_vec(),
+            pos: 0,
+            chunk: 4,
+        });
+        assert!(read_reply(&mut reader).is_err());
     }
 }
@@ -0,0 +1,298 @@
+// src/client.rs
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::sync::{broadcast, Notify};
+
+/// Number of lines buffered for each connection in MONITOR mode. A connection reading them
+/// too slowly misses the oldest lines rather than slowing down the other connections.
+const MONITOR_CAPACITY: usize = 1024;
+
+/// The ClientInfo struct holds the metadata of a single client connection.
+#[derive(Debug, Clone)]
+pub struct ClientInfo {
+    /// Unique ID of the connection, assigned when the connection is accepted.
+    pub id: u64,
+    /// Address of the peer of the connection.
+    pub addr: SocketAddr,
+    /// Name of the connection, set using CLIENT SETNAME.
+    pub name: Option<String>,
+    /// The time at which the connection was accepted.
+    pub connected_at: Instant,
+    /// The time at which the last complete command was received or replied to, in milliseconds
+    /// since `connected_at`. It is shared with the `ClientHandle`, which updates it for every
+    /// command without locking the registry.
+    last_activity_ms: Arc<AtomicU64>,
+    /// Signals the task handling the connection that it should be closed.
+    close_signal: Arc<Notify>,
+}
+
+/// The ClientRegistry keeps track of all the connected clients.
+///
+/// A client is added to the registry when its connection is accepted, and removed when
+/// the `ClientHandle` returned on registration is dropped.
+///
+/// The registry also carries the executed commands to the connections in MONITOR mode.
+#[derive(Debug)]
+pub struct ClientRegistry {
+    clients: Mutex<HashMap<u64, ClientInfo>>,
+    /// Sends a line describing every executed command to the connections in MONITOR mode.
+    monitor: broadcast::Sender<String>,
+    /// The number of connections in MONITOR mode. Checked for every command, so unlike the
+    /// receiver count of `monitor`, it can be read without taking a lock.
+    monitors: AtomicUsize,
+}
+
+/// A handle to the registry entry of a single connected client.
+///
+/// The entry is removed from the registry when the handle is dropped, including when the
+/// task handling the connection panics.
+#[derive(Debug)]
+pub struct ClientHandle {
+    id: u64,
+    addr: SocketAddr,
+    /// The time at which the connection was accepted.
+    connected_at: Instant,
+    /// Same as `ClientInfo::last_activity_ms`.
+    last_activity_ms: Arc<AtomicU64>,
+    /// Whether the connection is in MONITOR mode, and so counted in `ClientRegistry::monitors`.
+    monitoring: AtomicBool,
+    registry: Arc<ClientRegistry>,
+}
+
+impl Default for ClientRegistry {
+    fn default() -> ClientRegistry {
+        ClientRegistry::new()
+    }
+}
+
+impl ClientRegistry {
+    /// Create a new, empty `ClientRegistry`.
+    pub fn new() -> ClientRegistry {
+        ClientRegistry {
+            clients: Mutex::new(HashMap::new()),
+            monitor: broadcast::channel(MONITOR_CAPACITY).0,
+            monitors: AtomicUsize::new(0),
+        }
+    }
+
+    /// Add a newly accepted connection to the registry.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Unique ID of the connection.
+    ///
+    /// * `addr` - Address of the peer of the connection.
+    ///
+    /// # Returns
+    ///
+    /// A `ClientHandle` which removes the connection from the registry when dropped.
+    pub fn register(self: &Arc<Self>, id: u64, addr: SocketAddr) -> ClientHandle {
+        let now = Instant::now();
+        let last_activity_ms = Arc::new(AtomicU64::new(0));
+        self.lock().insert(
+            id,
+            ClientInfo {
+                id,
+                addr,
+                name: None,
+                connected_at: now,
+                last_activity_ms: Arc::clone(&last_activity_ms),
+                close_signal: Arc::new(Notify::new()),
+            },
+        );
+
+        ClientHandle {
+            id,
+            addr,
+            connected_at: now,
+            last_activity_ms,
+            monitoring: AtomicBool::new(false),
+            registry: Arc::clone(self),
+        }
+    }
+
+    /// Returns the metadata of all the connected clients, ordered by ID.
+    pub fn list(&self) -> Vec<ClientInfo> {
+        let mut clients: Vec<ClientInfo> = self.lock().values().cloned().collect();
+        clients.sort_by_key(|c| c.id);
+        clients
+    }
+
+    /// Ask every connection which has been idle for longer than the given timeout to close.
+    /// The connections are closed by their own tasks, which check that they are still idle first.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The time without any complete command after which a connection is idle.
+    ///
+    /// # Returns
+    ///
+    /// The number of connections asked to close.
+    pub fn close_idle(&self, timeout: Duration) -> usize {
+        let clients = self.lock();
+        let mut closed = 0;
+        for client in clients.values() {
+            if client.idle_time() > timeout {
+                client.close_signal.notify_one();
+                closed += 1;
+            }
+        }
+        closed
+    }
+
+    /// Returns whether at least one connection is in MONITOR mode, so that the lines
+    /// describing the executed commands are only built when someone reads them.
+    pub fn is_monitored(&self) -> bool {
+        self.monitors.load(Ordering::Relaxed) > 0
+    }
+
+    /// Send a line describing an executed command to the connections in MONITOR mode.
+    pub fn publish_monitor(&self, line: String) {
+        // Sending only fails if the last monitor disconnected in the meantime.
+        let _ = self.monitor.send(line);
+    }
+
+    /// Locks the registry. The map is never left half-updated, so a lock poisoned by a
+    /// panicking connection task can still be used.
+    fn lock(&self) -> MutexGuard<'_, HashMap<u64, ClientInfo>> {
+        self.clients.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl ClientInfo {
+    /// Returns the time elapsed since the last complete command was received or replied to.
+    pub fn idle_time(&self) -> Duration {
+        idle_time(self.connected_at, &self.last_activity_ms)
+    }
+}
+
+/// Returns the time elapsed since the last activity of a connection accepted at `connected_at`,
+/// given the time of that activity in milliseconds since `connected_at`.
+fn idle_time(connected_at: Instant, last_activity_ms: &AtomicU64) -> Duration {
+    let last_activity = Duration::from_millis(last_activity_ms.load(Ordering::Relaxed));
+    connected_at.elapsed().saturating_sub(last_activity)
+}
+
+impl ClientHandle {
+    /// Returns the ID of the connection.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the address of the peer of the connection.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the name of the connection, if one is set.
+    pub fn name(&self) -> Option<String> {
+        self.registry
+            .lock()
+            .get(&self.id)
+            .and_then(|c| c.name.clone())
+    }
+
+    /// Set the name of the connection. `None` removes the name.
+    pub fn set_name(&self, name: Option<String>) {
+        if let Some(client) = self.registry.lock().get_mut(&self.id) {
+            client.name = name;
+        }
+    }
+
+    /// Record that a complete command was received or replied to on the connection.
+    /// This is called for every command, so it doesn't lock the registry.
+    pub fn record_activity(&self) {
+        let elapsed_ms = self.connected_at.elapsed().as_millis() as u64;
+        self.last_activity_ms.store(elapsed_ms, Ordering::Relaxed);
+    }
+
+    /// Returns the time elapsed since the last complete command was received or replied to.
+    pub fn idle_time(&self) -> Duration {
+        idle_time(self.connected_at, &self.last_activity_ms)
+    }
+
+    /// Returns the signal notified when the connection is asked to close.
+    pub fn close_signal(&self) -> Arc<Notify> {
+        self.registry
+            .lock()
+            .get(&self.id)
+            .map_or_else(|| Arc::new(Notify::new()), |c| Arc::clone(&c.close_signal))
+    }
+
+    /// Subscribe the connection to the lines describing the executed commands, for MONITOR.
+    /// The connection stays in MONITOR mode until the handle is dropped.
+    pub fn subscribe_monitor(&self) -> broadcast::Receiver<String> {
+        if !self.monitoring.swap(true, Ordering::Relaxed) {
+            self.registry.monitors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.registry.monitor.subscribe()
+    }
+
+    /// Reset the state of the connection, as if it was newly accepted.
+    /// The ID of the connection is kept.
+    pub fn reset(&self) {
+        self.set_name(None);
+    }
+
+    /// Returns the registry of all the connected clients.
+    pub fn registry(&self) -> &ClientRegistry {
+        &self.registry
+    }
+}
+
+impl Drop for ClientHandle {
+    fn drop(&mut self) {
+        if self.monitoring.load(Ordering::Relaxed) {
+            self.registry.monitors.fetch_sub(1, Ordering::Relaxed);
+        }
+        self.registry.lock().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_activity_resets_the_idle_time() {
+        let registry = Arc::new(ClientRegistry::new());
+        let client = registry.register(1, "127.0.0.1:1000".parse().unwrap());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(client.idle_time() >= Duration::from_millis(30));
+        assert!(registry.list()[0].idle_time() >= Duration::from_millis(30));
+        assert_eq!(registry.close_idle(Duration::from_millis(20)), 1);
+
+        client.record_activity();
+        assert!(client.idle_time() < Duration::from_millis(20));
+        assert!(registry.list()[0].idle_time() < Duration::from_millis(20));
+        assert_eq!(registry.close_idle(Duration::from_millis(20)), 0);
+    }
+
+    #[test]
+    fn monitor_count_follows_the_monitoring_connections() {
+        let registry = Arc::new(ClientRegistry::new());
+        let monitor = registry.register(1, "127.0.0.1:1000".parse().unwrap());
+        let other = registry.register(2, "127.0.0.1:1001".parse().unwrap());
+        assert!(!registry.is_monitored());
+
+        let _lines = monitor.subscribe_monitor();
+        // subscribing twice doesn't count the connection twice
+        let _again = monitor.subscribe_monitor();
+        assert!(registry.is_monitored());
+
+        drop(other);
+        assert!(registry.is_monitored());
+        drop(monitor);
+        assert!(!registry.is_monitored());
+    }
+}
@@ -0,0 +1,67 @@
+// src/command/append.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the APPEND command in MuDB.
+///
+/// The APPEND command appends a string to the value stored at a key, creating the key
+/// if it doesn't exist.
+#[derive(Debug, Clone)]
+pub struct Append {
+    key: String,
+    value: String,
+}
+
+impl Append {
+    /// Creates a new `Append` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the APPEND command:
+    ///   the key and the string to append.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Append)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Append, CommandError> {
+        if args.len() != 2 {
+            return Err(CommandError::WrongArity("append"));
+        }
+
+        let mut strs: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key and value must be bulk strings",
+                    )));
+                }
+            }
+        }
+        let value = strs.pop().unwrap();
+        let key = strs.pop().unwrap();
+
+        Ok(Append { key, value })
+    }
+
+    /// Executes the APPEND command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - The length of the value after the append, as an `Integer`
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.append(self.key.clone(), &self.value) {
+            Ok(len) => RespType::Integer(len as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
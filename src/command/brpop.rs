@@ -0,0 +1,110 @@
+// src/command/brpop.rs
+
+use std::time::Duration;
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{ListEnd, DB},
+};
+
+use super::CommandError;
+
+/// Represents the BRPOP command in MuDB.
+///
+/// It pops an element from the tail of the first non-empty list among the given keys,
+/// or blocks the connection until an element is pushed to one of them.
+#[derive(Debug, Clone)]
+pub struct BRPop {
+    keys: Vec<String>,
+    /// Time to wait for an element. `None` blocks indefinitely.
+    timeout: Option<Duration>,
+}
+
+impl BRPop {
+    /// Creates a new `BRPOP` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the BRPOP command.
+    ///   The last argument is the timeout in seconds, and the rest are the keys.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BRPop)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<BRPop, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::WrongArity("brpop"));
+        }
+
+        // parse keys
+        let (keys, timeout) = args.split_at(args.len() - 1);
+        let mut key_names: Vec<String> = vec![];
+        for key in keys.iter() {
+            match key {
+                RespType::BulkString(k) => key_names.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        // parse timeout
+        let timeout = match &timeout[0] {
+            RespType::BulkString(t) => match t.parse::<f64>() {
+                Ok(t) if t.is_finite() => t,
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "timeout is not a float or out of range",
+                    )))
+                }
+            },
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Timeout must be a bulk string",
+                )));
+            }
+        };
+        if timeout < 0.0 {
+            return Err(CommandError::Other(String::from("timeout is negative")));
+        }
+
+        // A zero timeout blocks indefinitely.
+        let timeout = if timeout == 0.0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64(timeout))
+        };
+
+        Ok(BRPop {
+            keys: key_names,
+            timeout,
+        })
+    }
+
+    /// Executes the BRPOP command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - If an element is popped - A two-element `Array` with the key and the popped element
+    /// - If the timeout elapses - A `NullBulkString`
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub async fn apply(&self, db: &DB) -> RespType {
+        match db
+            .blocking_pop(&self.keys, ListEnd::Tail, self.timeout)
+            .await
+        {
+            Ok(Some((key, elem))) => {
+                RespType::Array(vec![RespType::BulkString(key), RespType::BulkString(elem)])
+            }
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+// src/command/cas.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the CAS command in MuDB.
+///
+/// The CAS command atomically replaces the string value stored against a key, only if
+/// it currently holds the expected value. This covers the common compare-and-set use of
+/// scripting without a script engine.
+#[derive(Debug, Clone)]
+pub struct Cas {
+    key: String,
+    /// The value the key must currently hold.
+    expected: String,
+    /// The value to be set against the key.
+    new: String,
+}
+
+impl Cas {
+    /// Creates a new `Cas` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CAS command:
+    ///   the key, the expected value and the new value.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Cas)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Cas, CommandError> {
+        if args.len() != 3 {
+            return Err(CommandError::WrongArity("cas"));
+        }
+
+        let mut strs: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key and values must be bulk strings",
+                    )));
+                }
+            }
+        }
+        let new = strs.pop().unwrap();
+        let expected = strs.pop().unwrap();
+        let key = strs.pop().unwrap();
+
+        Ok(Cas { key, expected, new })
+    }
+
+    /// Executes the CAS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(1)` if the value was replaced
+    /// - `Integer(0)` if the key doesn't exist, or holds a different value
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.cas(&self.key, &self.expected, self.new.clone()) {
+            Ok(updated) => RespType::Integer(updated as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
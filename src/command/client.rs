@@ -0,0 +1,176 @@
+// src/command/client.rs
+
+use crate::{
+    client::{ClientHandle, ClientInfo},
+    resp::types::RespType,
+};
+
+use super::{help::help_reply, CommandError};
+
+/// Represents the CLIENT command in MuDB.
+///
+/// The CLIENT command is used to inspect and change the state of the current connection.
+#[derive(Debug, Clone)]
+pub struct Client {
+    subcommand: ClientSubcommand,
+}
+
+/// The subcommands supported by the CLIENT command.
+#[derive(Debug, Clone)]
+enum ClientSubcommand {
+    /// CLIENT ID
+    Id,
+    /// CLIENT SETNAME name
+    SetName(String),
+    /// CLIENT GETNAME
+    GetName,
+    /// CLIENT LIST
+    List,
+    /// CLIENT HELP
+    Help,
+}
+
+impl Client {
+    /// Creates a new `Client` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CLIENT command.
+    ///   The first argument is the subcommand name.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Client)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Client, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::WrongArity("client"));
+        }
+
+        // parse subcommand
+        let subcommand = match &args[0] {
+            RespType::BulkString(s) => s.to_lowercase(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Subcommand must be a bulk string",
+                )));
+            }
+        };
+
+        let subcommand = match subcommand.as_str() {
+            "id" => {
+                if args.len() != 1 {
+                    return Err(CommandError::WrongArity("client|id"));
+                }
+                ClientSubcommand::Id
+            }
+            "setname" => {
+                if args.len() != 2 {
+                    return Err(CommandError::WrongArity("client|setname"));
+                }
+                let name = match &args[1] {
+                    RespType::BulkString(n) => n.to_string(),
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "Invalid argument. Name must be a bulk string",
+                        )));
+                    }
+                };
+
+                // names are printed space separated by CLIENT LIST, so they must be a single word
+                if name.chars().any(|c| !c.is_ascii_graphic()) {
+                    return Err(CommandError::Other(String::from(
+                        "ERR Client names cannot contain spaces, newlines or special characters.",
+                    )));
+                }
+                ClientSubcommand::SetName(name)
+            }
+            "getname" => {
+                if args.len() != 1 {
+                    return Err(CommandError::WrongArity("client|getname"));
+                }
+                ClientSubcommand::GetName
+            }
+            "list" => {
+                if args.len() != 1 {
+                    return Err(CommandError::WrongArity("client|list"));
+                }
+                ClientSubcommand::List
+            }
+            "help" => {
+                if args.len() != 1 {
+                    return Err(CommandError::WrongArity("client|help"));
+                }
+                ClientSubcommand::Help
+            }
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'CLIENT' command",
+                    subcommand
+                )));
+            }
+        };
+
+        Ok(Client { subcommand })
+    }
+
+    /// Executes the CLIENT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The connection the command was received on.
+    ///
+    /// # Returns
+    ///
+    /// - ID - The ID of the connection as an `Integer`
+    /// - SETNAME - `SimpleString("OK")`. An empty name removes the name of the connection.
+    /// - GETNAME - The name of the connection as a `BulkString`, empty if no name is set
+    /// - LIST - A `BulkString` with one line for each connected client
+    /// - HELP - An `Array` of `SimpleString` lines describing the subcommands
+    pub fn apply(&self, client: &ClientHandle) -> RespType {
+        match &self.subcommand {
+            ClientSubcommand::Id => RespType::Integer(client.id() as i64),
+            ClientSubcommand::SetName(name) => {
+                if name.is_empty() {
+                    client.set_name(None);
+                } else {
+                    client.set_name(Some(name.clone()));
+                }
+                RespType::SimpleString(String::from("OK"))
+            }
+            ClientSubcommand::GetName => RespType::BulkString(client.name().unwrap_or_default()),
+            ClientSubcommand::List => RespType::BulkString(
+                client
+                    .registry()
+                    .list()
+                    .iter()
+                    .map(Self::format_client)
+                    .collect(),
+            ),
+            ClientSubcommand::Help => help_reply(
+                "CLIENT",
+                &[
+                    ("ID", "Return the ID of the current connection."),
+                    (
+                        "SETNAME <name>",
+                        "Assign the name <name> to the current connection. An empty name removes it.",
+                    ),
+                    ("GETNAME", "Return the name of the current connection."),
+                    ("LIST", "Return information about client connections."),
+                ],
+            ),
+        }
+    }
+
+    /// Formats the metadata of a client as a line of CLIENT LIST output.
+    fn format_client(client: &ClientInfo) -> String {
+        format!(
+            "id={} addr={} name={} age={} idle={}\n",
+            client.id,
+            client.addr,
+            client.name.as_deref().unwrap_or(""),
+            client.connected_at.elapsed().as_secs(),
+            client.idle_time().as_secs()
+        )
+    }
+}
@@ -0,0 +1,115 @@
+// src/command/cluster.rs
+
+use crate::resp::types::RespType;
+
+use super::{help::help_reply, CommandError};
+
+/// Represents the CLUSTER command in MuDB.
+///
+/// MuDB doesn't support Redis Cluster. The CLUSTER command only exists so that cluster-aware
+/// clients, which probe CLUSTER INFO or CLUSTER SLOTS on connect, find a single node without
+/// any slots and fall back to standalone mode instead of failing.
+#[derive(Debug, Clone)]
+pub struct Cluster {
+    subcommand: ClusterSubcommand,
+}
+
+/// The subcommands supported by the CLUSTER command.
+#[derive(Debug, Clone)]
+enum ClusterSubcommand {
+    /// CLUSTER INFO
+    Info,
+    /// CLUSTER SLOTS
+    Slots,
+    /// CLUSTER SHARDS
+    Shards,
+    /// CLUSTER HELP
+    Help,
+    /// Any other subcommand, which is rejected since cluster support is disabled.
+    Other,
+}
+
+impl Cluster {
+    /// Creates a new `Cluster` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CLUSTER command.
+    ///   The first argument is the subcommand name.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Cluster)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Cluster, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::WrongArity("cluster"));
+        }
+
+        // parse subcommand
+        let subcommand = match &args[0] {
+            RespType::BulkString(s) => s.to_lowercase(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Subcommand must be a bulk string",
+                )));
+            }
+        };
+
+        let (parsed, name) = match subcommand.as_str() {
+            "info" => (ClusterSubcommand::Info, "cluster|info"),
+            "slots" => (ClusterSubcommand::Slots, "cluster|slots"),
+            "shards" => (ClusterSubcommand::Shards, "cluster|shards"),
+            "help" => (ClusterSubcommand::Help, "cluster|help"),
+            _ => (ClusterSubcommand::Other, "cluster"),
+        };
+        if !matches!(parsed, ClusterSubcommand::Other) && args.len() != 1 {
+            return Err(CommandError::WrongArity(name));
+        }
+
+        Ok(Cluster { subcommand: parsed })
+    }
+
+    /// Executes the CLUSTER command.
+    ///
+    /// # Returns
+    ///
+    /// - INFO - A `BulkString` with `field:value` lines describing a disabled cluster
+    /// - SLOTS, SHARDS - An empty `Array`, since the node serves no slots
+    /// - HELP - An `Array` of `SimpleString` lines describing the subcommands
+    /// - Other subcommands - A `SimpleError`, since cluster support is disabled
+    pub fn apply(&self) -> RespType {
+        match &self.subcommand {
+            ClusterSubcommand::Info => RespType::BulkString(String::from(
+                "cluster_enabled:0\r\n\
+                 cluster_state:ok\r\n\
+                 cluster_slots_assigned:0\r\n\
+                 cluster_slots_ok:0\r\n\
+                 cluster_slots_pfail:0\r\n\
+                 cluster_slots_fail:0\r\n\
+                 cluster_known_nodes:1\r\n\
+                 cluster_size:0\r\n\
+                 cluster_current_epoch:0\r\n\
+                 cluster_my_epoch:0\r\n",
+            )),
+            ClusterSubcommand::Slots | ClusterSubcommand::Shards => RespType::Array(vec![]),
+            ClusterSubcommand::Help => help_reply(
+                "CLUSTER",
+                &[
+                    ("INFO", "Return information about the cluster."),
+                    (
+                        "SLOTS",
+                        "Return the mapping of hash slots to nodes. Always empty in MuDB.",
+                    ),
+                    (
+                        "SHARDS",
+                        "Return the mapping of shards to nodes. Always empty in MuDB.",
+                    ),
+                ],
+            ),
+            ClusterSubcommand::Other => RespType::SimpleError(String::from(
+                "ERR This instance has cluster support disabled",
+            )),
+        }
+    }
+}
@@ -0,0 +1,881 @@
+// src/command/command_info.rs
+
+use crate::resp::types::RespType;
+
+use super::{help::help_reply, CommandError};
+
+/// Represents the COMMAND command in MuDB.
+///
+/// The COMMAND command describes the commands supported by the server. Client libraries
+/// call it on connect to learn the arity, flags and key positions of the commands.
+#[derive(Debug, Clone)]
+pub struct CommandInfo {
+    subcommand: CommandSubcommand,
+}
+
+/// The subcommands supported by the COMMAND command.
+#[derive(Debug, Clone)]
+enum CommandSubcommand {
+    /// COMMAND COUNT
+    Count,
+    /// COMMAND DOCS [command-name ...]. Describes every command if no names are given.
+    Docs(Vec<String>),
+    /// COMMAND INFO [command-name ...], or COMMAND without a subcommand.
+    /// Describes every command if no names are given.
+    Info(Vec<String>),
+    /// COMMAND HELP
+    Help,
+}
+
+/// Static description of a command, as reported by COMMAND INFO and COMMAND DOCS.
+struct CommandSpec {
+    /// Lowercased name of the command.
+    name: &'static str,
+    /// Number of arguments, including the command name. Negative if it's the minimum number
+    /// of arguments of a command accepting a variable number of them.
+    arity: i64,
+    /// Flags describing the behaviour of the command, such as `write` or `readonly`.
+    flags: &'static [&'static str],
+    /// Position of the first key in the arguments, 0 if the command has no keys.
+    first_key: i64,
+    /// Position of the last key in the arguments. Negative positions count from the end.
+    last_key: i64,
+    /// Step between the positions of consecutive keys.
+    step: i64,
+    /// Group of the command, such as `string` or `list`.
+    group: &'static str,
+    /// One line summary of what the command does.
+    summary: &'static str,
+}
+
+/// The commands supported by MuDB, in alphabetical order.
+const COMMAND_TABLE: &[CommandSpec] = &[
+    CommandSpec {
+        name: "append",
+        arity: 3,
+        flags: &["write", "denyoom", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "string",
+        summary: "Appends a string to the value of a key. Creates the key if it doesn't exist.",
+    },
+    CommandSpec {
+        name: "blpop",
+        arity: -3,
+        flags: &["write", "blocking"],
+        first_key: 1,
+        last_key: -2,
+        step: 1,
+        group: "list",
+        summary: "Removes and returns the first element in a list. Blocks until an element is available otherwise. Deletes the list if the last element was popped.",
+    },
+    CommandSpec {
+        name: "brpop",
+        arity: -3,
+        flags: &["write", "blocking"],
+        first_key: 1,
+        last_key: -2,
+        step: 1,
+        group: "list",
+        summary: "Removes and returns the last element in a list. Blocks until an element is available otherwise. Deletes the list if the last element was popped.",
+    },
+    CommandSpec {
+        name: "cas",
+        arity: 4,
+        flags: &["write", "denyoom"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "string",
+        summary: "Sets the string value of a key only if its current value is the expected one.",
+    },
+    CommandSpec {
+        name: "client",
+        arity: -2,
+        flags: &[],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "connection",
+        summary: "A container for client connection commands.",
+    },
+    CommandSpec {
+        name: "cluster",
+        arity: -2,
+        flags: &[],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "cluster",
+        summary: "A container for Redis Cluster commands.",
+    },
+    CommandSpec {
+        name: "command",
+        arity: -1,
+        flags: &["loading", "stale"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "server",
+        summary: "Returns detailed information about all commands.",
+    },
+    CommandSpec {
+        name: "copy",
+        arity: -3,
+        flags: &["write", "denyoom"],
+        first_key: 1,
+        last_key: 2,
+        step: 1,
+        group: "generic",
+        summary: "Copies the value of a key to a new key.",
+    },
+    CommandSpec {
+        name: "debug",
+        arity: -2,
+        flags: &["admin", "noscript", "loading", "stale"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "server",
+        summary: "A container for debugging commands.",
+    },
+    CommandSpec {
+        name: "dump",
+        arity: 2,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "generic",
+        summary: "Returns a serialized representation of the value stored at a key.",
+    },
+    CommandSpec {
+        name: "expire",
+        arity: -3,
+        flags: &["write", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "generic",
+        summary: "Sets the expiration time of a key in seconds.",
+    },
+    CommandSpec {
+        name: "expireat",
+        arity: -3,
+        flags: &["write", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "generic",
+        summary: "Sets the expiration time of a key to a Unix timestamp.",
+    },
+    CommandSpec {
+        name: "expiretime",
+        arity: 2,
+        flags: &["readonly", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "generic",
+        summary: "Returns the expiration time of a key as a Unix timestamp.",
+    },
+    CommandSpec {
+        name: "flushall",
+        arity: -1,
+        flags: &["write"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "server",
+        summary: "Removes all keys.",
+    },
+    CommandSpec {
+        name: "get",
+        arity: 2,
+        flags: &["readonly", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "string",
+        summary: "Returns the string value of a key.",
+    },
+    CommandSpec {
+        name: "getdel",
+        arity: 2,
+        flags: &["write", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "string",
+        summary: "Returns the string value of a key after deleting the key.",
+    },
+    CommandSpec {
+        name: "getex",
+        arity: -2,
+        flags: &["write", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "string",
+        summary: "Returns the string value of a key after setting its expiration time.",
+    },
+    CommandSpec {
+        name: "getrange",
+        arity: 4,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "string",
+        summary: "Returns a substring of the string stored at a key.",
+    },
+    CommandSpec {
+        name: "hexpire",
+        arity: -6,
+        flags: &["write", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "hash",
+        summary: "Set expiry for hash field using relative time to expire (seconds).",
+    },
+    CommandSpec {
+        name: "hkeys",
+        arity: 2,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "hash",
+        summary: "Returns all fields in a hash.",
+    },
+    CommandSpec {
+        name: "hmget",
+        arity: -3,
+        flags: &["readonly", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "hash",
+        summary: "Returns the values of all fields in a hash.",
+    },
+    CommandSpec {
+        name: "hrandfield",
+        arity: -2,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "hash",
+        summary: "Returns one or more random fields from a hash.",
+    },
+    CommandSpec {
+        name: "hscan",
+        arity: -3,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "hash",
+        summary: "Iterates over fields and values of a hash.",
+    },
+    CommandSpec {
+        name: "hset",
+        arity: -4,
+        flags: &["write", "denyoom", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "hash",
+        summary: "Creates or modifies the value of a field in a hash.",
+    },
+    CommandSpec {
+        name: "hsetnx",
+        arity: 4,
+        flags: &["write", "denyoom", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "hash",
+        summary: "Sets the value of a field in a hash only when the field doesn't exist.",
+    },
+    CommandSpec {
+        name: "httl",
+        arity: -5,
+        flags: &["readonly", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "hash",
+        summary: "Returns the TTL in seconds of a hash field.",
+    },
+    CommandSpec {
+        name: "hvals",
+        arity: 2,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "hash",
+        summary: "Returns all values in a hash.",
+    },
+    CommandSpec {
+        name: "info",
+        arity: -1,
+        flags: &["loading", "stale"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "server",
+        summary: "Returns information and statistics about the server.",
+    },
+    CommandSpec {
+        name: "latency",
+        arity: -2,
+        flags: &[],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "server",
+        summary: "A container for latency diagnostics commands.",
+    },
+    CommandSpec {
+        name: "lcs",
+        arity: -3,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 2,
+        step: 1,
+        group: "string",
+        summary: "Finds the longest common substring.",
+    },
+    CommandSpec {
+        name: "lmpop",
+        arity: -4,
+        flags: &["write", "movablekeys"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "list",
+        summary: "Returns multiple elements from a list after removing them. Deletes the list if the last element was popped.",
+    },
+    CommandSpec {
+        name: "lpos",
+        arity: -3,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "list",
+        summary: "Returns the index of matching elements in a list.",
+    },
+    CommandSpec {
+        name: "lpush",
+        arity: -3,
+        flags: &["write", "denyoom", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "list",
+        summary: "Prepends one or more elements to a list. Creates the key if it doesn't exist.",
+    },
+    CommandSpec {
+        name: "lrange",
+        arity: 4,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "list",
+        summary: "Returns a range of elements from a list.",
+    },
+    CommandSpec {
+        name: "memory",
+        arity: -2,
+        flags: &[],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "server",
+        summary: "A container for memory diagnostics commands.",
+    },
+    CommandSpec {
+        name: "monitor",
+        arity: 1,
+        flags: &["admin", "noscript", "loading", "stale"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "server",
+        summary: "Listens for all requests received by the server in real-time.",
+    },
+    CommandSpec {
+        name: "object",
+        arity: -2,
+        flags: &[],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "generic",
+        summary: "A container for object introspection commands.",
+    },
+    CommandSpec {
+        name: "pexpire",
+        arity: -3,
+        flags: &["write", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "generic",
+        summary: "Sets the expiration time of a key in milliseconds.",
+    },
+    CommandSpec {
+        name: "pexpireat",
+        arity: -3,
+        flags: &["write", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "generic",
+        summary: "Sets the expiration time of a key to a Unix milliseconds timestamp.",
+    },
+    CommandSpec {
+        name: "pexpiretime",
+        arity: 2,
+        flags: &["readonly", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "generic",
+        summary: "Returns the expiration time of a key as a Unix milliseconds timestamp.",
+    },
+    CommandSpec {
+        name: "ping",
+        arity: -1,
+        flags: &["fast"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "connection",
+        summary: "Returns the server's liveliness response.",
+    },
+    CommandSpec {
+        name: "quit",
+        arity: -1,
+        flags: &["noscript", "loading", "stale", "fast"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "connection",
+        summary: "Closes the connection.",
+    },
+    CommandSpec {
+        name: "rename",
+        arity: 3,
+        flags: &["write"],
+        first_key: 1,
+        last_key: 2,
+        step: 1,
+        group: "generic",
+        summary: "Renames a key and overwrites the destination.",
+    },
+    CommandSpec {
+        name: "renamenx",
+        arity: 3,
+        flags: &["write", "fast"],
+        first_key: 1,
+        last_key: 2,
+        step: 1,
+        group: "generic",
+        summary: "Renames a key only when the target key name doesn't exist.",
+    },
+    CommandSpec {
+        name: "reset",
+        arity: 1,
+        flags: &["noscript", "loading", "stale", "fast"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "connection",
+        summary: "Resets the connection.",
+    },
+    CommandSpec {
+        name: "restore",
+        arity: -4,
+        flags: &["write", "denyoom"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "generic",
+        summary: "Creates a key from the serialized representation of a value.",
+    },
+    CommandSpec {
+        name: "rpush",
+        arity: -3,
+        flags: &["write", "denyoom", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "list",
+        summary: "Appends one or more elements to a list. Creates the key if it doesn't exist.",
+    },
+    CommandSpec {
+        name: "sadd",
+        arity: -3,
+        flags: &["write", "denyoom", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "set",
+        summary: "Adds one or more members to a set. Creates the key if it doesn't exist.",
+    },
+    CommandSpec {
+        name: "scan",
+        arity: -2,
+        flags: &["readonly"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "generic",
+        summary: "Iterates over the key names in the database.",
+    },
+    CommandSpec {
+        name: "set",
+        arity: -3,
+        flags: &["write", "denyoom"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "string",
+        summary: "Sets the string value of a key, ignoring its type. The key is created if it doesn't exist.",
+    },
+    CommandSpec {
+        name: "sinterstore",
+        arity: -3,
+        flags: &["write", "denyoom"],
+        first_key: 1,
+        last_key: -1,
+        step: 1,
+        group: "set",
+        summary: "Stores the intersect of multiple sets in a key.",
+    },
+    CommandSpec {
+        name: "smismember",
+        arity: -3,
+        flags: &["readonly", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "set",
+        summary: "Determines whether multiple members belong to a set.",
+    },
+    CommandSpec {
+        name: "sscan",
+        arity: -3,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "set",
+        summary: "Iterates over members of a set.",
+    },
+    CommandSpec {
+        name: "stats",
+        arity: 1,
+        flags: &["loading", "stale"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "server",
+        summary: "Returns the server statistics.",
+    },
+    CommandSpec {
+        name: "substr",
+        arity: 4,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "string",
+        summary: "Returns a substring from a string value.",
+    },
+    CommandSpec {
+        name: "time",
+        arity: 1,
+        flags: &["loading", "stale", "fast"],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "server",
+        summary: "Returns the server time.",
+    },
+    CommandSpec {
+        name: "touch",
+        arity: -2,
+        flags: &["readonly", "fast"],
+        first_key: 1,
+        last_key: -1,
+        step: 1,
+        group: "generic",
+        summary: "Returns the number of existing keys out of those specified after updating the time they were last accessed.",
+    },
+    CommandSpec {
+        name: "unlink",
+        arity: -2,
+        flags: &["write", "fast"],
+        first_key: 1,
+        last_key: -1,
+        step: 1,
+        group: "generic",
+        summary: "Asynchronously deletes one or more keys.",
+    },
+    CommandSpec {
+        name: "wait",
+        arity: 3,
+        flags: &[],
+        first_key: 0,
+        last_key: 0,
+        step: 0,
+        group: "generic",
+        summary: "Blocks until the asynchronous replication of all preceding write commands sent by the connection is completed.",
+    },
+    CommandSpec {
+        name: "zadd",
+        arity: -4,
+        flags: &["write", "denyoom", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "sorted-set",
+        summary: "Adds one or more members to a sorted set, or updates their scores. Creates the key if it doesn't exist.",
+    },
+    CommandSpec {
+        name: "zcard",
+        arity: 2,
+        flags: &["readonly", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "sorted-set",
+        summary: "Returns the number of members in a sorted set.",
+    },
+    CommandSpec {
+        name: "zincrby",
+        arity: 4,
+        flags: &["write", "denyoom", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "sorted-set",
+        summary: "Increments the score of a member in a sorted set.",
+    },
+    CommandSpec {
+        name: "zrangebylex",
+        arity: -4,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "sorted-set",
+        summary: "Returns members in a sorted set within a lexicographical range.",
+    },
+    CommandSpec {
+        name: "zrangebyscore",
+        arity: -4,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "sorted-set",
+        summary: "Returns members in a sorted set within a range of scores.",
+    },
+    CommandSpec {
+        name: "zrank",
+        arity: -3,
+        flags: &["readonly", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "sorted-set",
+        summary: "Returns the index of a member in a sorted set ordered by ascending scores.",
+    },
+    CommandSpec {
+        name: "zrem",
+        arity: -3,
+        flags: &["write", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "sorted-set",
+        summary: "Removes one or more members from a sorted set. Deletes the sorted set if all members were removed.",
+    },
+    CommandSpec {
+        name: "zrevrank",
+        arity: -3,
+        flags: &["readonly", "fast"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "sorted-set",
+        summary: "Returns the index of a member in a sorted set ordered by descending scores.",
+    },
+    CommandSpec {
+        name: "zscan",
+        arity: -3,
+        flags: &["readonly"],
+        first_key: 1,
+        last_key: 1,
+        step: 1,
+        group: "sorted-set",
+        summary: "Iterates over members and scores of a sorted set.",
+    },
+];
+
+impl CommandInfo {
+    /// Creates a new `CommandInfo` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the COMMAND command.
+    ///   The first argument, if any, is the subcommand name.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CommandInfo)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<CommandInfo, CommandError> {
+        let mut strs: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let subcommand = match strs.first().map(|s| s.to_lowercase()) {
+            None => CommandSubcommand::Info(vec![]),
+            Some(s) if s == "count" => {
+                if strs.len() != 1 {
+                    return Err(CommandError::WrongArity("command|count"));
+                }
+                CommandSubcommand::Count
+            }
+            Some(s) if s == "docs" => CommandSubcommand::Docs(strs.split_off(1)),
+            Some(s) if s == "info" => CommandSubcommand::Info(strs.split_off(1)),
+            Some(s) if s == "help" => {
+                if strs.len() != 1 {
+                    return Err(CommandError::WrongArity("command|help"));
+                }
+                CommandSubcommand::Help
+            }
+            Some(s) => {
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'COMMAND' command",
+                    s
+                )));
+            }
+        };
+
+        Ok(CommandInfo { subcommand })
+    }
+
+    /// Looks up the given command names in the command table. Names which don't match a
+    /// supported command are skipped. Every command is returned if no names are given.
+    fn lookup(names: &[String]) -> Vec<&'static CommandSpec> {
+        if names.is_empty() {
+            return COMMAND_TABLE.iter().collect();
+        }
+
+        names
+            .iter()
+            .filter_map(|name| {
+                let name = name.to_lowercase();
+                COMMAND_TABLE.iter().find(|spec| spec.name == name)
+            })
+            .collect()
+    }
+
+    /// Describes a command the way Redis does in the reply to COMMAND INFO: its name, arity,
+    /// flags and key positions, followed by the ACL categories, tips, key specifications and
+    /// subcommands, which are always empty in MuDB.
+    fn info(spec: &CommandSpec) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(spec.name.to_string()),
+            RespType::Integer(spec.arity),
+            RespType::Array(
+                spec.flags
+                    .iter()
+                    .map(|flag| RespType::SimpleString(flag.to_string()))
+                    .collect(),
+            ),
+            RespType::Integer(spec.first_key),
+            RespType::Integer(spec.last_key),
+            RespType::Integer(spec.step),
+            RespType::Array(vec![]),
+            RespType::Array(vec![]),
+            RespType::Array(vec![]),
+            RespType::Array(vec![]),
+        ])
+    }
+
+    /// Describes a command the way Redis does in the reply to COMMAND DOCS, as a flattened
+    /// map of its summary and group.
+    fn docs(spec: &CommandSpec) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(String::from("summary")),
+            RespType::BulkString(spec.summary.to_string()),
+            RespType::BulkString(String::from("group")),
+            RespType::BulkString(spec.group.to_string()),
+        ])
+    }
+
+    /// Executes the COMMAND command.
+    ///
+    /// # Returns
+    ///
+    /// - COUNT - The number of supported commands as an `Integer`
+    /// - DOCS - A flattened map of the command names to their documentation, as an `Array`
+    /// - INFO - An `Array` with the description of each command
+    /// - HELP - An `Array` of `SimpleString` lines describing the subcommands
+    ///
+    /// Names which aren't supported commands are left out of the DOCS and INFO replies.
+    pub fn apply(&self) -> RespType {
+        match &self.subcommand {
+            CommandSubcommand::Count => RespType::Integer(COMMAND_TABLE.len() as i64),
+            CommandSubcommand::Docs(names) => RespType::Array(
+                Self::lookup(names)
+                    .into_iter()
+                    .flat_map(|spec| {
+                        [
+                            RespType::BulkString(spec.name.to_string()),
+                            Self::docs(spec),
+                        ]
+                    })
+                    .collect(),
+            ),
+            CommandSubcommand::Info(names) => {
+                RespType::Array(Self::lookup(names).into_iter().map(Self::info).collect())
+            }
+            CommandSubcommand::Help => help_reply(
+                "COMMAND",
+                &[
+                    ("(no subcommand)", "Return details about all commands."),
+                    ("COUNT", "Return the total number of commands."),
+                    (
+                        "DOCS [<command-name> ...]",
+                        "Return documentary information about the given commands, or all commands.",
+                    ),
+                    (
+                        "INFO [<command-name> ...]",
+                        "Return details about the given commands, or all commands.",
+                    ),
+                ],
+            ),
+        }
+    }
+}
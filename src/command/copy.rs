@@ -0,0 +1,83 @@
+// src/command/copy.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the COPY command in MuDB.
+///
+/// The COPY command copies the value stored at a source key to a destination key.
+#[derive(Debug, Clone)]
+pub struct Copy {
+    src: String,
+    dst: String,
+    /// Whether an existing destination key should be overwritten.
+    replace: bool,
+}
+
+impl Copy {
+    /// Creates a new `Copy` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the COPY command.
+    ///   The source and destination keys can be followed by the optional `REPLACE` keyword.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Copy)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Copy, CommandError> {
+        if args.len() < 2 || args.len() > 3 {
+            return Err(CommandError::WrongArity("copy"));
+        }
+
+        // parse source and destination keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args[..2].iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+        let dst = keys.pop().unwrap();
+        let src = keys.pop().unwrap();
+
+        if src == dst {
+            return Err(CommandError::Other(String::from(
+                "ERR source and destination objects are the same",
+            )));
+        }
+
+        // parse REPLACE option
+        let replace = match args.get(2) {
+            None => false,
+            Some(RespType::BulkString(opt)) if opt.eq_ignore_ascii_case("replace") => true,
+            Some(_) => return Err(CommandError::Other(String::from("ERR syntax error"))),
+        };
+
+        Ok(Copy { src, dst, replace })
+    }
+
+    /// Executes the COPY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(1)` if the value was copied
+    /// - `Integer(0)` if the source key is missing, or the destination exists and `REPLACE` wasn't given
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.copy(&self.src, &self.dst, self.replace) {
+            Ok(copied) => RespType::Integer(copied as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+// src/command/debug.rs
+
+use std::time::Duration;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{help::help_reply, CommandError};
+
+/// Represents the DEBUG command in MuDB.
+///
+/// The DEBUG command provides hooks for testing the server. Since it can be used to
+/// stall connections, it can only be executed when the server is started with
+/// `--enable-debug-command`.
+#[derive(Debug, Clone)]
+pub struct Debug {
+    subcommand: DebugSubcommand,
+}
+
+/// The subcommands supported by the DEBUG command.
+#[derive(Debug, Clone)]
+enum DebugSubcommand {
+    /// DEBUG SLEEP seconds
+    Sleep(Duration),
+    /// DEBUG OBJECT key
+    Object(String),
+    /// DEBUG SET-ACTIVE-EXPIRE 0|1
+    SetActiveExpire(bool),
+    /// DEBUG HELP
+    Help,
+    /// Any other subcommand, which is only accepted if it is in the server's allowlist
+    /// of no-op subcommands. Holds the lowercased subcommand name.
+    Other(String),
+}
+
+impl Debug {
+    /// Creates a new `Debug` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the DEBUG command.
+    ///   The first argument is the subcommand name.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Debug)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Debug, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::WrongArity("debug"));
+        }
+
+        // parse subcommand
+        let subcommand = match &args[0] {
+            RespType::BulkString(s) => s.to_lowercase(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Subcommand must be a bulk string",
+                )));
+            }
+        };
+
+        let subcommand = match subcommand.as_str() {
+            "sleep" => {
+                if args.len() != 2 {
+                    return Err(CommandError::WrongArity("debug|sleep"));
+                }
+                let secs = match &args[1] {
+                    RespType::BulkString(s) => s.parse::<f64>(),
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "Invalid argument. Value must be a number in bulk string format",
+                        )));
+                    }
+                };
+                match secs {
+                    Ok(secs) if secs.is_finite() && secs >= 0.0 => {
+                        DebugSubcommand::Sleep(Duration::from_secs_f64(secs))
+                    }
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "ERR value is not a valid float",
+                        )));
+                    }
+                }
+            }
+            "object" => {
+                if args.len() != 2 {
+                    return Err(CommandError::WrongArity("debug|object"));
+                }
+                match &args[1] {
+                    RespType::BulkString(k) => DebugSubcommand::Object(k.to_string()),
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "Invalid argument. Key must be a bulk string",
+                        )));
+                    }
+                }
+            }
+            "set-active-expire" => {
+                if args.len() != 2 {
+                    return Err(CommandError::WrongArity("debug|set-active-expire"));
+                }
+                match &args[1] {
+                    RespType::BulkString(flag) if flag == "0" || flag == "1" => {
+                        DebugSubcommand::SetActiveExpire(flag == "1")
+                    }
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "ERR value is not an integer or out of range",
+                        )));
+                    }
+                }
+            }
+            "help" => {
+                if args.len() != 1 {
+                    return Err(CommandError::WrongArity("debug|help"));
+                }
+                DebugSubcommand::Help
+            }
+            _ => DebugSubcommand::Other(subcommand),
+        };
+
+        Ok(Debug { subcommand })
+    }
+
+    /// Returns `true` if the subcommand is one of the given no-op subcommands, which are
+    /// acknowledged without doing anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `noop_subcommands` - Lowercased names of the subcommands which are accepted as no-ops.
+    pub fn is_noop(&self, noop_subcommands: &[String]) -> bool {
+        match &self.subcommand {
+            DebugSubcommand::Other(name) => noop_subcommands.contains(name),
+            _ => false,
+        }
+    }
+
+    /// Executes the DEBUG command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - SLEEP - `SimpleString("OK")` once the given time has elapsed. Only the calling
+    ///   connection is paused, other connections keep being served.
+    /// - OBJECT - A `SimpleString` with internal details of the key, or a `SimpleError` if the key
+    ///   doesn't exist. Collections also report their number of elements, and lists stored as a
+    ///   quicklist the `ql_*` fields of Redis. The format is meant for tests and may change
+    ///   between versions.
+    /// - SET-ACTIVE-EXPIRE - `SimpleString("OK")`, once the background removal of expired keys
+    ///   has been turned on or off. While it is off, keys are only expired when they are accessed.
+    /// - HELP - An `Array` of `SimpleString` lines describing the subcommands
+    /// - Other subcommands - A `SimpleError`. Subcommands in the no-op allowlist are
+    ///   acknowledged by the connection handler instead, see `Debug::is_noop`.
+    pub async fn apply(&self, db: &DB) -> RespType {
+        match &self.subcommand {
+            DebugSubcommand::Sleep(duration) => {
+                tokio::time::sleep(*duration).await;
+                RespType::SimpleString(String::from("OK"))
+            }
+            DebugSubcommand::Object(key) => match db.debug_object(key) {
+                Ok(Some(details)) => {
+                    let mut report = format!(
+                        "encoding:{} serializedlength:{} lru_seconds_idle:{} ttl_ms:{}",
+                        details.encoding,
+                        details.serialized_length,
+                        details.idle_time.as_secs(),
+                        details.ttl.map_or(-1, |ttl| ttl.as_millis() as i64),
+                    );
+                    if let Some(elements) = details.elements {
+                        report.push_str(&format!(" elements:{}", elements));
+                    }
+                    // Same fields as Redis. A quicklist keeps all its elements in a single
+                    // buffer, so it is reported as one node which is never compressed.
+                    if let (Some(listpack_max), Some(elements)) =
+                        (details.quicklist_listpack_max, details.elements)
+                    {
+                        report.push_str(&format!(
+                            " ql_nodes:1 ql_avg_node:{:.2} ql_listpack_max:{} ql_compressed:0",
+                            elements as f64, listpack_max
+                        ));
+                    }
+                    RespType::SimpleString(report)
+                }
+                Ok(None) => RespType::SimpleError(String::from("ERR no such key")),
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+            DebugSubcommand::SetActiveExpire(enabled) => {
+                db.set_active_expire(*enabled);
+                RespType::SimpleString(String::from("OK"))
+            }
+            DebugSubcommand::Help => help_reply(
+                "DEBUG",
+                &[
+                    (
+                        "SLEEP <seconds>",
+                        "Pause the current connection for <seconds>, which may be fractional.",
+                    ),
+                    (
+                        "OBJECT <key>",
+                        "Show low level info about <key> and the value it holds.",
+                    ),
+                    (
+                        "SET-ACTIVE-EXPIRE <0|1>",
+                        "Turn the background removal of expired keys off (0) or on (1).",
+                    ),
+                ],
+            ),
+            DebugSubcommand::Other(name) => {
+                RespType::SimpleError(format!("ERR Unknown subcommand '{}' for 'DEBUG' command", name))
+            }
+        }
+    }
+}
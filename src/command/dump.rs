@@ -0,0 +1,64 @@
+// src/command/dump.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the DUMP command in MuDB.
+///
+/// The DUMP command returns a serialization of the value stored against a key,
+/// which can be stored back with the RESTORE command.
+#[derive(Debug, Clone)]
+pub struct Dump {
+    /// Key whose value is serialized
+    key: String,
+}
+
+impl Dump {
+    /// Creates a new `Dump` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the DUMP command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Dump)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Dump, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::WrongArity("dump"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(Dump { key })
+    }
+
+    /// Executes the DUMP command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - If key is present in DB - The serialized value as a `BulkString`
+    /// - If key is not found in DB - A `NullBulkString`
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.dump(&self.key) {
+            Ok(Some(payload)) => RespType::BulkString(payload),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+// src/command/expire.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{ExpireCondition, DB},
+};
+
+use super::CommandError;
+
+/// Represents the EXPIRE command in MuDB.
+///
+/// The EXPIRE command sets a time to live (in seconds) after which the key is deleted.
+#[derive(Debug, Clone)]
+pub struct Expire {
+    key: String,
+    /// Time to live of the key, in milliseconds.
+    ttl_ms: i64,
+    /// The conditions under which the expiry time is updated.
+    condition: ExpireCondition,
+}
+
+impl Expire {
+    /// Creates a new `Expire` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the EXPIRE command.
+    ///   The key and the number of seconds can be followed by the `NX`, `XX`, `GT` and `LT` options.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Expire)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Expire, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::WrongArity("expire"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse time to live
+        let ttl = match &args[1] {
+            RespType::BulkString(t) => t.parse::<i64>(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+        };
+        let ttl_ms = match ttl {
+            Ok(t) => match t.checked_mul(1000) {
+                Some(ttl_ms) => ttl_ms,
+                None => {
+                    return Err(CommandError::Other(String::from(
+                        "ERR invalid expire time in 'expire' command",
+                    )));
+                }
+            },
+            Err(_) => {
+                return Err(CommandError::Other(String::from(
+                    "ERR value is not an integer or out of range",
+                )));
+            }
+        };
+
+        let condition = Self::parse_condition(&args[2..])?;
+
+        Ok(Expire {
+            key,
+            ttl_ms,
+            condition,
+        })
+    }
+
+    /// Parses the `NX`, `XX`, `GT` and `LT` options shared by the commands of the EXPIRE family.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The options following the key and the expiry time.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ExpireCondition)` if parsing succeeds.
+    /// * `Err(CommandError)` if an option is unknown, or the options can't be combined.
+    pub(super) fn parse_condition(args: &[RespType]) -> Result<ExpireCondition, CommandError> {
+        let mut condition = ExpireCondition::default();
+        for arg in args.iter() {
+            let opt = match arg {
+                RespType::BulkString(opt) => opt,
+                _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+            };
+
+            match opt.to_lowercase().as_str() {
+                "nx" => condition.nx = true,
+                "xx" => condition.xx = true,
+                "gt" => condition.gt = true,
+                "lt" => condition.lt = true,
+                _ => {
+                    return Err(CommandError::Other(format!(
+                        "ERR Unsupported option {}",
+                        opt
+                    )));
+                }
+            }
+        }
+
+        if condition.nx && (condition.xx || condition.gt || condition.lt) {
+            return Err(CommandError::Other(String::from(
+                "ERR NX and XX, GT or LT options at the same time are not compatible",
+            )));
+        }
+        if condition.gt && condition.lt {
+            return Err(CommandError::Other(String::from(
+                "ERR GT and LT options at the same time are not compatible",
+            )));
+        }
+
+        Ok(condition)
+    }
+
+    /// Executes the EXPIRE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(1)` if the expiry was set, or the key was deleted because the time to live isn't positive
+    /// - `Integer(0)` if the key doesn't exist, or the expiry wasn't changed because of the given options
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.expire(&self.key, self.ttl_ms, self.condition) {
+            Ok(updated) => RespType::Integer(updated as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// src/command/expiretime.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the EXPIRETIME command in MuDB.
+///
+/// The EXPIRETIME command returns the absolute Unix timestamp (in seconds) at which a key expires.
+#[derive(Debug, Clone)]
+pub struct ExpireTime {
+    key: String,
+}
+
+impl ExpireTime {
+    /// Creates a new `ExpireTime` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the EXPIRETIME command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ExpireTime)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ExpireTime, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::WrongArity("expiretime"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(ExpireTime { key })
+    }
+
+    /// Executes the EXPIRETIME command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - The Unix time in seconds at which the key expires, as an `Integer`
+    /// - `Integer(-1)` if the key exists but doesn't expire
+    /// - `Integer(-2)` if the key doesn't exist
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.expire_time(&self.key) {
+            Ok(Some(Some(unix_ms))) => RespType::Integer(unix_ms.div_euclid(1000)),
+            Ok(Some(None)) => RespType::Integer(-1),
+            Ok(None) => RespType::Integer(-2),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// src/command/flushall.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the FLUSHALL command in MuDB.
+///
+/// The FLUSHALL command deletes all the keys on the server.
+#[derive(Debug, Clone)]
+pub struct FlushAll {
+    /// Whether the deleted keys are freed in the background (ASYNC) rather than before replying (SYNC).
+    lazy: bool,
+}
+
+impl FlushAll {
+    /// Creates a new `FlushAll` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the FLUSHALL command.
+    ///   Accepts an optional `ASYNC` or `SYNC` keyword.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FlushAll)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<FlushAll, CommandError> {
+        if args.len() > 1 {
+            return Err(CommandError::WrongArity("flushall"));
+        }
+
+        // parse ASYNC / SYNC option
+        let lazy = match args.first() {
+            None => false,
+            Some(RespType::BulkString(opt)) if opt.eq_ignore_ascii_case("async") => true,
+            Some(RespType::BulkString(opt)) if opt.eq_ignore_ascii_case("sync") => false,
+            Some(_) => return Err(CommandError::Other(String::from("ERR syntax error"))),
+        };
+
+        Ok(FlushAll { lazy })
+    }
+
+    /// Executes the FLUSHALL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `SimpleString("OK")` once the keys are deleted
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.flush_all(self.lazy) {
+            Ok(()) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -27,10 +27,8 @@ impl Get {
     /// * `Ok(Get)` - If parsing succeeds and the key is valid.
     /// * `Err(CommandError)` - if parsing fails due to validation errors.
     pub fn with_args(args: Vec<RespType>) -> Result<Get, CommandError> {
-        if args.len() < 1 {
-            return Err(CommandError::Other(String::from(
-                "Wrong number of arguments specified for 'GET' command",
-            )));
+        if args.len() != 1 {
+            return Err(CommandError::WrongArity("get"));
         }
 
         // parse key
@@ -0,0 +1,63 @@
+// src/command/getdel.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the GETDEL command in MuDB.
+///
+/// The GETDEL command returns the string value stored against a key and deletes the key.
+#[derive(Debug, Clone)]
+pub struct GetDel {
+    /// Key to be fetched and deleted
+    key: String,
+}
+
+impl GetDel {
+    /// Creates a new `GetDel` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GETDEL command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GetDel)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GetDel, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::WrongArity("getdel"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(GetDel { key })
+    }
+
+    /// Executes the GETDEL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - If key is present in DB - The deleted value of the key as a `BulkString`
+    /// - If key is not found in DB - A `NullBulkString`
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.getdel(&self.key) {
+            Ok(Some(s)) => RespType::BulkString(s),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
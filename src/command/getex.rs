@@ -0,0 +1,89 @@
+// src/command/getex.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{ExpiryOp, DB},
+};
+
+use super::{
+    ttl::{parse_ttl, TtlUnit},
+    CommandError,
+};
+
+/// Represents the GETEX command in MuDB.
+///
+/// The GETEX command returns the string value stored against a key, and optionally
+/// changes the expiry time of the key.
+#[derive(Debug, Clone)]
+pub struct GetEx {
+    key: String,
+    /// How the expiry time of the key is changed (`EX`, `PX` or `PERSIST` option).
+    expiry_op: ExpiryOp,
+}
+
+impl GetEx {
+    /// Creates a new `GetEx` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GETEX command.
+    ///   The key can be followed by one of `EX seconds`, `PX milliseconds` or `PERSIST`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GetEx)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GetEx, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::WrongArity("getex"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse expiry option
+        let opt = match args.get(1) {
+            None => None,
+            Some(RespType::BulkString(opt)) => Some(opt.to_lowercase()),
+            Some(_) => return Err(CommandError::Other(String::from("ERR syntax error"))),
+        };
+
+        let expiry_op = match (opt.as_deref(), args.len()) {
+            (None, _) => ExpiryOp::Keep,
+            (Some("persist"), 2) => ExpiryOp::Persist,
+            (Some("ex"), 3) => ExpiryOp::ExpireIn(parse_ttl(&args[2], TtlUnit::Seconds, "getex")?),
+            (Some("px"), 3) => {
+                ExpiryOp::ExpireIn(parse_ttl(&args[2], TtlUnit::Milliseconds, "getex")?)
+            }
+            _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+        };
+
+        Ok(GetEx { key, expiry_op })
+    }
+
+    /// Executes the GETEX command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - If key is present in DB - Value of the key as a `BulkString`
+    /// - If key is not found in DB - A `NullBulkString`
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.getex(&self.key, self.expiry_op) {
+            Ok(Some(s)) => RespType::BulkString(s),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// src/command/getrange.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the GETRANGE command in MuDB, and its older name SUBSTR.
+///
+/// The GETRANGE command returns the substring of the string value stored at a key
+/// between two byte offsets, both included.
+#[derive(Debug, Clone)]
+pub struct GetRange {
+    key: String,
+    start: i64,
+    end: i64,
+}
+
+impl GetRange {
+    /// Creates a new `GetRange` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command:
+    ///   the key and the start and end offsets.
+    ///
+    /// * `cmd` - The lowercased name the command was called with, `getrange` or `substr`,
+    ///   for the arity error.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GetRange)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, cmd: &'static str) -> Result<GetRange, CommandError> {
+        if args.len() != 3 {
+            return Err(CommandError::WrongArity(cmd));
+        }
+
+        let mut strs: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key and offsets must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let (start, end) = match (strs[1].parse::<i64>(), strs[2].parse::<i64>()) {
+            (Ok(start), Ok(end)) => (start, end),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "ERR value is not an integer or out of range",
+                )));
+            }
+        };
+
+        Ok(GetRange {
+            key: strs[0].clone(),
+            start,
+            end,
+        })
+    }
+
+    /// Executes the GETRANGE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - The substring as a `BulkString`, empty if the key doesn't exist
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.getrange(&self.key, self.start, self.end) {
+            Ok(s) => RespType::BulkString(s),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
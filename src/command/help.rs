@@ -0,0 +1,33 @@
+// src/command/help.rs
+
+use crate::resp::types::RespType;
+
+/// Builds the reply to the HELP subcommand of a command with subcommands, in the same
+/// layout as Redis: a header line, then the usage of each subcommand followed by an
+/// indented description. HELP itself is always listed last.
+///
+/// # Arguments
+///
+/// * `cmd` - The uppercased name of the command.
+///
+/// * `subcommands` - The `(usage, description)` pair of each subcommand, in display order.
+///
+/// # Returns
+///
+/// An `Array` of `SimpleString` lines.
+pub fn help_reply(cmd: &str, subcommands: &[(&str, &str)]) -> RespType {
+    let mut lines = vec![format!(
+        "{} <subcommand> [<arg> [value] [opt] ...]. Subcommands are:",
+        cmd
+    )];
+
+    for (usage, description) in subcommands
+        .iter()
+        .chain([("HELP", "Print this help.")].iter())
+    {
+        lines.push(usage.to_string());
+        lines.push(format!("    {}", description));
+    }
+
+    RespType::Array(lines.into_iter().map(RespType::SimpleString).collect())
+}
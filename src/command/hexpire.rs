@@ -0,0 +1,171 @@
+// src/command/hexpire.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{ExpireCondition, DB},
+};
+
+use super::{expire::Expire, CommandError};
+
+/// Largest time to live accepted for a field, in milliseconds. Redis caps the expiry time of
+/// fields at 2^48 - 1 milliseconds, so the same bound is applied to the time to live.
+const MAX_FIELD_TTL_MS: i64 = (1 << 48) - 1;
+
+/// Represents the HEXPIRE command in MuDB.
+///
+/// The HEXPIRE command sets a time to live (in seconds) on fields of a hash, after which
+/// the fields are deleted.
+#[derive(Debug, Clone)]
+pub struct HExpire {
+    key: String,
+    /// Time to live of the fields, in milliseconds.
+    ttl_ms: i64,
+    /// The conditions under which the expiry time of each field is updated.
+    condition: ExpireCondition,
+    fields: Vec<String>,
+}
+
+impl HExpire {
+    /// Creates a new `HExpire` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HEXPIRE command.
+    ///   The key and the number of seconds can be followed by the `NX`, `XX`, `GT` and `LT`
+    ///   options, and then by `FIELDS numfields field [field ...]`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HExpire)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HExpire, CommandError> {
+        if args.len() < 5 {
+            return Err(CommandError::WrongArity("hexpire"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse time to live. Unlike EXPIRE, a time to live of 0 deletes the fields and a
+        // negative one is rejected.
+        let ttl = match &args[1] {
+            RespType::BulkString(t) => t.parse::<i64>().map_err(|_| {
+                CommandError::Other(String::from("ERR value is not an integer or out of range"))
+            })?,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+        };
+        let ttl_ms = match ttl.checked_mul(1000) {
+            Some(ttl_ms) if (0..=MAX_FIELD_TTL_MS).contains(&ttl_ms) => ttl_ms,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "ERR invalid expire time in 'hexpire' command",
+                )));
+            }
+        };
+
+        // the options, if any, are followed by FIELDS
+        let fields_idx = args[2..]
+            .iter()
+            .position(
+                |arg| matches!(arg, RespType::BulkString(s) if s.eq_ignore_ascii_case("fields")),
+            )
+            .map(|i| i + 2);
+        let fields_idx = match fields_idx {
+            Some(i) => i,
+            None => {
+                return Err(CommandError::Other(String::from(
+                    "ERR Mandatory argument FIELDS is missing or not at the right position",
+                )));
+            }
+        };
+        let condition = Expire::parse_condition(&args[2..fields_idx])?;
+        let fields = Self::parse_fields(&args[fields_idx + 1..])?;
+
+        Ok(HExpire {
+            key,
+            ttl_ms,
+            condition,
+            fields,
+        })
+    }
+
+    /// Parses the `numfields field [field ...]` arguments which follow `FIELDS` in the commands
+    /// setting or reading the expiry time of hash fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The arguments following `FIELDS`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - The fields.
+    /// * `Err(CommandError)` - if the number of fields isn't positive, or doesn't match the
+    ///   number of fields given.
+    pub(super) fn parse_fields(args: &[RespType]) -> Result<Vec<String>, CommandError> {
+        let mut strs: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Fields must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let numfields = match strs.first().map(|n| n.parse::<i64>()) {
+            Some(Ok(n)) if n > 0 => n as usize,
+            Some(Ok(_)) => {
+                return Err(CommandError::Other(String::from(
+                    "ERR Parameter `numFields` should be greater than 0",
+                )));
+            }
+            Some(Err(_)) => {
+                return Err(CommandError::Other(String::from(
+                    "ERR value is not an integer or out of range",
+                )));
+            }
+            None => return Err(CommandError::Other(String::from("ERR syntax error"))),
+        };
+        if numfields != strs.len() - 1 {
+            return Err(CommandError::Other(String::from(
+                "ERR The `numfields` parameter must match the number of arguments",
+            )));
+        }
+
+        Ok(strs.split_off(1))
+    }
+
+    /// Executes the HEXPIRE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - An `Array` with an `Integer` for each field, in the order they were given:
+    ///   - `-2` if the field or the key doesn't exist
+    ///   - `0` if the expiry time wasn't changed because of the given options
+    ///   - `1` if the expiry time was set
+    ///   - `2` if the field was deleted, because the time to live is 0
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hexpire(&self.key, self.ttl_ms, self.condition, &self.fields) {
+            Ok(outcomes) => RespType::Array(outcomes.into_iter().map(RespType::Integer).collect()),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// src/command/hkeys.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the HKEYS command in MuDB.
+///
+/// The HKEYS command returns all the field names of the hash stored at a key.
+#[derive(Debug, Clone)]
+pub struct HKeys {
+    key: String,
+}
+
+impl HKeys {
+    /// Creates a new `HKEYS` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HKEYS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HKeys)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HKeys, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::WrongArity("hkeys"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(HKeys { key })
+    }
+
+    /// Executes the HKEYS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` of the field names of the hash. Empty if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hkeys(&self.key) {
+            Ok(items) => RespType::Array(items.into_iter().map(RespType::BulkString).collect()),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
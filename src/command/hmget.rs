@@ -0,0 +1,80 @@
+// src/command/hmget.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the HMGET command in MuDB.
+#[derive(Debug, Clone)]
+pub struct HMGet {
+    key: String,
+    fields: Vec<String>,
+}
+
+impl HMGet {
+    /// Creates a new `HMGET` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HMGET command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HMGet)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HMGet, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::WrongArity("hmget"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse fields
+        let mut fields: Vec<String> = vec![];
+        for arg in args[1..].iter() {
+            match arg {
+                RespType::BulkString(f) => fields.push(f.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Field must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(HMGet { key, fields })
+    }
+
+    /// Executes the HMGET command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` with the value of each requested field as a `BulkString`, or a
+    /// `NullBulkString` for fields (or keys) which don't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hmget(&self.key, &self.fields) {
+            Ok(values) => RespType::Array(
+                values
+                    .into_iter()
+                    .map(|v| match v {
+                        Some(v) => RespType::BulkString(v),
+                        None => RespType::NullBulkString,
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
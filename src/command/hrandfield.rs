@@ -0,0 +1,121 @@
+// src/command/hrandfield.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the HRANDFIELD command in MuDB.
+///
+/// The HRANDFIELD command returns random fields from the hash stored at a key.
+#[derive(Debug, Clone)]
+pub struct HRandField {
+    key: String,
+    /// Number of fields to return. Negative counts allow the same field to be returned
+    /// more than once. `None` if only a single field is requested.
+    count: Option<i64>,
+    /// Whether each field is followed by its value in the reply.
+    with_values: bool,
+}
+
+impl HRandField {
+    /// Creates a new `HRandField` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HRANDFIELD command.
+    ///   The key can be followed by a count, which can in turn be followed by `WITHVALUES`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HRandField)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HRandField, CommandError> {
+        if args.is_empty() || args.len() > 3 {
+            return Err(CommandError::WrongArity("hrandfield"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse count
+        let count = match args.get(1) {
+            None => None,
+            Some(RespType::BulkString(c)) => match c.parse::<i64>() {
+                // Like in Redis, so that the number of elements of the reply, which is twice
+                // the count with WITHVALUES, can't overflow.
+                Ok(c) if c < -(i64::MAX / 2) => {
+                    return Err(CommandError::Other(String::from(
+                        "ERR value is out of range",
+                    )));
+                }
+                Ok(c) => Some(c),
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "ERR value is not an integer or out of range",
+                    )));
+                }
+            },
+            Some(_) => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Count must be an integer in bulk string format",
+                )));
+            }
+        };
+
+        // parse WITHVALUES option
+        let with_values = match args.get(2) {
+            None => false,
+            Some(RespType::BulkString(opt)) if opt.eq_ignore_ascii_case("withvalues") => true,
+            Some(_) => return Err(CommandError::Other(String::from("ERR syntax error"))),
+        };
+
+        Ok(HRandField {
+            key,
+            count,
+            with_values,
+        })
+    }
+
+    /// Executes the HRANDFIELD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - Without a count - A random field as a `BulkString`, or a `NullBulkString` if the key doesn't exist
+    /// - With a count - An `Array` of fields, empty if the key doesn't exist. With WITHVALUES,
+    ///   each field is followed by its value. A negative count asking for more than about a
+    ///   million fields is rejected, since the reply is built in memory.
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        let fields = match db.hrandfield(&self.key, self.count.unwrap_or(1)) {
+            Ok(fields) => fields,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        if self.count.is_none() {
+            return match fields.into_iter().next() {
+                Some((field, _)) => RespType::BulkString(field),
+                None => RespType::NullBulkString,
+            };
+        }
+
+        let mut reply: Vec<RespType> = vec![];
+        for (field, value) in fields {
+            reply.push(RespType::BulkString(field));
+            if self.with_values {
+                reply.push(RespType::BulkString(value));
+            }
+        }
+        RespType::Array(reply)
+    }
+}
@@ -0,0 +1,57 @@
+// src/command/hscan.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{scan_args::ScanArgs, CommandError};
+
+/// Represents the HSCAN command in MuDB.
+///
+/// The HSCAN command iterates the hash stored at a key with a cursor, a page at a time.
+#[derive(Debug, Clone)]
+pub struct HScan {
+    args: ScanArgs,
+}
+
+impl HScan {
+    /// Creates a new `HScan` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HSCAN command:
+    ///   the key, the cursor, and the optional MATCH and COUNT options.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HScan)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HScan, CommandError> {
+        let args = ScanArgs::parse(args, "hscan")?;
+        Ok(HScan { args })
+    }
+
+    /// Executes the HSCAN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - An `Array` of the next cursor as a `BulkString` ("0" once the iteration is complete)
+    ///   and an `Array` of the fields and their values of the page
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        let args = &self.args;
+        match db.hscan(&args.key, args.cursor, args.pattern.as_deref(), args.count) {
+            Ok((next, page)) => RespType::Array(vec![
+                RespType::BulkString(next.to_string()),
+                RespType::Array(
+                    page.into_iter()
+                        .flat_map(|(f, v)| [RespType::BulkString(f), RespType::BulkString(v)])
+                        .collect(),
+                ),
+            ]),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
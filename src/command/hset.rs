@@ -0,0 +1,74 @@
+// src/command/hset.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the HSET command in MuDB.
+#[derive(Debug, Clone)]
+pub struct HSet {
+    key: String,
+    fields: Vec<(String, String)>,
+}
+
+impl HSet {
+    /// Creates a new `HSET` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HSET command.
+    ///   The key is followed by one or more field-value pairs.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HSet)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HSet, CommandError> {
+        if args.len() < 3 || args.len().is_multiple_of(2) {
+            return Err(CommandError::WrongArity("hset"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse field-value pairs
+        let mut fields: Vec<(String, String)> = vec![];
+        for pair in args[1..].chunks(2) {
+            match (&pair[0], &pair[1]) {
+                (RespType::BulkString(f), RespType::BulkString(v)) => {
+                    fields.push((f.to_string(), v.to_string()))
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Field and value must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        Ok(HSet { key, fields })
+    }
+
+    /// Executes the HSET command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of fields that were added to the hash.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hset(self.key.clone(), self.fields.clone()) {
+            Ok(added) => RespType::Integer(added as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
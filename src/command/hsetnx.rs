@@ -0,0 +1,71 @@
+// src/command/hsetnx.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the HSETNX command in MuDB.
+///
+/// The HSETNX command sets a field in a hash only if the field doesn't exist yet,
+/// which allows fields to be initialized once.
+#[derive(Debug, Clone)]
+pub struct HSetNx {
+    key: String,
+    field: String,
+    value: String,
+}
+
+impl HSetNx {
+    /// Creates a new `HSetNx` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HSETNX command.
+    ///   The arguments are the key, the field and the value.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HSetNx)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HSetNx, CommandError> {
+        if args.len() != 3 {
+            return Err(CommandError::WrongArity("hsetnx"));
+        }
+
+        let mut strs: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key, field and value must be bulk strings",
+                    )));
+                }
+            }
+        }
+        let value = strs.pop().unwrap();
+        let field = strs.pop().unwrap();
+        let key = strs.pop().unwrap();
+
+        Ok(HSetNx { key, field, value })
+    }
+
+    /// Executes the HSETNX command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(1)` if the field was set
+    /// - `Integer(0)` if the field already existed
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hsetnx(self.key.clone(), self.field.clone(), self.value.clone()) {
+            Ok(true) => RespType::Integer(1),
+            Ok(false) => RespType::Integer(0),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// src/command/httl.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{hexpire::HExpire, CommandError};
+
+/// Represents the HTTL command in MuDB.
+///
+/// The HTTL command returns the time left (in seconds) until fields of a hash expire.
+#[derive(Debug, Clone)]
+pub struct HTtl {
+    key: String,
+    fields: Vec<String>,
+}
+
+impl HTtl {
+    /// Creates a new `HTtl` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HTTL command:
+    ///   the key, followed by `FIELDS numfields field [field ...]`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HTtl)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HTtl, CommandError> {
+        if args.len() < 4 {
+            return Err(CommandError::WrongArity("httl"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        match &args[1] {
+            RespType::BulkString(s) if s.eq_ignore_ascii_case("fields") => {}
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "ERR Mandatory argument FIELDS is missing or not at the right position",
+                )));
+            }
+        }
+        let fields = HExpire::parse_fields(&args[2..])?;
+
+        Ok(HTtl { key, fields })
+    }
+
+    /// Executes the HTTL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - An `Array` with an `Integer` for each field, in the order they were given:
+    ///   - `-2` if the field or the key doesn't exist
+    ///   - `-1` if the field doesn't expire
+    ///   - Else the number of seconds left until the field expires, rounded to the nearest second
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.httl(&self.key, &self.fields) {
+            Ok(ttls) => RespType::Array(
+                ttls.into_iter()
+                    .map(|ttl| match ttl {
+                        None => RespType::Integer(-2),
+                        Some(None) => RespType::Integer(-1),
+                        Some(Some(ttl)) => {
+                            RespType::Integer(((ttl.as_millis() + 500) / 1000) as i64)
+                        }
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
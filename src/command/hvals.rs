@@ -0,0 +1,59 @@
+// src/command/hvals.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the HVALS command in MuDB.
+///
+/// The HVALS command returns all the values of the hash stored at a key.
+#[derive(Debug, Clone)]
+pub struct HVals {
+    key: String,
+}
+
+impl HVals {
+    /// Creates a new `HVALS` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HVALS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HVals)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HVals, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::WrongArity("hvals"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(HVals { key })
+    }
+
+    /// Executes the HVALS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` of the values of the hash. Empty if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hvals(&self.key) {
+            Ok(items) => RespType::Array(items.into_iter().map(RespType::BulkString).collect()),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+// src/command/info.rs
+
+use clap::ValueEnum;
+
+use crate::{config::Config, resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the INFO command in MuDB.
+///
+/// The INFO command reports information about the server, grouped in sections. Only the
+/// `memory`, `replication` and `keyspace` sections are supported. The memory section reports the
+/// approximate memory used by the keys and the `--maxmemory` options. The replication section lets clients
+/// aware of replication work with a single-node MuDB: it always reports the server as a master
+/// without replicas. The keyspace section reports the key counts of the only database, db0.
+#[derive(Debug, Clone)]
+pub struct Info {
+    /// Lowercased names of the requested sections. Empty for the default sections.
+    sections: Vec<String>,
+}
+
+impl Info {
+    /// Creates a new `Info` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the INFO command:
+    ///   the names of the requested sections, if any.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Info)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Info, CommandError> {
+        let mut sections = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => sections.push(s.to_lowercase()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Section names must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        Ok(Info { sections })
+    }
+
+    /// Executes the INFO command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database which holds the server stats.
+    ///
+    /// * `config` - The server options, for the memory section.
+    ///
+    /// # Returns
+    ///
+    /// A `BulkString` with a `# Section` header followed by `field:value` lines for each
+    /// requested section. Unknown sections are left out, like in Redis.
+    pub fn apply(&self, db: &DB, config: &Config) -> RespType {
+        let mut info = String::new();
+        if self.includes("memory") {
+            let used_memory = match db.used_memory() {
+                Ok(used_memory) => used_memory,
+                Err(e) => return RespType::SimpleError(format!("{}", e)),
+            };
+
+            info.push_str("# Memory\r\n");
+            info.push_str(&format!("used_memory:{}\r\n", used_memory));
+            // like in Redis, 0 means no limit
+            info.push_str(&format!("maxmemory:{}\r\n", config.maxmemory.unwrap_or(0)));
+            if let Some(policy) = config.maxmemory_policy.to_possible_value() {
+                info.push_str(&format!("maxmemory_policy:{}\r\n", policy.get_name()));
+            }
+        }
+
+        if self.includes("replication") {
+            if !info.is_empty() {
+                info.push_str("\r\n");
+            }
+            info.push_str("# Replication\r\n");
+            info.push_str("role:master\r\n");
+            info.push_str("connected_slaves:0\r\n");
+            info.push_str(&format!("master_replid:{}\r\n", db.stats().replid()));
+            info.push_str(&format!("master_replid2:{}\r\n", "0".repeat(40)));
+            info.push_str("master_repl_offset:0\r\n");
+            info.push_str("second_repl_offset:-1\r\n");
+        }
+
+        if self.includes("keyspace") {
+            let stats = match db.keyspace_stats() {
+                Ok(stats) => stats,
+                Err(e) => return RespType::SimpleError(format!("{}", e)),
+            };
+
+            if !info.is_empty() {
+                info.push_str("\r\n");
+            }
+            info.push_str("# Keyspace\r\n");
+            // like in Redis, empty databases are left out
+            if stats.keys > 0 {
+                info.push_str(&format!(
+                    "db0:keys={},expires={},avg_ttl={}\r\n",
+                    stats.keys,
+                    stats.expires,
+                    stats.avg_ttl.as_millis()
+                ));
+            }
+        }
+
+        RespType::BulkString(info)
+    }
+
+    /// Returns `true` if the given section was requested, either by name or as part of
+    /// the default sections.
+    fn includes(&self, section: &str) -> bool {
+        self.sections.is_empty()
+            || self
+                .sections
+                .iter()
+                .any(|s| s == section || matches!(s.as_str(), "default" | "all" | "everything"))
+    }
+}
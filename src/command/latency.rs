@@ -0,0 +1,106 @@
+// src/command/latency.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the LATENCY command in MuDB.
+///
+/// The LATENCY command reports the execution latency of the commands, as recorded when the
+/// server is started with `--track-timing`.
+#[derive(Debug, Clone)]
+pub struct Latency {
+    subcommand: LatencySubcommand,
+}
+
+/// The subcommands supported by the LATENCY command.
+#[derive(Debug, Clone)]
+enum LatencySubcommand {
+    /// LATENCY HISTORY [command-name ...]. Reports every command executed so far if no
+    /// names are given.
+    History(Vec<String>),
+    /// LATENCY RESET
+    Reset,
+}
+
+impl Latency {
+    /// Creates a new `Latency` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the LATENCY command.
+    ///   The first argument is the subcommand name.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Latency)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Latency, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::WrongArity("latency"));
+        }
+
+        let mut strs: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let subcommand = match strs[0].to_lowercase().as_str() {
+            "history" => LatencySubcommand::History(strs.split_off(1)),
+            "reset" => {
+                if strs.len() != 1 {
+                    return Err(CommandError::WrongArity("latency|reset"));
+                }
+                LatencySubcommand::Reset
+            }
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'LATENCY' command",
+                    strs[0].to_lowercase()
+                )));
+            }
+        };
+
+        Ok(Latency { subcommand })
+    }
+
+    /// Executes the LATENCY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database which holds the server stats.
+    ///
+    /// # Returns
+    ///
+    /// - HISTORY - An `Array` with a `[name, calls, p50, p99, max]` entry for each command
+    ///   executed so far, with the latencies in microseconds. Commands which haven't been
+    ///   executed are left out.
+    /// - RESET - The number of commands whose latencies were cleared, as an `Integer`
+    pub fn apply(&self, db: &DB) -> RespType {
+        match &self.subcommand {
+            LatencySubcommand::History(names) => RespType::Array(
+                db.stats()
+                    .latency_summaries(names)
+                    .into_iter()
+                    .map(|(name, summary)| {
+                        RespType::Array(vec![
+                            RespType::BulkString(name),
+                            RespType::Integer(summary.calls as i64),
+                            RespType::Integer(summary.p50_us as i64),
+                            RespType::Integer(summary.p99_us as i64),
+                            RespType::Integer(summary.max_us as i64),
+                        ])
+                    })
+                    .collect(),
+            ),
+            LatencySubcommand::Reset => RespType::Integer(db.stats().reset_latencies() as i64),
+        }
+    }
+}
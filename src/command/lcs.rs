@@ -0,0 +1,79 @@
+// src/command/lcs.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the LCS command in MuDB.
+///
+/// The LCS command finds the longest common subsequence of the strings stored at two keys.
+#[derive(Debug, Clone)]
+pub struct Lcs {
+    key1: String,
+    key2: String,
+    /// Whether only the length of the subsequence is returned (`LEN` option).
+    len_only: bool,
+}
+
+impl Lcs {
+    /// Creates a new `Lcs` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the LCS command:
+    ///   the two keys, optionally followed by `LEN`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Lcs)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Lcs, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::WrongArity("lcs"));
+        }
+
+        let mut strs: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Keys and options must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let len_only = match &strs[2..] {
+            [] => false,
+            [option] if option.eq_ignore_ascii_case("len") => true,
+            _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+        };
+
+        Ok(Lcs {
+            key1: strs[0].clone(),
+            key2: strs[1].clone(),
+            len_only,
+        })
+    }
+
+    /// Executes the LCS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// * `max_len` - The maximum combined size of the two values, in bytes.
+    ///
+    /// # Returns
+    ///
+    /// - The longest common subsequence as a `BulkString`, or its length as an `Integer` with `LEN`
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB, max_len: usize) -> RespType {
+        match db.lcs(&self.key1, &self.key2, self.len_only, max_len) {
+            Ok((_, Some(lcs))) => RespType::BulkString(lcs),
+            Ok((len, None)) => RespType::Integer(len as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
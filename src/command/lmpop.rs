@@ -0,0 +1,128 @@
+// src/command/lmpop.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{ListEnd, DB},
+};
+
+use super::CommandError;
+
+/// Represents the LMPOP command in MuDB.
+///
+/// It pops one or more elements from the first non-empty list among the given keys,
+/// so that clients can poll several queues with a single command.
+#[derive(Debug, Clone)]
+pub struct LMPop {
+    keys: Vec<String>,
+    end: ListEnd,
+    /// The maximum number of elements to pop.
+    count: usize,
+}
+
+impl LMPop {
+    /// Creates a new `LMPop` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the LMPOP command.
+    ///   The number of keys is followed by the keys, `LEFT` or `RIGHT`, and the optional `COUNT count` option.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(LMPop)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<LMPop, CommandError> {
+        if args.len() < 3 {
+            return Err(CommandError::WrongArity("lmpop"));
+        }
+
+        let mut strs: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        // parse the number of keys
+        let numkeys = match strs[0].parse::<i64>() {
+            Ok(n) if n > 0 => n as usize,
+            Ok(_) => {
+                return Err(CommandError::Other(String::from(
+                    "ERR numkeys should be greater than 0",
+                )));
+            }
+            Err(_) => {
+                return Err(CommandError::Other(String::from(
+                    "ERR value is not an integer or out of range",
+                )));
+            }
+        };
+        if numkeys > strs.len() - 2 {
+            return Err(CommandError::Other(String::from("ERR syntax error")));
+        }
+        let keys = strs[1..=numkeys].to_vec();
+
+        // parse the end of the lists to pop from
+        let end = match strs[numkeys + 1].to_lowercase().as_str() {
+            "left" => ListEnd::Head,
+            "right" => ListEnd::Tail,
+            _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+        };
+
+        // parse options
+        let mut count = None;
+        let mut opts = strs[numkeys + 2..].iter();
+        while let Some(opt) = opts.next() {
+            match opt.to_lowercase().as_str() {
+                "count" if count.is_none() => {
+                    let value = match opts.next() {
+                        Some(v) => v,
+                        None => return Err(CommandError::Other(String::from("ERR syntax error"))),
+                    };
+                    count = match value.parse::<i64>() {
+                        Ok(n) if n > 0 => Some(n as usize),
+                        _ => {
+                            return Err(CommandError::Other(String::from(
+                                "ERR count should be greater than 0",
+                            )));
+                        }
+                    };
+                }
+                _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+            }
+        }
+
+        Ok(LMPop {
+            keys,
+            end,
+            count: count.unwrap_or(1),
+        })
+    }
+
+    /// Executes the LMPOP command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - If elements are popped - A two-element `Array` with the key and an `Array` of the popped elements
+    /// - If all the lists are empty - A `NullArray`
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.lmpop(&self.keys, self.end, self.count) {
+            Ok(Some((key, elems))) => RespType::Array(vec![
+                RespType::BulkString(key),
+                RespType::Array(elems.into_iter().map(RespType::BulkString).collect()),
+            ]),
+            Ok(None) => RespType::NullArray,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// src/command/lpos.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the LPOS command in MuDB.
+///
+/// The LPOS command returns the index of matching elements in a list, so that clients
+/// don't need to fetch the whole list to locate an element.
+#[derive(Debug, Clone)]
+pub struct LPos {
+    key: String,
+    element: String,
+    /// Which match to start from. Negative ranks search from the tail.
+    rank: i64,
+    /// The maximum number of matches to return, 0 meaning all. `None` if COUNT wasn't given.
+    count: Option<usize>,
+}
+
+impl LPos {
+    /// Creates a new `LPos` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the LPOS command.
+    ///   The key and element can be followed by the optional `RANK rank` and `COUNT num-matches` options.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(LPos)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<LPos, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::WrongArity("lpos"));
+        }
+
+        // parse key and element
+        let mut strs: Vec<String> = vec![];
+        for arg in args[..2].iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key and element must be bulk strings",
+                    )));
+                }
+            }
+        }
+        let element = strs.pop().unwrap();
+        let key = strs.pop().unwrap();
+
+        // parse options
+        let mut rank = 1;
+        let mut count = None;
+        let mut opts = args[2..].iter();
+        while let Some(opt) = opts.next() {
+            let opt = match opt {
+                RespType::BulkString(opt) => opt.to_lowercase(),
+                _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+            };
+
+            let value = match opts.next() {
+                Some(RespType::BulkString(v)) => v,
+                _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+            };
+            let value = match value.parse::<i64>() {
+                Ok(v) => v,
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "ERR value is not an integer or out of range",
+                    )));
+                }
+            };
+
+            match opt.as_str() {
+                "rank" => {
+                    if value == 0 {
+                        return Err(CommandError::Other(String::from(
+                            "ERR RANK can't be zero: use 1 to start from the first match, 2 from the second ... or use negative to start from the end of the list",
+                        )));
+                    }
+                    if value == i64::MIN {
+                        return Err(CommandError::Other(String::from(
+                            "ERR value is out of range, value must between -9223372036854775807 and 9223372036854775807",
+                        )));
+                    }
+                    rank = value;
+                }
+                "count" => {
+                    if value < 0 {
+                        return Err(CommandError::Other(String::from(
+                            "ERR COUNT can't be negative",
+                        )));
+                    }
+                    count = Some(value as usize);
+                }
+                _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+            }
+        }
+
+        Ok(LPos {
+            key,
+            element,
+            rank,
+            count,
+        })
+    }
+
+    /// Executes the LPOS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - Without COUNT - The index of the match as an `Integer`, or a `NullBulkString` if no element matches
+    /// - With COUNT - An `Array` with the indices of the matches, empty if no element matches
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        let indices = match db.lpos(&self.key, &self.element, self.rank, self.count) {
+            Ok(indices) => indices,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        match self.count {
+            Some(_) => RespType::Array(
+                indices
+                    .into_iter()
+                    .map(|i| RespType::Integer(i as i64))
+                    .collect(),
+            ),
+            None => match indices.first() {
+                Some(i) => RespType::Integer(*i as i64),
+                None => RespType::NullBulkString,
+            },
+        }
+    }
+}
@@ -24,9 +24,7 @@ impl LPush {
     /// * `Err(CommandError)` if parsing fails.
     pub fn with_args(args: Vec<RespType>) -> Result<LPush, CommandError> {
         if args.len() < 2 {
-            return Err(CommandError::Other(String::from(
-                "Wrong number of arguments specified for 'LPUSH' command",
-            )));
+            return Err(CommandError::WrongArity("lpush"));
         }
 
         // parse key
@@ -74,18 +72,4 @@ impl LPush {
             Err(e) => RespType::SimpleError(format!("{}", e)),
         }
     }
-
-    pub fn build_command(&self) -> RespType {
-        let mut args: Vec<RespType> = vec![
-            RespType::BulkString(String::from("LPUSH")),
-            RespType::BulkString(self.key.clone()),
-        ];
-
-        let arg_vals = self.values.clone();
-        for arg in arg_vals.iter() {
-            args.push(RespType::BulkString(arg.to_string()));
-        }
-
-        RespType::Array(args)
-    }
 }
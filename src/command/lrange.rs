@@ -1,9 +1,22 @@
 // src/command/lrange.rs
 
-use crate::{resp::types::RespType, storage::db::DB};
+use std::time::{Duration, Instant};
+
+use bytes::{BufMut, BytesMut};
+
+use crate::{
+    resp::{frame::RespCommandFrame, types::RespType},
+    storage::db::DB,
+};
 
 use super::CommandError;
 
+/// Number of elements visited between two checks of the deadline set by `--command-timeout`.
+const DEADLINE_CHECK_INTERVAL: usize = 1024;
+
+/// Error replied when a command is aborted by `--command-timeout`.
+const COMMAND_TIMEOUT_ERROR: &str = "ERR command exceeded time limit";
+
 /// Represents the LRANGE command in Nimblecache.
 #[derive(Debug, Clone)]
 pub struct LRange {
@@ -24,10 +37,8 @@ impl LRange {
     /// * `Ok(LRange)` if parsing succeeds.
     /// * `Err(CommandError)` if parsing fails.
     pub fn with_args(args: Vec<RespType>) -> Result<LRange, CommandError> {
-        if args.len() < 3 {
-            return Err(CommandError::Other(String::from(
-                "Wrong number of arguments specified for 'LRANGE' command",
-            )));
+        if args.len() != 3 {
+            return Err(CommandError::WrongArity("lrange"));
         }
 
         // parse key
@@ -96,20 +107,81 @@ impl LRange {
     ///
     /// * `db` - The database where the key and values are stored.
     ///
+    /// * `timeout` - Time after which the command is aborted, `None` if it isn't limited.
+    ///
     /// # Returns
     ///
-    /// It returns the specified number of elements in the list stored at key, based on start and stop indices.
-    pub fn apply(&self, db: &DB) -> RespType {
-        match db.lrange(self.key.clone(), self.start_idx, self.end_idx) {
-            Ok(elems) => {
-                let sub_list = elems
-                    .iter()
-                    .cloned()
-                    .map(|e| RespType::BulkString(e))
-                    .collect();
-                RespType::Array(sub_list)
+    /// It returns the specified number of elements in the list stored at key, based on start and stop indices,
+    /// or a `SimpleError` if the command ran out of time.
+    pub fn apply(&self, db: &DB, timeout: Option<Duration>) -> RespType {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        let res = db.with_lrange(&self.key, self.start_idx, self.end_idx, |elems| {
+            let mut sub_list = Vec::with_capacity(elems.len());
+            for (i, elem) in elems.enumerate() {
+                if Self::deadline_passed(deadline, i) {
+                    return None;
+                }
+                sub_list.push(RespType::BulkString(elem.clone()));
             }
+            Some(sub_list)
+        });
+
+        match res {
+            Ok(Some(sub_list)) => RespType::Array(sub_list),
+            Ok(None) => RespType::SimpleError(String::from(COMMAND_TIMEOUT_ERROR)),
             Err(e) => RespType::SimpleError(format!("{}", e)),
         }
     }
+
+    /// Executes the LRANGE command, writing the response straight into the output buffer
+    /// of the connection.
+    ///
+    /// Unlike `apply`, the elements are encoded directly from the list while the read lock
+    /// is held, so a large range isn't copied into a `RespType::Array` first.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// * `dst` - The output buffer to which the encoded response is written.
+    ///
+    /// * `timeout` - Time after which the command is aborted, `None` if it isn't limited.
+    ///   The partly encoded range is then removed from `dst` and replaced by an error.
+    pub fn apply_into(&self, db: &DB, dst: &mut BytesMut, timeout: Option<Duration>) {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        let start_len = dst.len();
+        let res = db.with_lrange(&self.key, self.start_idx, self.end_idx, |elems| {
+            RespCommandFrame::encode_array_header(elems.len(), dst);
+            for (i, elem) in elems.enumerate() {
+                if Self::deadline_passed(deadline, i) {
+                    return false;
+                }
+                RespCommandFrame::encode_bulk_string(elem, dst);
+            }
+            true
+        });
+
+        match res {
+            Ok(true) => {}
+            Ok(false) => {
+                dst.truncate(start_len);
+                dst.put_slice(
+                    &RespType::SimpleError(String::from(COMMAND_TIMEOUT_ERROR)).to_bytes(),
+                );
+            }
+            Err(e) => dst.put_slice(&RespType::SimpleError(format!("{}", e)).to_bytes()),
+        }
+    }
+
+    /// Returns whether the deadline has passed, before the element at index `i` of the range is
+    /// visited. The clock is only read every `DEADLINE_CHECK_INTERVAL` elements, so that the
+    /// check stays cheap next to encoding the elements.
+    fn deadline_passed(deadline: Option<Instant>, i: usize) -> bool {
+        match deadline {
+            Some(deadline) => {
+                i > 0 && i.is_multiple_of(DEADLINE_CHECK_INTERVAL) && Instant::now() >= deadline
+            }
+            None => false,
+        }
+    }
 }
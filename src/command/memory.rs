@@ -0,0 +1,93 @@
+// src/command/memory.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the MEMORY command in MuDB.
+///
+/// The MEMORY command is used to inspect the memory used by the values stored in the database.
+#[derive(Debug, Clone)]
+pub struct Memory {
+    subcommand: MemorySubcommand,
+}
+
+/// The subcommands supported by the MEMORY command.
+#[derive(Debug, Clone)]
+enum MemorySubcommand {
+    /// MEMORY USAGE key
+    Usage(String),
+}
+
+impl Memory {
+    /// Creates a new `Memory` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the MEMORY command.
+    ///   The first argument is the subcommand name.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Memory)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Memory, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::WrongArity("memory"));
+        }
+
+        // parse subcommand
+        let subcommand = match &args[0] {
+            RespType::BulkString(s) => s.to_lowercase(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Subcommand must be a bulk string",
+                )));
+            }
+        };
+
+        let subcommand = match subcommand.as_str() {
+            "usage" => {
+                if args.len() != 2 {
+                    return Err(CommandError::WrongArity("memory|usage"));
+                }
+                match &args[1] {
+                    RespType::BulkString(k) => MemorySubcommand::Usage(k.to_string()),
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "Invalid argument. Key must be a bulk string",
+                        )));
+                    }
+                }
+            }
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'MEMORY' command",
+                    subcommand
+                )));
+            }
+        };
+
+        Ok(Memory { subcommand })
+    }
+
+    /// Executes the MEMORY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - USAGE - The approximate size of the key and its value in bytes as an `Integer`,
+    ///   or `NullBulkString` if the key is missing
+    pub fn apply(&self, db: &DB) -> RespType {
+        match &self.subcommand {
+            MemorySubcommand::Usage(key) => match db.memory_usage(key) {
+                Ok(Some(size)) => RespType::Integer(size as i64),
+                Ok(None) => RespType::NullBulkString,
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+        }
+    }
+}
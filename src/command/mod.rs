@@ -6,8 +6,69 @@ use set::Set;
 use lpush::LPush;
 use rpush::RPush;
 use lrange::LRange;
+use blpop::BLPop;
+use brpop::BRPop;
+use object::Object;
+use time::Time;
+use copy::Copy;
+use debug::Debug;
+use expireat::ExpireAt;
+use pexpireat::PExpireAt;
+use hset::HSet;
+use hmget::HMGet;
+use hkeys::HKeys;
+use hvals::HVals;
+use sadd::SAdd;
+use smismember::SMIsMember;
+use zadd::ZAdd;
+use zrangebyscore::ZRangeByScore;
+use zincrby::ZIncrBy;
+use zrank::ZRank;
+use zrevrank::ZRevRank;
+use rename::Rename;
+use renamenx::RenameNx;
+use client::Client;
+use stats::Stats;
+use memory::Memory;
+use wait::Wait;
+use reset::Reset;
+use getdel::GetDel;
+use getex::GetEx;
+use dump::Dump;
+use restore::Restore;
+use lpos::LPos;
+use flushall::FlushAll;
+use expire::Expire;
+use pexpire::PExpire;
+use hrandfield::HRandField;
+use zrangebylex::ZRangeByLex;
+use zrem::ZRem;
+use zcard::ZCard;
+use touch::Touch;
+use unlink::Unlink;
+use cas::Cas;
+use expiretime::ExpireTime;
+use pexpiretime::PExpireTime;
+use lmpop::LMPop;
+use quit::Quit;
+use command_info::CommandInfo;
+use hsetnx::HSetNx;
+use latency::Latency;
+use append::Append;
+use lcs::Lcs;
+use hscan::HScan;
+use sscan::SScan;
+use zscan::ZScan;
+use monitor::Monitor;
+use info::Info;
+use scan::Scan;
+use hexpire::HExpire;
+use httl::HTtl;
+use cluster::Cluster;
+use getrange::GetRange;
+use sinterstore::SInterStore;
 
-use crate::{resp::types::RespType, storage::db::DB};
+use crate::{client::ClientHandle, config::Config, resp::{has_error_code, types::RespType}, storage::db::DB};
 
 mod get;
 mod ping;
@@ -15,6 +76,70 @@ mod set;
 mod lpush;
 mod rpush;
 mod lrange;
+mod blpop;
+mod brpop;
+mod object;
+mod time;
+mod copy;
+mod debug;
+mod expireat;
+mod pexpireat;
+mod hset;
+mod hmget;
+mod hkeys;
+mod hvals;
+mod sadd;
+mod smismember;
+mod zadd;
+mod zrangebyscore;
+mod zincrby;
+mod zrank;
+mod zrevrank;
+mod rename;
+mod renamenx;
+mod client;
+mod stats;
+mod memory;
+mod wait;
+mod reset;
+mod getdel;
+mod getex;
+mod dump;
+mod restore;
+mod lpos;
+mod flushall;
+mod expire;
+mod pexpire;
+mod hrandfield;
+mod zrangebylex;
+mod zrem;
+mod zcard;
+mod touch;
+mod unlink;
+mod cas;
+mod expiretime;
+mod pexpiretime;
+mod lmpop;
+mod quit;
+mod command_info;
+mod hsetnx;
+mod latency;
+mod append;
+mod lcs;
+mod hscan;
+mod sscan;
+mod zscan;
+mod scan_args;
+mod ttl;
+mod monitor;
+mod info;
+mod help;
+mod scan;
+mod hexpire;
+mod httl;
+mod cluster;
+mod getrange;
+mod sinterstore;
 
 
 /// Represents the supported Nimblecache commands.
@@ -32,6 +157,128 @@ pub enum Command {
     RPush(RPush),
     /// The LRANGE command.
     LRange(LRange),
+    /// The BLPOP command.
+    BLPop(BLPop),
+    /// The BRPOP command.
+    BRPop(BRPop),
+    /// The OBJECT command.
+    Object(Object),
+    /// The TIME command.
+    Time(Time),
+    /// The COPY command.
+    Copy(Copy),
+    /// The DEBUG command.
+    Debug(Debug),
+    /// The EXPIREAT command.
+    ExpireAt(ExpireAt),
+    /// The PEXPIREAT command.
+    PExpireAt(PExpireAt),
+    /// The HSET command.
+    HSet(HSet),
+    /// The HMGET command.
+    HMGet(HMGet),
+    /// The HKEYS command.
+    HKeys(HKeys),
+    /// The HVALS command.
+    HVals(HVals),
+    /// The SADD command.
+    SAdd(SAdd),
+    /// The SMISMEMBER command.
+    SMIsMember(SMIsMember),
+    /// The ZADD command.
+    ZAdd(ZAdd),
+    /// The ZRANGEBYSCORE command.
+    ZRangeByScore(ZRangeByScore),
+    /// The ZINCRBY command.
+    ZIncrBy(ZIncrBy),
+    /// The ZRANK command.
+    ZRank(ZRank),
+    /// The ZREVRANK command.
+    ZRevRank(ZRevRank),
+    /// The RENAME command.
+    Rename(Rename),
+    /// The RENAMENX command.
+    RenameNx(RenameNx),
+    /// The CLIENT command.
+    Client(Client),
+    /// The STATS command.
+    Stats(Stats),
+    /// The MEMORY command.
+    Memory(Memory),
+    /// The WAIT command.
+    Wait(Wait),
+    /// The RESET command.
+    Reset(Reset),
+    /// The GETDEL command.
+    GetDel(GetDel),
+    /// The GETEX command.
+    GetEx(GetEx),
+    /// The DUMP command.
+    Dump(Dump),
+    /// The RESTORE command.
+    Restore(Restore),
+    /// The LPOS command.
+    LPos(LPos),
+    /// The FLUSHALL command.
+    FlushAll(FlushAll),
+    /// The EXPIRE command.
+    Expire(Expire),
+    /// The PEXPIRE command.
+    PExpire(PExpire),
+    /// The HRANDFIELD command.
+    HRandField(HRandField),
+    /// The ZRANGEBYLEX command.
+    ZRangeByLex(ZRangeByLex),
+    /// The ZREM command.
+    ZRem(ZRem),
+    /// The ZCARD command.
+    ZCard(ZCard),
+    /// The TOUCH command.
+    Touch(Touch),
+    /// The UNLINK command.
+    Unlink(Unlink),
+    /// The CAS command.
+    Cas(Cas),
+    /// The EXPIRETIME command.
+    ExpireTime(ExpireTime),
+    /// The PEXPIRETIME command.
+    PExpireTime(PExpireTime),
+    /// The LMPOP command.
+    LMPop(LMPop),
+    /// The QUIT command.
+    Quit(Quit),
+    /// The COMMAND command.
+    CommandInfo(CommandInfo),
+    /// The HSETNX command.
+    HSetNx(HSetNx),
+    /// The LATENCY command.
+    Latency(Latency),
+    /// The APPEND command.
+    Append(Append),
+    /// The LCS command.
+    Lcs(Lcs),
+    /// The HSCAN command.
+    HScan(HScan),
+    /// The SSCAN command.
+    SScan(SScan),
+    /// The ZSCAN command.
+    ZScan(ZScan),
+    /// The MONITOR command.
+    Monitor(Monitor),
+    /// The INFO command.
+    Info(Info),
+    /// The SCAN command.
+    Scan(Scan),
+    /// The HEXPIRE command.
+    HExpire(HExpire),
+    /// The HTTL command.
+    HTtl(HTtl),
+    /// The CLUSTER command.
+    Cluster(Cluster),
+    /// The GETRANGE command.
+    GetRange(GetRange),
+    /// The SINTERSTORE command.
+    SInterStore(SInterStore),
 }
 
 impl Command {
@@ -40,7 +287,7 @@ impl Command {
     /// # Arguments
     ///
     /// * `frame` - A vector of `RespType` representing the command and its arguments.
-    /// The first item is always the command name, and the rest are its arguments.
+    ///   The first item is always the command name, and the rest are its arguments.
     ///
     /// # Returns
     ///
@@ -90,6 +337,435 @@ impl Command {
                     Err(e) => return Err(e),
                 }
             }
+            "blpop" => {
+                let cmd = BLPop::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::BLPop(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "brpop" => {
+                let cmd = BRPop::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::BRPop(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "object" => {
+                let cmd = Object::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Object(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "time" => Command::Time(Time::with_args(Vec::from(args))?),
+            "copy" => {
+                let cmd = Copy::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Copy(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "debug" => {
+                let cmd = Debug::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Debug(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "expireat" => {
+                let cmd = ExpireAt::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::ExpireAt(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "pexpireat" => {
+                let cmd = PExpireAt::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::PExpireAt(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "hset" => {
+                let cmd = HSet::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::HSet(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "hmget" => {
+                let cmd = HMGet::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::HMGet(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "hkeys" => {
+                let cmd = HKeys::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::HKeys(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "hvals" => {
+                let cmd = HVals::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::HVals(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "sadd" => {
+                let cmd = SAdd::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::SAdd(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "smismember" => {
+                let cmd = SMIsMember::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::SMIsMember(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "zadd" => {
+                let cmd = ZAdd::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::ZAdd(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "zrangebyscore" => {
+                let cmd = ZRangeByScore::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::ZRangeByScore(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "zincrby" => {
+                let cmd = ZIncrBy::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::ZIncrBy(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "zrank" => {
+                let cmd = ZRank::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::ZRank(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "zrevrank" => {
+                let cmd = ZRevRank::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::ZRevRank(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "rename" => {
+                let cmd = Rename::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Rename(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "renamenx" => {
+                let cmd = RenameNx::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::RenameNx(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "client" => {
+                let cmd = Client::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Client(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "stats" => {
+                let cmd = Stats::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Stats(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "memory" => {
+                let cmd = Memory::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Memory(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "wait" => {
+                let cmd = Wait::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Wait(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "reset" => {
+                let cmd = Reset::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Reset(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "getdel" => {
+                let cmd = GetDel::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::GetDel(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "getex" => {
+                let cmd = GetEx::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::GetEx(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "dump" => {
+                let cmd = Dump::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Dump(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "restore" => {
+                let cmd = Restore::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Restore(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "lpos" => {
+                let cmd = LPos::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::LPos(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "flushall" => {
+                let cmd = FlushAll::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::FlushAll(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "expire" => {
+                let cmd = Expire::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Expire(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "pexpire" => {
+                let cmd = PExpire::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::PExpire(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "hrandfield" => {
+                let cmd = HRandField::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::HRandField(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "zrangebylex" => {
+                let cmd = ZRangeByLex::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::ZRangeByLex(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "zrem" => {
+                let cmd = ZRem::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::ZRem(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "zcard" => {
+                let cmd = ZCard::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::ZCard(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "touch" => {
+                let cmd = Touch::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Touch(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "unlink" => {
+                let cmd = Unlink::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Unlink(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "cas" => {
+                let cmd = Cas::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Cas(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "expiretime" => {
+                let cmd = ExpireTime::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::ExpireTime(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "pexpiretime" => {
+                let cmd = PExpireTime::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::PExpireTime(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "lmpop" => {
+                let cmd = LMPop::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::LMPop(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "quit" => {
+                let cmd = Quit::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Quit(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "command" => {
+                let cmd = CommandInfo::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::CommandInfo(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "hsetnx" => {
+                let cmd = HSetNx::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::HSetNx(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "latency" => {
+                let cmd = Latency::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Latency(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "append" => {
+                let cmd = Append::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Append(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "lcs" => {
+                let cmd = Lcs::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Lcs(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "hscan" => {
+                let cmd = HScan::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::HScan(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "sscan" => {
+                let cmd = SScan::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::SScan(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "zscan" => {
+                let cmd = ZScan::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::ZScan(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "monitor" => {
+                let cmd = Monitor::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Monitor(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "info" => {
+                let cmd = Info::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Info(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "scan" => {
+                let cmd = Scan::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Scan(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "hexpire" => {
+                let cmd = HExpire::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::HExpire(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "httl" => {
+                let cmd = HTtl::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::HTtl(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "cluster" => {
+                let cmd = Cluster::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::Cluster(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "getrange" => {
+                let cmd = GetRange::with_args(Vec::from(args), "getrange");
+                match cmd {
+                    Ok(cmd) => Command::GetRange(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            // SUBSTR is the name GETRANGE had before Redis 2.0, still sent by some clients
+            "substr" => {
+                let cmd = GetRange::with_args(Vec::from(args), "substr");
+                match cmd {
+                    Ok(cmd) => Command::GetRange(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
+            "sinterstore" => {
+                let cmd = SInterStore::with_args(Vec::from(args));
+                match cmd {
+                    Ok(cmd) => Command::SInterStore(cmd),
+                    Err(e) => return Err(e),
+                }
+            }
             _ => {
                 return Err(CommandError::UnknownCommand(ErrUnknownCommand {
                     cmd: cmd_name,
@@ -101,26 +777,186 @@ impl Command {
         Ok(cmd)
     }
 
+    /// Returns `true` if the command can modify the data stored in the DB.
+    /// Write commands are rejected when the server is started with `--readonly`.
+    ///
+    /// Every command must be classified here, so that adding a command without
+    /// deciding whether it writes fails to compile.
+    pub fn is_write(&self) -> bool {
+        match self {
+            // write commands
+            Command::Set(_)
+            | Command::GetDel(_)
+            | Command::GetEx(_)
+            | Command::LPush(_)
+            | Command::RPush(_)
+            | Command::BLPop(_)
+            | Command::BRPop(_)
+            | Command::HSet(_)
+            | Command::SAdd(_)
+            | Command::ZAdd(_)
+            | Command::ZIncrBy(_)
+            | Command::Copy(_)
+            | Command::Rename(_)
+            | Command::RenameNx(_)
+            | Command::Expire(_)
+            | Command::PExpire(_)
+            | Command::ExpireAt(_)
+            | Command::PExpireAt(_)
+            | Command::Restore(_)
+            | Command::ZRem(_)
+            | Command::Unlink(_)
+            | Command::Cas(_)
+            | Command::LMPop(_)
+            | Command::HSetNx(_)
+            | Command::Append(_)
+            | Command::HExpire(_)
+            | Command::SInterStore(_)
+            | Command::FlushAll(_) => true,
+
+            // read and server commands
+            Command::Ping(_)
+            | Command::Time(_)
+            | Command::Debug(_)
+            | Command::Client(_)
+            | Command::Stats(_)
+            | Command::Wait(_)
+            | Command::Reset(_)
+            | Command::Get(_)
+            | Command::LRange(_)
+            | Command::LPos(_)
+            | Command::HMGet(_)
+            | Command::HKeys(_)
+            | Command::HVals(_)
+            | Command::SMIsMember(_)
+            | Command::ZRangeByScore(_)
+            | Command::ZRank(_)
+            | Command::ZRevRank(_)
+            | Command::Dump(_)
+            | Command::Object(_)
+            | Command::HRandField(_)
+            | Command::ZRangeByLex(_)
+            | Command::ZCard(_)
+            | Command::Touch(_)
+            | Command::ExpireTime(_)
+            | Command::PExpireTime(_)
+            | Command::Quit(_)
+            | Command::CommandInfo(_)
+            | Command::Latency(_)
+            | Command::Lcs(_)
+            | Command::HScan(_)
+            | Command::SScan(_)
+            | Command::ZScan(_)
+            | Command::Monitor(_)
+            | Command::Info(_)
+            | Command::Scan(_)
+            | Command::HTtl(_)
+            | Command::Cluster(_)
+            | Command::GetRange(_)
+            | Command::Memory(_) => false,
+        }
+    }
+
     /// Executes the Nimblecache command.
      /// # Arguments
     ///
     /// * `db` - Reference to the database where the key-value pairs are stored.
+    ///
+    /// * `client_handle` - The connection the command was received on.
+    ///
+    /// * `config` - Server options, for the commands whose limits are configurable.
     /// # Returns
     ///
     /// The result of the command execution as a `RespType`.
-    pub fn execute(&self, db : &DB) -> RespType {
+    ///
+    /// Blocking commands (such as BLPOP) only resolve once they have a reply, so the
+    /// returned future may stay pending for as long as the command's timeout.
+    pub async fn execute(&self, db : &DB, client_handle: &ClientHandle, config: &Config) -> RespType {
         match self {
             // ping command
             Command::Ping(ping) => ping.apply(),
 
+            // server commands
+            Command::Time(time) => time.apply(),
+            Command::Debug(debug) => debug.apply(db).await,
+            Command::Client(client) => client.apply(client_handle),
+            Command::Stats(stats) => stats.apply(db),
+            Command::Wait(wait) => wait.apply(),
+            Command::Reset(reset) => reset.apply(client_handle),
+            Command::FlushAll(flushall) => flushall.apply(db),
+            Command::Quit(quit) => quit.apply(),
+            Command::Monitor(monitor) => monitor.apply(),
+            Command::Info(info) => info.apply(db, config),
+
             // string commands
             Command::Set(set) => set.apply(db),
             Command::Get(get) => get.apply(db),
+            Command::GetDel(getdel) => getdel.apply(db),
+            Command::GetEx(getex) => getex.apply(db),
+            Command::Cas(cas) => cas.apply(db),
+            Command::Append(append) => append.apply(db),
+            Command::Lcs(lcs) => lcs.apply(db, config.lcs_max_len),
+            Command::GetRange(getrange) => getrange.apply(db),
 
             // list commands
             Command::LPush(lpush) => lpush.apply(db),
             Command::RPush(rpush) => rpush.apply(db),
-            Command::LRange(lrange) => lrange.apply(db),
+            Command::LRange(lrange) => lrange.apply(db, config.command_timeout),
+            Command::BLPop(blpop) => blpop.apply(db).await,
+            Command::BRPop(brpop) => brpop.apply(db).await,
+            Command::LPos(lpos) => lpos.apply(db),
+            Command::LMPop(lmpop) => lmpop.apply(db),
+
+            // hash commands
+            Command::HSet(hset) => hset.apply(db),
+            Command::HMGet(hmget) => hmget.apply(db),
+            Command::HKeys(hkeys) => hkeys.apply(db),
+            Command::HVals(hvals) => hvals.apply(db),
+            Command::HRandField(hrandfield) => hrandfield.apply(db),
+            Command::HSetNx(hsetnx) => hsetnx.apply(db),
+            Command::HScan(hscan) => hscan.apply(db),
+            Command::HExpire(hexpire) => hexpire.apply(db),
+            Command::HTtl(httl) => httl.apply(db),
+
+            // set commands
+            Command::SAdd(sadd) => sadd.apply(db),
+            Command::SMIsMember(smismember) => smismember.apply(db),
+            Command::SScan(sscan) => sscan.apply(db),
+            Command::SInterStore(sinterstore) => sinterstore.apply(db),
+
+            // sorted set commands
+            Command::ZAdd(zadd) => zadd.apply(db),
+            Command::ZRangeByScore(zrangebyscore) => zrangebyscore.apply(db),
+            Command::ZIncrBy(zincrby) => zincrby.apply(db),
+            Command::ZRank(zrank) => zrank.apply(db),
+            Command::ZRevRank(zrevrank) => zrevrank.apply(db),
+            Command::ZRangeByLex(zrangebylex) => zrangebylex.apply(db),
+            Command::ZRem(zrem) => zrem.apply(db),
+            Command::ZCard(zcard) => zcard.apply(db),
+            Command::ZScan(zscan) => zscan.apply(db),
+
+            // generic commands
+            Command::Copy(copy) => copy.apply(db),
+            Command::ExpireAt(expireat) => expireat.apply(db),
+            Command::PExpireAt(pexpireat) => pexpireat.apply(db),
+            Command::Rename(rename) => rename.apply(db),
+            Command::RenameNx(renamenx) => renamenx.apply(db),
+            Command::Dump(dump) => dump.apply(db),
+            Command::Restore(restore) => restore.apply(db),
+            Command::Expire(expire) => expire.apply(db),
+            Command::PExpire(pexpire) => pexpire.apply(db),
+            Command::Touch(touch) => touch.apply(db),
+            Command::Unlink(unlink) => unlink.apply(db),
+            Command::ExpireTime(expiretime) => expiretime.apply(db),
+            Command::PExpireTime(pexpiretime) => pexpiretime.apply(db),
+            Command::Scan(scan) => scan.apply(db),
+
+            // introspection commands
+            Command::Object(object) => object.apply(db, config.maxmemory_policy),
+            Command::Memory(memory) => memory.apply(db),
+            Command::CommandInfo(command_info) => command_info.apply(),
+            Command::Latency(latency) => latency.apply(db),
+            Command::Cluster(cluster) => cluster.apply(),
         }
     }
 }
@@ -132,6 +968,9 @@ pub enum CommandError {
     InvalidFormat,
     /// Indicates that the command is unknown.
     UnknownCommand(ErrUnknownCommand),
+    /// Indicates that the command was given the wrong number of arguments.
+    /// Holds the lowercased command name, as used in the Redis error message.
+    WrongArity(&'static str),
     /// Represents any other error with a descriptive message.
     Other(String),
 }
@@ -148,9 +987,148 @@ impl std::error::Error for CommandError {}
 impl fmt::Display for CommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            CommandError::InvalidFormat => "Invalid command format".fmt(f),
-            CommandError::UnknownCommand(e) => write!(f, "Unknown command: {}", e.cmd),
-            CommandError::Other(msg) => msg.as_str().fmt(f),
+            CommandError::InvalidFormat => "ERR Invalid command format".fmt(f),
+            CommandError::UnknownCommand(e) => write!(f, "ERR unknown command '{}'", e.cmd),
+            CommandError::WrongArity(cmd) => {
+                write!(f, "ERR wrong number of arguments for '{}' command", cmd)
+            }
+            // Clients branch on the error code at the start of the message, so messages
+            // without one get the generic `ERR` code, like Redis argument errors.
+            CommandError::Other(msg) if has_error_code(msg) => msg.as_str().fmt(f),
+            CommandError::Other(msg) => write!(f, "ERR {}", msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a command given as strings, and returns the error message if it is rejected.
+    fn parse_error(args: &[&str]) -> Option<String> {
+        let frame = args
+            .iter()
+            .map(|arg| RespType::BulkString(arg.to_string()))
+            .collect();
+        Command::from_resp_command_frame(frame)
+            .err()
+            .map(|e| e.to_string())
+    }
+
+    /// Commands given the wrong number of arguments fail with the same error as in Redis,
+    /// which client libraries recognize. Subcommands are named `command|subcommand`.
+    #[test]
+    fn wrong_arity_errors_use_redis_wording() {
+        let cases: &[(&[&str], &str)] = &[
+            (&["GET"], "get"),
+            (&["GET", "k", "extra"], "get"),
+            (&["SET", "k"], "set"),
+            (&["LPUSH", "k"], "lpush"),
+            (&["RPUSH", "k"], "rpush"),
+            (&["LRANGE", "k", "0"], "lrange"),
+            (&["PING", "a", "b"], "ping"),
+            (&["APPEND", "k"], "append"),
+            (&["BLPOP", "k"], "blpop"),
+            (&["BRPOP", "k"], "brpop"),
+            (&["CAS", "k", "old"], "cas"),
+            (&["CLIENT"], "client"),
+            (&["CLIENT", "ID", "extra"], "client|id"),
+            (&["CLIENT", "SETNAME"], "client|setname"),
+            (&["CLIENT", "GETNAME", "extra"], "client|getname"),
+            (&["CLIENT", "LIST", "extra"], "client|list"),
+            (&["CLIENT", "HELP", "extra"], "client|help"),
+            (&["CLUSTER"], "cluster"),
+            (&["CLUSTER", "INFO", "extra"], "cluster|info"),
+            (&["CLUSTER", "SLOTS", "extra"], "cluster|slots"),
+            (&["CLUSTER", "SHARDS", "extra"], "cluster|shards"),
+            (&["CLUSTER", "HELP", "extra"], "cluster|help"),
+            (&["COMMAND", "COUNT", "extra"], "command|count"),
+            (&["COMMAND", "HELP", "extra"], "command|help"),
+            (&["COPY", "k"], "copy"),
+            (&["DEBUG"], "debug"),
+            (&["DEBUG", "SLEEP"], "debug|sleep"),
+            (&["DEBUG", "OBJECT"], "debug|object"),
+            (&["DEBUG", "SET-ACTIVE-EXPIRE"], "debug|set-active-expire"),
+            (&["DEBUG", "HELP", "extra"], "debug|help"),
+            (&["DUMP"], "dump"),
+            (&["EXPIRE", "k"], "expire"),
+            (&["EXPIREAT", "k"], "expireat"),
+            (&["FLUSHALL", "ASYNC", "extra"], "flushall"),
+            (&["GETDEL"], "getdel"),
+            (&["GETEX"], "getex"),
+            (&["HKEYS"], "hkeys"),
+            (&["HMGET", "k"], "hmget"),
+            (&["HRANDFIELD"], "hrandfield"),
+            (&["HSET", "k", "f"], "hset"),
+            (&["HSETNX", "k", "f"], "hsetnx"),
+            (&["HVALS"], "hvals"),
+            (&["LATENCY"], "latency"),
+            (&["LATENCY", "RESET", "extra"], "latency|reset"),
+            (&["LPOS", "k"], "lpos"),
+            (&["MEMORY"], "memory"),
+            (&["MEMORY", "USAGE"], "memory|usage"),
+            (&["OBJECT"], "object"),
+            (&["OBJECT", "ENCODING"], "object|encoding"),
+            (&["OBJECT", "FREQ"], "object|freq"),
+            (&["OBJECT", "IDLETIME", "a", "b"], "object|idletime"),
+            (&["OBJECT", "REFCOUNT"], "object|refcount"),
+            (&["OBJECT", "HELP", "extra"], "object|help"),
+            (&["PEXPIRE", "k"], "pexpire"),
+            (&["PEXPIREAT", "k"], "pexpireat"),
+            (&["RENAME", "k"], "rename"),
+            (&["RENAMENX", "k"], "renamenx"),
+            (&["RESET", "extra"], "reset"),
+            (&["RESTORE", "k", "0"], "restore"),
+            (&["SADD", "k"], "sadd"),
+            (&["SINTERSTORE", "dest"], "sinterstore"),
+            (&["SMISMEMBER", "k"], "smismember"),
+            (&["STATS", "extra"], "stats"),
+            (&["TIME", "extra"], "time"),
+            (&["TOUCH"], "touch"),
+            (&["UNLINK"], "unlink"),
+            (&["WAIT", "0"], "wait"),
+            (&["ZADD", "k", "1"], "zadd"),
+            (&["ZCARD"], "zcard"),
+            (&["ZINCRBY", "k", "1"], "zincrby"),
+            (&["ZRANGEBYLEX", "k", "-"], "zrangebylex"),
+            (&["ZRANGEBYSCORE", "k", "0"], "zrangebyscore"),
+            (&["ZRANK", "k"], "zrank"),
+            (&["ZREM", "k"], "zrem"),
+            (&["ZREVRANK", "k"], "zrevrank"),
+        ];
+
+        for (args, cmd) in cases {
+            assert_eq!(
+                parse_error(args),
+                Some(format!(
+                    "ERR wrong number of arguments for '{}' command",
+                    cmd
+                )),
+                "{:?}",
+                args
+            );
+        }
+    }
+
+    /// Like in Redis, negative counts whose reply could overflow with WITHVALUES are rejected.
+    #[test]
+    fn hrandfield_rejects_counts_out_of_range() {
+        let limit = -(i64::MAX / 2);
+        for count in [limit - 1, i64::MIN] {
+            assert_eq!(
+                parse_error(&["HRANDFIELD", "h", &count.to_string()]).as_deref(),
+                Some("ERR value is out of range"),
+                "count {}",
+                count
+            );
+        }
+        for count in [limit, -1, 0, i64::MAX] {
+            assert_eq!(
+                parse_error(&["HRANDFIELD", "h", &count.to_string(), "WITHVALUES"]),
+                None,
+                "count {}",
+                count
+            );
         }
     }
 }
@@ -0,0 +1,42 @@
+// src/command/monitor.rs
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Represents the MONITOR command in MuDB.
+///
+/// The MONITOR command switches the connection into a streaming mode, in which it receives
+/// a line for every command executed by any connection. The streaming itself is done by
+/// the `FrameHandler` once the reply is sent.
+#[derive(Debug, Clone)]
+pub struct Monitor;
+
+impl Monitor {
+    /// Creates a new `Monitor` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the MONITOR command.
+    ///   The command takes no arguments.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Monitor)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Monitor, CommandError> {
+        if !args.is_empty() {
+            return Err(CommandError::WrongArity("monitor"));
+        }
+        Ok(Monitor)
+    }
+
+    /// Executes the MONITOR command.
+    ///
+    /// # Returns
+    ///
+    /// `SimpleString("OK")`.
+    pub fn apply(&self) -> RespType {
+        RespType::SimpleString(String::from("OK"))
+    }
+}
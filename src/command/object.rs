@@ -0,0 +1,161 @@
+// src/command/object.rs
+
+use crate::{config::MaxmemoryPolicy, resp::types::RespType, storage::db::DB};
+
+use super::{help::help_reply, CommandError};
+
+/// Represents the OBJECT command in MuDB.
+///
+/// The OBJECT command is used to inspect the internals of the value stored against a key.
+#[derive(Debug, Clone)]
+pub struct Object {
+    subcommand: ObjectSubcommand,
+}
+
+/// The subcommands supported by the OBJECT command.
+#[derive(Debug, Clone)]
+enum ObjectSubcommand {
+    /// OBJECT ENCODING key
+    Encoding(String),
+    /// OBJECT FREQ key
+    Freq(String),
+    /// OBJECT IDLETIME key
+    IdleTime(String),
+    /// OBJECT REFCOUNT key
+    RefCount(String),
+    /// OBJECT HELP
+    Help,
+}
+
+impl Object {
+    /// Creates a new `Object` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the OBJECT command.
+    ///   The first argument is the subcommand name.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Object)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Object, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::WrongArity("object"));
+        }
+
+        // parse subcommand
+        let subcommand = match &args[0] {
+            RespType::BulkString(s) => s.to_lowercase(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Subcommand must be a bulk string",
+                )));
+            }
+        };
+
+        let subcommand = match subcommand.as_str() {
+            "encoding" => ObjectSubcommand::Encoding(Self::parse_key(&args, "object|encoding")?),
+            "freq" => ObjectSubcommand::Freq(Self::parse_key(&args, "object|freq")?),
+            "idletime" => ObjectSubcommand::IdleTime(Self::parse_key(&args, "object|idletime")?),
+            "refcount" => ObjectSubcommand::RefCount(Self::parse_key(&args, "object|refcount")?),
+            "help" => {
+                if args.len() != 1 {
+                    return Err(CommandError::WrongArity("object|help"));
+                }
+                ObjectSubcommand::Help
+            }
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'OBJECT' command",
+                    subcommand
+                )));
+            }
+        };
+
+        Ok(Object { subcommand })
+    }
+
+    /// Parses the key argument of a subcommand, which must be the only argument after the subcommand name.
+    /// `cmd` is the lowercased name of the subcommand, for the arity error.
+    fn parse_key(args: &[RespType], cmd: &'static str) -> Result<String, CommandError> {
+        if args.len() != 2 {
+            return Err(CommandError::WrongArity(cmd));
+        }
+
+        match &args[1] {
+            RespType::BulkString(k) => Ok(k.to_string()),
+            _ => Err(CommandError::Other(String::from(
+                "Invalid argument. Key must be a bulk string",
+            ))),
+        }
+    }
+
+    /// Executes the OBJECT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// * `policy` - The eviction policy of the server (`--maxmemory-policy`).
+    ///
+    /// # Returns
+    ///
+    /// - ENCODING - The encoding of the value as a `BulkString`, or a `SimpleError` if the key is missing
+    /// - FREQ - The logarithmic access frequency counter of the key as an `Integer`, or a
+    ///   `SimpleError` if the key is missing or the eviction policy isn't an LFU one
+    /// - IDLETIME - The seconds since the key was last read or written as an `Integer`,
+    ///   or a `SimpleError` if the key is missing
+    /// - REFCOUNT - The number of references to the value as an `Integer`,
+    ///   or a `SimpleError` if the key is missing
+    /// - HELP - An `Array` of `SimpleString` lines describing the subcommands
+    pub fn apply(&self, db: &DB, policy: MaxmemoryPolicy) -> RespType {
+        match &self.subcommand {
+            ObjectSubcommand::Encoding(key) => match db.object_encoding(key) {
+                Ok(Some(encoding)) => RespType::BulkString(encoding.to_string()),
+                Ok(None) => RespType::SimpleError(String::from("ERR no such key")),
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+            // As in Redis, a missing key is reported before the missing LFU policy.
+            ObjectSubcommand::Freq(key) => match db.object_freq(key) {
+                Ok(Some(_)) if !policy.is_lfu() => RespType::SimpleError(String::from(
+                    "ERR An LFU maxmemory policy is not selected, access frequency not tracked.",
+                )),
+                Ok(Some(freq)) => RespType::Integer(freq as i64),
+                Ok(None) => RespType::SimpleError(String::from("ERR no such key")),
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+            ObjectSubcommand::IdleTime(key) => match db.object_idletime(key) {
+                Ok(Some(idle)) => RespType::Integer(idle.as_secs() as i64),
+                Ok(None) => RespType::SimpleError(String::from("ERR no such key")),
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+            ObjectSubcommand::RefCount(key) => match db.object_refcount(key) {
+                Ok(Some(refcount)) => RespType::Integer(refcount as i64),
+                Ok(None) => RespType::SimpleError(String::from("ERR no such key")),
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+            ObjectSubcommand::Help => help_reply(
+                "OBJECT",
+                &[
+                    (
+                        "ENCODING <key>",
+                        "Return the kind of internal representation used to store the value of <key>.",
+                    ),
+                    (
+                        "FREQ <key>",
+                        "Return the access frequency index of <key>. Requires an LFU maxmemory policy.",
+                    ),
+                    (
+                        "IDLETIME <key>",
+                        "Return the idle time of <key>, in seconds.",
+                    ),
+                    (
+                        "REFCOUNT <key>",
+                        "Return the number of references of the value of <key>.",
+                    ),
+                ],
+            ),
+        }
+    }
+}
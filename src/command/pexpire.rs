@@ -0,0 +1,93 @@
+// src/command/pexpire.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{ExpireCondition, DB},
+};
+
+use super::{expire::Expire, CommandError};
+
+/// Represents the PEXPIRE command in MuDB.
+///
+/// The PEXPIRE command sets a time to live (in milliseconds) after which the key is deleted.
+#[derive(Debug, Clone)]
+pub struct PExpire {
+    key: String,
+    /// Time to live of the key, in milliseconds.
+    ttl_ms: i64,
+    /// The conditions under which the expiry time is updated.
+    condition: ExpireCondition,
+}
+
+impl PExpire {
+    /// Creates a new `PExpire` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PEXPIRE command.
+    ///   The key and the number of milliseconds can be followed by the `NX`, `XX`, `GT` and `LT` options.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PExpire)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PExpire, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::WrongArity("pexpire"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse time to live
+        let ttl = match &args[1] {
+            RespType::BulkString(t) => t.parse::<i64>(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+        };
+        let ttl_ms = match ttl {
+            Ok(t) => t,
+            Err(_) => {
+                return Err(CommandError::Other(String::from(
+                    "ERR value is not an integer or out of range",
+                )));
+            }
+        };
+
+        let condition = Expire::parse_condition(&args[2..])?;
+
+        Ok(PExpire {
+            key,
+            ttl_ms,
+            condition,
+        })
+    }
+
+    /// Executes the PEXPIRE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(1)` if the expiry was set, or the key was deleted because the time to live isn't positive
+    /// - `Integer(0)` if the key doesn't exist, or the expiry wasn't changed because of the given options
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.expire(&self.key, self.ttl_ms, self.condition) {
+            Ok(updated) => RespType::Integer(updated as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// src/command/pexpireat.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{ExpireCondition, DB},
+};
+
+use super::CommandError;
+
+/// Represents the PEXPIREAT command in MuDB.
+///
+/// The PEXPIREAT command sets an absolute Unix timestamp (in milliseconds) after which the key
+/// is deleted.
+#[derive(Debug, Clone)]
+pub struct PExpireAt {
+    key: String,
+    /// Unix time, in milliseconds, at which the key expires.
+    unix_ms: i64,
+}
+
+impl PExpireAt {
+    /// Creates a new `PExpireAt` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PEXPIREAT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PExpireAt)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PExpireAt, CommandError> {
+        if args.len() != 2 {
+            return Err(CommandError::WrongArity("pexpireat"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse timestamp
+        let timestamp = match &args[1] {
+            RespType::BulkString(t) => t.parse::<i64>(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+        };
+        let unix_ms = match timestamp {
+            Ok(t) => t,
+            Err(_) => {
+                return Err(CommandError::Other(String::from(
+                    "ERR value is not an integer or out of range",
+                )));
+            }
+        };
+
+        Ok(PExpireAt { key, unix_ms })
+    }
+
+    /// Executes the PEXPIREAT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(1)` if the expiry was set, or the key was deleted because the timestamp is in the past
+    /// - `Integer(0)` if the key doesn't exist
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.expire_at(&self.key, self.unix_ms, ExpireCondition::default()) {
+            Ok(updated) => RespType::Integer(updated as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
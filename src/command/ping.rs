@@ -20,12 +20,15 @@ impl Ping {
     ///
     /// * `Ok(Ping)` if parsing succeeds.
     /// * `Err(CommandError)` if parsing fails.
-    
     pub fn with_args(args: Vec<RespType>) -> Result<Ping, CommandError> {
-        if args.len() == 0 {
+        if args.is_empty() {
             return Ok(Ping { message: None });
         }
 
+        if args.len() > 1 {
+            return Err(CommandError::WrongArity("ping"));
+        }
+
         let message = match &args[0] {
             RespType::BulkString(s) => s.clone(),
             _ => return Err(CommandError::Other(String::from("Invalid message"))),
@@ -48,4 +51,35 @@ impl Ping {
             RespType::SimpleString(String::from("PONG"))
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(strs: &[&str]) -> Vec<RespType> {
+        strs.iter()
+            .map(|s| RespType::BulkString(s.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn ping_without_arguments_replies_pong() {
+        let ping = Ping::with_args(args(&[])).unwrap();
+        assert_eq!(ping.apply(), RespType::SimpleString(String::from("PONG")));
+    }
+
+    #[test]
+    fn ping_with_a_message_echoes_it() {
+        let ping = Ping::with_args(args(&["hello"])).unwrap();
+        assert_eq!(ping.apply(), RespType::BulkString(String::from("hello")));
+    }
+
+    #[test]
+    fn ping_with_more_arguments_is_rejected() {
+        let err = Ping::with_args(args(&["hello", "world"])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ERR wrong number of arguments for 'ping' command"
+        );
+    }
+}
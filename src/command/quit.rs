@@ -0,0 +1,40 @@
+// src/command/quit.rs
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Represents the QUIT command in MuDB.
+///
+/// The QUIT command asks the server to close the connection. The server replies `OK`
+/// and closes the connection once the reply is flushed, so that clients shutting down
+/// a connection pool get a clean reply instead of an error.
+#[derive(Debug, Clone)]
+pub struct Quit;
+
+impl Quit {
+    /// Creates a new `Quit` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the QUIT command.
+    ///   Like in Redis, any arguments are ignored.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Quit)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(_args: Vec<RespType>) -> Result<Quit, CommandError> {
+        Ok(Quit)
+    }
+
+    /// Executes the QUIT command. The connection itself is closed by the `FrameHandler`
+    /// after the reply is sent.
+    ///
+    /// # Returns
+    ///
+    /// `SimpleString("OK")`.
+    pub fn apply(&self) -> RespType {
+        RespType::SimpleString(String::from("OK"))
+    }
+}
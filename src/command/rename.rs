@@ -0,0 +1,66 @@
+// src/command/rename.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the RENAME command in MuDB.
+///
+/// The RENAME command renames a key, overwriting the destination key if it exists.
+#[derive(Debug, Clone)]
+pub struct Rename {
+    src: String,
+    dst: String,
+}
+
+impl Rename {
+    /// Creates a new `Rename` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the RENAME command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Rename)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Rename, CommandError> {
+        if args.len() != 2 {
+            return Err(CommandError::WrongArity("rename"));
+        }
+
+        // parse source and destination keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+        let dst = keys.pop().unwrap();
+        let src = keys.pop().unwrap();
+
+        Ok(Rename { src, dst })
+    }
+
+    /// Executes the RENAME command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `SimpleString("OK")` if the key was renamed
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.rename(&self.src, &self.dst) {
+            Ok(()) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
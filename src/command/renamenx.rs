@@ -0,0 +1,67 @@
+// src/command/renamenx.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the RENAMENX command in MuDB.
+///
+/// The RENAMENX command renames a key, only if the destination key doesn't exist.
+#[derive(Debug, Clone)]
+pub struct RenameNx {
+    src: String,
+    dst: String,
+}
+
+impl RenameNx {
+    /// Creates a new `RenameNx` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the RENAMENX command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RenameNx)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<RenameNx, CommandError> {
+        if args.len() != 2 {
+            return Err(CommandError::WrongArity("renamenx"));
+        }
+
+        // parse source and destination keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+        let dst = keys.pop().unwrap();
+        let src = keys.pop().unwrap();
+
+        Ok(RenameNx { src, dst })
+    }
+
+    /// Executes the RENAMENX command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(1)` if the key was renamed
+    /// - `Integer(0)` if the destination key already exists
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.renamenx(&self.src, &self.dst) {
+            Ok(renamed) => RespType::Integer(renamed as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
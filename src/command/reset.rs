@@ -0,0 +1,47 @@
+// src/command/reset.rs
+
+use crate::{client::ClientHandle, resp::types::RespType};
+
+use super::CommandError;
+
+/// Represents the RESET command in MuDB.
+///
+/// The RESET command clears the state of the current connection, such as its name,
+/// so that a pooled connection can be reused without reconnecting.
+#[derive(Debug, Clone)]
+pub struct Reset;
+
+impl Reset {
+    /// Creates a new `Reset` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the RESET command.
+    ///   RESET does not accept any arguments.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Reset)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Reset, CommandError> {
+        if !args.is_empty() {
+            return Err(CommandError::WrongArity("reset"));
+        }
+
+        Ok(Reset)
+    }
+
+    /// Executes the RESET command.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The connection the command was received on.
+    ///
+    /// # Returns
+    ///
+    /// `SimpleString("RESET")` once the state of the connection is cleared.
+    pub fn apply(&self, client: &ClientHandle) -> RespType {
+        client.reset();
+        RespType::SimpleString(String::from("RESET"))
+    }
+}
@@ -0,0 +1,129 @@
+// src/command/restore.rs
+
+use std::time::Duration;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        db::{Value, DB},
+        dump,
+    },
+};
+
+use super::CommandError;
+
+/// Represents the RESTORE command in MuDB.
+///
+/// The RESTORE command stores a value serialized with the DUMP command against a key.
+#[derive(Debug, Clone)]
+pub struct Restore {
+    key: String,
+    /// Time to live of the key. `None` if the key doesn't expire.
+    ttl: Option<Duration>,
+    /// The deserialized value.
+    value: Value,
+    /// Whether an existing key should be overwritten.
+    replace: bool,
+}
+
+impl Restore {
+    /// Creates a new `Restore` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the RESTORE command.
+    ///   The key, the time to live in milliseconds (0 for no expiry) and the serialized value
+    ///   can be followed by the optional `REPLACE` keyword.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Restore)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails, or the serialized value is corrupt.
+    pub fn with_args(args: Vec<RespType>) -> Result<Restore, CommandError> {
+        if args.len() < 3 || args.len() > 4 {
+            return Err(CommandError::WrongArity("restore"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse time to live
+        let ttl = match &args[1] {
+            RespType::BulkString(ttl) => match ttl.parse::<i64>() {
+                Ok(0) => None,
+                Ok(ttl) if ttl > 0 => Some(Duration::from_millis(ttl as u64)),
+                Ok(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "ERR Invalid TTL value, must be >= 0",
+                    )));
+                }
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "ERR value is not an integer or out of range",
+                    )));
+                }
+            },
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. TTL must be a bulk string",
+                )));
+            }
+        };
+
+        // parse and check the serialized value
+        let value = match &args[2] {
+            RespType::BulkString(payload) => match dump::deserialize(payload) {
+                Some(value) => value,
+                None => {
+                    return Err(CommandError::Other(String::from(
+                        "ERR DUMP payload version or checksum are wrong",
+                    )));
+                }
+            },
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Serialized value must be a bulk string",
+                )));
+            }
+        };
+
+        // parse REPLACE option
+        let replace = match args.get(3) {
+            None => false,
+            Some(RespType::BulkString(opt)) if opt.eq_ignore_ascii_case("replace") => true,
+            Some(_) => return Err(CommandError::Other(String::from("ERR syntax error"))),
+        };
+
+        Ok(Restore {
+            key,
+            ttl,
+            value,
+            replace,
+        })
+    }
+
+    /// Executes the RESTORE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `SimpleString("OK")` if the value was stored
+    /// - If the key exists and `REPLACE` wasn't given, or another error is encountered -
+    ///   A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.restore(&self.key, self.value.clone(), self.ttl, self.replace) {
+            Ok(()) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+// src/command/sadd.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SADD command in MuDB.
+#[derive(Debug, Clone)]
+pub struct SAdd {
+    key: String,
+    members: Vec<String>,
+}
+
+impl SAdd {
+    /// Creates a new `SADD` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SADD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SAdd, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::WrongArity("sadd"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse members
+        let mut members: Vec<String> = vec![];
+        for arg in args[1..].iter() {
+            match arg {
+                RespType::BulkString(f) => members.push(f.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Member must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(SAdd { key, members })
+    }
+
+    /// Executes the SADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of members that were added to the set.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.sadd(self.key.clone(), self.members.clone()) {
+            Ok(added) => RespType::Integer(added as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// src/command/scan.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{scan_args::KeyspaceScanArgs, CommandError};
+
+/// Represents the SCAN command in MuDB.
+///
+/// The SCAN command iterates the keys of the DB with a cursor, a page at a time.
+#[derive(Debug, Clone)]
+pub struct Scan {
+    args: KeyspaceScanArgs,
+}
+
+impl Scan {
+    /// Creates a new `Scan` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SCAN command:
+    ///   the cursor, and the optional MATCH, COUNT and TYPE options.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Scan)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Scan, CommandError> {
+        let args = KeyspaceScanArgs::parse(args)?;
+        Ok(Scan { args })
+    }
+
+    /// Executes the SCAN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - An `Array` of the next cursor as a `BulkString` ("0" once the iteration is complete)
+    ///   and an `Array` of the keys of the page
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        let args = &self.args;
+        match db.scan(
+            args.cursor,
+            args.pattern.as_deref(),
+            args.count,
+            args.type_name.as_deref(),
+        ) {
+            Ok((next, page)) => RespType::Array(vec![
+                RespType::BulkString(next.to_string()),
+                RespType::Array(page.into_iter().map(RespType::BulkString).collect()),
+            ]),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+// src/command/scan_args.rs
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Number of items visited by a scan command when COUNT isn't given, like in Redis.
+const DEFAULT_SCAN_COUNT: usize = 10;
+
+/// The arguments shared by the HSCAN, SSCAN and ZSCAN commands:
+/// `key cursor [MATCH pattern] [COUNT count]`.
+#[derive(Debug, Clone)]
+pub struct ScanArgs {
+    pub key: String,
+    pub cursor: u64,
+    /// Glob-style pattern of the items to return (`MATCH` option).
+    pub pattern: Option<String>,
+    /// Number of items visited by the call (`COUNT` option).
+    pub count: usize,
+}
+
+impl ScanArgs {
+    /// Parses the arguments of a scan command.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `cmd` - The lowercased name of the command, for the arity error.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ScanArgs)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn parse(args: Vec<RespType>, cmd: &'static str) -> Result<ScanArgs, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::WrongArity(cmd));
+        }
+
+        let mut strs: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key, cursor and options must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let options = KeyspaceScanArgs::from_strs(&strs[1..], false)?;
+        Ok(ScanArgs {
+            key: strs[0].clone(),
+            cursor: options.cursor,
+            pattern: options.pattern,
+            count: options.count,
+        })
+    }
+}
+
+/// The arguments of the SCAN command: `cursor [MATCH pattern] [COUNT count] [TYPE type]`.
+#[derive(Debug, Clone)]
+pub struct KeyspaceScanArgs {
+    pub cursor: u64,
+    /// Glob-style pattern of the keys to return (`MATCH` option).
+    pub pattern: Option<String>,
+    /// Number of keys visited by the call (`COUNT` option).
+    pub count: usize,
+    /// Type of the values of the keys to return (`TYPE` option), such as "list".
+    pub type_name: Option<String>,
+}
+
+impl KeyspaceScanArgs {
+    /// Parses the arguments of the SCAN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SCAN command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(KeyspaceScanArgs)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn parse(args: Vec<RespType>) -> Result<KeyspaceScanArgs, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::WrongArity("scan"));
+        }
+
+        let mut strs: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => strs.push(s.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Cursor and options must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        Self::from_strs(&strs, true)
+    }
+
+    /// Parses the cursor and the options which follow it. The TYPE option is only accepted
+    /// if `allow_type` is set, since it only applies to SCAN.
+    fn from_strs(strs: &[String], allow_type: bool) -> Result<KeyspaceScanArgs, CommandError> {
+        let cursor = strs[0]
+            .parse::<u64>()
+            .map_err(|_| CommandError::Other(String::from("ERR invalid cursor")))?;
+
+        let mut pattern = None;
+        let mut count = DEFAULT_SCAN_COUNT;
+        let mut type_name = None;
+        let mut options = strs[1..].iter();
+        while let Some(option) = options.next() {
+            let value = match options.next() {
+                Some(value) => value,
+                None => return Err(CommandError::Other(String::from("ERR syntax error"))),
+            };
+            match option.to_lowercase().as_str() {
+                "match" => pattern = Some(value.clone()),
+                "count" => {
+                    count = value.parse::<usize>().map_err(|_| {
+                        CommandError::Other(String::from(
+                            "ERR value is not an integer or out of range",
+                        ))
+                    })?;
+                    if count == 0 {
+                        return Err(CommandError::Other(String::from("ERR syntax error")));
+                    }
+                }
+                "type" if allow_type => type_name = Some(value.to_lowercase()),
+                _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+            }
+        }
+
+        Ok(KeyspaceScanArgs {
+            cursor,
+            pattern,
+            count,
+            type_name,
+        })
+    }
+}
@@ -1,9 +1,14 @@
+use std::time::Duration;
+
 use crate::{
     resp::types::RespType,
     storage::db::{Value, DB},
 };
 
-use super::CommandError;
+use super::{
+    ttl::{parse_ttl, TtlUnit},
+    CommandError,
+};
 
 /// Represents the SET command in Nimblecache.
 ///
@@ -13,6 +18,10 @@ use super::CommandError;
 pub struct Set {
     key: String,
     value: String,
+    /// Whether the previous value should be returned instead of `OK` (`GET` option).
+    get: bool,
+    /// Time after which the key expires (`EX` or `PX` option). `None` if the key doesn't expire.
+    ttl: Option<Duration>,
 }
 
 impl Set {
@@ -24,17 +33,15 @@ impl Set {
     /// # Arguments
     ///
     /// * `args` - A vector of `RespType` representing the arguments to the SET command.
+    ///   The key and value can be followed by the `GET`, `EX seconds` and `PX milliseconds` options.
     ///
     /// # Returns
     ///
     /// * `Ok(Set)` - If parsing succeeds and the key-value pair is valid.
     /// * `Err(CommandError)` - if parsing fails due to validation errors.
-    
     pub fn with_args(args: Vec<RespType>) -> Result<Set, CommandError> {
         if args.len() < 2 {
-            return Err(CommandError::Other(String::from(
-                "Wrong number of arguments specified for 'SET' command",
-            )));
+            return Err(CommandError::WrongArity("set"));
         }
 
         // parse key
@@ -59,16 +66,44 @@ impl Set {
             }
         };
 
+        // parse options
+        let mut get = false;
+        let mut ttl = None;
+        let mut options = args[2..].iter();
+        while let Some(arg) = options.next() {
+            let opt = match arg {
+                RespType::BulkString(opt) => opt.to_lowercase(),
+                _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+            };
+            let unit = match opt.as_str() {
+                "get" => {
+                    get = true;
+                    continue;
+                }
+                "ex" => TtlUnit::Seconds,
+                "px" => TtlUnit::Milliseconds,
+                _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+            };
+            // EX and PX can't be combined, nor given twice.
+            match (ttl, options.next()) {
+                (None, Some(arg)) => ttl = Some(parse_ttl(arg, unit, "set")?),
+                _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+            }
+        }
+
         Ok(Set {
             key: key.to_string(),
             value,
+            get,
+            ttl,
         })
     }
 
     /// Executes the SET command.
     ///
     /// This method writes the string value to the database under the specified key.
-    /// If the operation is successful, it returns an "OK" response as a `BulkString`.
+    /// If the operation is successful, it returns an "OK" response as a `BulkString`,
+    /// or the previous value if the `GET` option was given.
     /// If the operation fails, it returns an error response.
     ///
     /// # Arguments
@@ -78,9 +113,14 @@ impl Set {
     /// # Returns
     ///
     /// * `BulkString("OK")` - If the value is successfully written.
+    /// * `BulkString` / `NullBulkString` - The previous value, if the `GET` option was given.
     /// * `SimpleError` - If the operation fails due to some error.
     pub fn apply(&self, db: &DB) -> RespType {
-        match db.set(self.key.clone(), Value::String(self.value.clone())) {
+        match db.set(self.key.clone(), Value::String(self.value.clone()), self.ttl) {
+            Ok(prev) if self.get => match prev {
+                Some(s) => RespType::BulkString(s),
+                None => RespType::NullBulkString,
+            },
             Ok(_) => RespType::BulkString("OK".to_string()),
             Err(e) => RespType::SimpleError(format!("{}", e)),
         }
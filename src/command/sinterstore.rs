@@ -0,0 +1,62 @@
+// src/command/sinterstore.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SINTERSTORE command in MuDB.
+#[derive(Debug, Clone)]
+pub struct SInterStore {
+    destination: String,
+    keys: Vec<String>,
+}
+
+impl SInterStore {
+    /// Creates a new `SINTERSTORE` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SINTERSTORE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SInterStore)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SInterStore, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::WrongArity("sinterstore"));
+        }
+
+        // parse destination and source keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+        let destination = keys.remove(0);
+
+        Ok(SInterStore { destination, keys })
+    }
+
+    /// Executes the SINTERSTORE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Integer` with the number of members in the resulting set.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.sinterstore(self.destination.clone(), &self.keys) {
+            Ok(len) => RespType::Integer(len as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
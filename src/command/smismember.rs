@@ -0,0 +1,77 @@
+// src/command/smismember.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SMISMEMBER command in MuDB.
+#[derive(Debug, Clone)]
+pub struct SMIsMember {
+    key: String,
+    members: Vec<String>,
+}
+
+impl SMIsMember {
+    /// Creates a new `SMISMEMBER` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SMISMEMBER command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SMIsMember)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SMIsMember, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::WrongArity("smismember"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse members
+        let mut members: Vec<String> = vec![];
+        for arg in args[1..].iter() {
+            match arg {
+                RespType::BulkString(f) => members.push(f.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Member must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(SMIsMember { key, members })
+    }
+
+    /// Executes the SMISMEMBER command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` with an `Integer` for each requested member: `1` if it belongs to the set,
+    /// else `0`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.smismember(&self.key, &self.members) {
+            Ok(found) => RespType::Array(
+                found
+                    .into_iter()
+                    .map(|f| RespType::Integer(f as i64))
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+// src/command/sscan.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{scan_args::ScanArgs, CommandError};
+
+/// Represents the SSCAN command in MuDB.
+///
+/// The SSCAN command iterates the set stored at a key with a cursor, a page at a time.
+#[derive(Debug, Clone)]
+pub struct SScan {
+    args: ScanArgs,
+}
+
+impl SScan {
+    /// Creates a new `SScan` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SSCAN command:
+    ///   the key, the cursor, and the optional MATCH and COUNT options.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SScan)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SScan, CommandError> {
+        let args = ScanArgs::parse(args, "sscan")?;
+        Ok(SScan { args })
+    }
+
+    /// Executes the SSCAN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - An `Array` of the next cursor as a `BulkString` ("0" once the iteration is complete)
+    ///   and an `Array` of the members of the page
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        let args = &self.args;
+        match db.sscan(&args.key, args.cursor, args.pattern.as_deref(), args.count) {
+            Ok((next, page)) => RespType::Array(vec![
+                RespType::BulkString(next.to_string()),
+                RespType::Array(page.into_iter().map(RespType::BulkString).collect()),
+            ]),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// src/command/stats.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the STATS command in MuDB.
+///
+/// The STATS command reports server wide counters, such as the number of connected clients.
+#[derive(Debug, Clone)]
+pub struct Stats;
+
+impl Stats {
+    /// Creates a new `Stats` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the STATS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Stats)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Stats, CommandError> {
+        if !args.is_empty() {
+            return Err(CommandError::WrongArity("stats"));
+        }
+
+        Ok(Stats)
+    }
+
+    /// Executes the STATS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database which holds the server stats.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` with a `[name, value]` pair for each counter.
+    pub fn apply(&self, db: &DB) -> RespType {
+        RespType::Array(
+            db.stats()
+                .snapshot()
+                .into_iter()
+                .map(|(name, value)| {
+                    RespType::Array(vec![
+                        RespType::BulkString(name.to_string()),
+                        RespType::Integer(value as i64),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,50 @@
+// src/command/time.rs
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Represents the TIME command in MuDB.
+///
+/// The TIME command returns the current server time.
+#[derive(Debug, Clone)]
+pub struct Time;
+
+impl Time {
+    /// Creates a new `Time` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TIME command.
+    ///   TIME does not accept any arguments.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Time)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Time, CommandError> {
+        if !args.is_empty() {
+            return Err(CommandError::WrongArity("time"));
+        }
+
+        Ok(Time)
+    }
+
+    /// Executes the TIME command.
+    ///
+    /// # Returns
+    ///
+    /// A two-element `Array` of `BulkString`s: the Unix time in seconds and the
+    /// microseconds already elapsed in the current second.
+    pub fn apply(&self) -> RespType {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(now) => RespType::Array(vec![
+                RespType::BulkString(now.as_secs().to_string()),
+                RespType::BulkString(now.subsec_micros().to_string()),
+            ]),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
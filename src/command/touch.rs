@@ -0,0 +1,63 @@
+// src/command/touch.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the TOUCH command in MuDB.
+///
+/// The TOUCH command records an access to the given keys without changing their values,
+/// so that a cache-warming process can mark keys as recently used.
+#[derive(Debug, Clone)]
+pub struct Touch {
+    keys: Vec<String>,
+}
+
+impl Touch {
+    /// Creates a new `Touch` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TOUCH command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Touch)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Touch, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::WrongArity("touch"));
+        }
+
+        // parse keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(Touch { keys })
+    }
+
+    /// Executes the TOUCH command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The number of keys which exist and were touched, as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.touch(&self.keys) {
+            Ok(touched) => RespType::Integer(touched as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
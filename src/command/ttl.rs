@@ -0,0 +1,59 @@
+// src/command/ttl.rs
+
+use std::time::Duration;
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// The unit of a time to live given to a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TtlUnit {
+    /// `EX` option, or a command such as EXPIRE.
+    Seconds,
+    /// `PX` option, or a command such as PEXPIRE.
+    Milliseconds,
+}
+
+/// Parses a relative time to live for the commands which reject keys that would expire
+/// immediately, such as the `EX` and `PX` options of SET and GETEX. Like in Redis, a time to
+/// live which isn't positive, or which overflows once converted to milliseconds, is rejected
+/// when the command is parsed, so the DB is never modified.
+///
+/// # Arguments
+///
+/// * `arg` - The time to live as an integer in bulk string format.
+///
+/// * `unit` - The unit of the time to live.
+///
+/// * `cmd` - The lowercased name of the command, for the error message.
+///
+/// # Returns
+///
+/// * `Ok(Duration)` - The time to live.
+/// * `Err(CommandError)` - if the time to live isn't an integer, or isn't positive.
+pub fn parse_ttl(arg: &RespType, unit: TtlUnit, cmd: &str) -> Result<Duration, CommandError> {
+    let ttl = match arg {
+        RespType::BulkString(t) => match t.parse::<i64>() {
+            Ok(ttl) => ttl,
+            Err(_) => {
+                return Err(CommandError::Other(String::from(
+                    "ERR value is not an integer or out of range",
+                )));
+            }
+        },
+        _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+    };
+
+    let ttl_ms = match unit {
+        TtlUnit::Seconds => ttl.checked_mul(1000),
+        TtlUnit::Milliseconds => Some(ttl),
+    };
+    match ttl_ms {
+        Some(ttl_ms) if ttl_ms > 0 => Ok(Duration::from_millis(ttl_ms as u64)),
+        _ => Err(CommandError::Other(format!(
+            "ERR invalid expire time in '{}' command",
+            cmd
+        ))),
+    }
+}
@@ -0,0 +1,63 @@
+// src/command/unlink.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the UNLINK command in MuDB.
+///
+/// The UNLINK command deletes the given keys. Unlike a plain delete, the memory of large
+/// values is reclaimed in the background, so the connection isn't stalled freeing them.
+#[derive(Debug, Clone)]
+pub struct Unlink {
+    keys: Vec<String>,
+}
+
+impl Unlink {
+    /// Creates a new `Unlink` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the UNLINK command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Unlink)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Unlink, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::WrongArity("unlink"));
+        }
+
+        // parse keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(Unlink { keys })
+    }
+
+    /// Executes the UNLINK command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The number of keys which were deleted, as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.unlink(&self.keys) {
+            Ok(removed) => RespType::Integer(removed as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+// src/command/wait.rs
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Represents the WAIT command in MuDB.
+///
+/// The WAIT command blocks until previous writes are acknowledged by the given number of
+/// replicas. MuDB runs as a single node without replicas, so the command replies immediately.
+#[derive(Debug, Clone)]
+pub struct Wait;
+
+impl Wait {
+    /// Creates a new `Wait` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the WAIT command.
+    ///   The arguments are the number of replicas and the timeout in milliseconds.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Wait)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Wait, CommandError> {
+        if args.len() != 2 {
+            return Err(CommandError::WrongArity("wait"));
+        }
+
+        // parse number of replicas and timeout
+        let mut values: Vec<i64> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(v) => match v.parse::<i64>() {
+                    Ok(v) => values.push(v),
+                    Err(_) => {
+                        return Err(CommandError::Other(String::from(
+                            "ERR value is not an integer or out of range",
+                        )));
+                    }
+                },
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Value must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        if values[1] < 0 {
+            return Err(CommandError::Other(String::from("ERR timeout is negative")));
+        }
+
+        Ok(Wait)
+    }
+
+    /// Executes the WAIT command.
+    ///
+    /// # Returns
+    ///
+    /// `Integer(0)`, the number of replicas which acknowledged the writes.
+    pub fn apply(&self) -> RespType {
+        RespType::Integer(0)
+    }
+}
@@ -0,0 +1,90 @@
+// src/command/zadd.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the ZADD command in MuDB.
+#[derive(Debug, Clone)]
+pub struct ZAdd {
+    key: String,
+    members: Vec<(f64, String)>,
+}
+
+impl ZAdd {
+    /// Creates a new `ZADD` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZADD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZAdd, CommandError> {
+        if args.len() < 3 || !(args.len() - 1).is_multiple_of(2) {
+            return Err(CommandError::WrongArity("zadd"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse score and member pairs
+        let mut members: Vec<(f64, String)> = vec![];
+        for pair in args[1..].chunks(2) {
+            let score = match &pair[0] {
+                RespType::BulkString(s) => match s.parse::<f64>() {
+                    Ok(score) if !score.is_nan() => score,
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "ERR value is not a valid float",
+                        )));
+                    }
+                },
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Score must be a bulk string",
+                    )));
+                }
+            };
+
+            let member = match &pair[1] {
+                RespType::BulkString(m) => m.to_string(),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Member must be a bulk string",
+                    )));
+                }
+            };
+
+            members.push((score, member));
+        }
+
+        Ok(ZAdd { key, members })
+    }
+
+    /// Executes the ZADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Integer` with the number of members added to the sorted set, not including
+    /// members whose score was updated.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zadd(self.key.clone(), self.members.clone()) {
+            Ok(added) => RespType::Integer(added as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
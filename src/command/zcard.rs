@@ -0,0 +1,57 @@
+// src/command/zcard.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the ZCARD command in MuDB.
+#[derive(Debug, Clone)]
+pub struct ZCard {
+    key: String,
+}
+
+impl ZCard {
+    /// Creates a new `ZCARD` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZCARD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZCard)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZCard, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::WrongArity("zcard"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(ZCard { key })
+    }
+
+    /// Executes the ZCARD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The number of members of the sorted set as an `Integer`, `0` if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zcard(&self.key) {
+            Ok(len) => RespType::Integer(len as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// src/command/zincrby.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the ZINCRBY command in MuDB.
+#[derive(Debug, Clone)]
+pub struct ZIncrBy {
+    key: String,
+    delta: f64,
+    member: String,
+}
+
+impl ZIncrBy {
+    /// Creates a new `ZINCRBY` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZINCRBY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZIncrBy)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZIncrBy, CommandError> {
+        if args.len() != 3 {
+            return Err(CommandError::WrongArity("zincrby"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse delta
+        let delta = match &args[1] {
+            RespType::BulkString(d) => match d.parse::<f64>() {
+                Ok(delta) if !delta.is_nan() => delta,
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "ERR value is not a valid float",
+                    )));
+                }
+            },
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Increment must be a bulk string",
+                )));
+            }
+        };
+
+        // parse member
+        let member = match &args[2] {
+            RespType::BulkString(m) => m.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Member must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(ZIncrBy { key, delta, member })
+    }
+
+    /// Executes the ZINCRBY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// A `BulkString` with the new score of the member.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zincrby(&self.key, self.delta, &self.member) {
+            Ok(score) => RespType::BulkString(score.to_string()),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// src/command/zrangebylex.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, sorted_set::LexBound},
+};
+
+use super::CommandError;
+
+/// Represents the ZRANGEBYLEX command in MuDB.
+///
+/// The ZRANGEBYLEX command returns the members of a sorted set within a lexicographic range.
+/// As in Redis, the result is only well-defined when all the members have the same score,
+/// which is how sorted sets are used for autocomplete-style indexes.
+#[derive(Debug, Clone)]
+pub struct ZRangeByLex {
+    key: String,
+    min: LexBound,
+    max: LexBound,
+}
+
+impl ZRangeByLex {
+    /// Creates a new `ZRANGEBYLEX` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZRANGEBYLEX command.
+    ///   The bounds are either `-` or `+`, or a member prefixed with `[` (inclusive) or `(` (exclusive).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZRangeByLex)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZRangeByLex, CommandError> {
+        if args.len() != 3 {
+            return Err(CommandError::WrongArity("zrangebylex"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse min and max
+        let mut bounds: Vec<LexBound> = vec![];
+        for arg in args[1..3].iter() {
+            let bound = match arg {
+                RespType::BulkString(b) => LexBound::parse(b),
+                _ => None,
+            };
+
+            match bound {
+                Some(b) => bounds.push(b),
+                None => {
+                    return Err(CommandError::Other(String::from(
+                        "ERR min or max not valid string range item",
+                    )));
+                }
+            }
+        }
+        let max = bounds.pop().unwrap();
+        let min = bounds.pop().unwrap();
+
+        Ok(ZRangeByLex { key, min, max })
+    }
+
+    /// Executes the ZRANGEBYLEX command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` with the members which fall in the range, empty if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zrangebylex(&self.key, &self.min, &self.max) {
+            Ok(members) => RespType::Array(members.into_iter().map(RespType::BulkString).collect()),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+// src/command/zrangebyscore.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, sorted_set::ScoreBound},
+};
+
+use super::CommandError;
+
+/// Represents the ZRANGEBYSCORE command in MuDB.
+#[derive(Debug, Clone)]
+pub struct ZRangeByScore {
+    key: String,
+    min: ScoreBound,
+    max: ScoreBound,
+    with_scores: bool,
+}
+
+impl ZRangeByScore {
+    /// Creates a new `ZRANGEBYSCORE` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZRANGEBYSCORE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZRangeByScore)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZRangeByScore, CommandError> {
+        if args.len() < 3 {
+            return Err(CommandError::WrongArity("zrangebyscore"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse min and max
+        let mut bounds: Vec<ScoreBound> = vec![];
+        for arg in args[1..3].iter() {
+            let bound = match arg {
+                RespType::BulkString(b) => ScoreBound::parse(b),
+                _ => None,
+            };
+
+            match bound {
+                Some(b) => bounds.push(b),
+                None => {
+                    return Err(CommandError::Other(String::from(
+                        "ERR min or max is not a float",
+                    )));
+                }
+            }
+        }
+
+        // parse options
+        let mut with_scores = false;
+        for arg in args[3..].iter() {
+            match arg {
+                RespType::BulkString(opt) if opt.to_lowercase() == "withscores" => {
+                    with_scores = true;
+                }
+                _ => return Err(CommandError::Other(String::from("ERR syntax error"))),
+            }
+        }
+
+        Ok(ZRangeByScore {
+            key,
+            min: bounds[0],
+            max: bounds[1],
+            with_scores,
+        })
+    }
+
+    /// Executes the ZRANGEBYSCORE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` with the members whose score falls in the range, ordered by ascending score.
+    /// With WITHSCORES, each member is followed by its score.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zrangebyscore(&self.key, self.min, self.max) {
+            Ok(members) => {
+                let mut reply: Vec<RespType> = vec![];
+                for (member, score) in members {
+                    reply.push(RespType::BulkString(member));
+                    if self.with_scores {
+                        reply.push(RespType::BulkString(score.to_string()));
+                    }
+                }
+                RespType::Array(reply)
+            }
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+// src/command/zrem.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the ZREM command in MuDB.
+#[derive(Debug, Clone)]
+pub struct ZRem {
+    key: String,
+    members: Vec<String>,
+}
+
+impl ZRem {
+    /// Creates a new `ZREM` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZREM command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZRem)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZRem, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::WrongArity("zrem"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse members
+        let mut members: Vec<String> = vec![];
+        for arg in args[1..].iter() {
+            match arg {
+                RespType::BulkString(m) => members.push(m.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Member must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(ZRem { key, members })
+    }
+
+    /// Executes the ZREM command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The number of members removed from the sorted set, as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zrem(&self.key, &self.members) {
+            Ok(removed) => RespType::Integer(removed as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// src/command/zrevrank.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the ZREVRANK command in MuDB.
+#[derive(Debug, Clone)]
+pub struct ZRevRank {
+    key: String,
+    member: String,
+}
+
+impl ZRevRank {
+    /// Creates a new `ZREVRANK` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZREVRANK command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZRevRank)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZRevRank, CommandError> {
+        if args.len() != 2 {
+            return Err(CommandError::WrongArity("zrevrank"));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse member
+        let member = match &args[1] {
+            RespType::BulkString(m) => m.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Member must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(ZRevRank { key, member })
+    }
+
+    /// Executes the ZREVRANK command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Integer` with the zero-based rank of the member in descending score order,
+    /// or `NullBulkString` if the key or the member doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zrevrank(&self.key, &self.member) {
+            Ok(Some(rank)) => RespType::Integer(rank as i64),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+// src/command/zscan.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{scan_args::ScanArgs, CommandError};
+
+/// Represents the ZSCAN command in MuDB.
+///
+/// The ZSCAN command iterates the sorted set stored at a key with a cursor, a page at a time.
+#[derive(Debug, Clone)]
+pub struct ZScan {
+    args: ScanArgs,
+}
+
+impl ZScan {
+    /// Creates a new `ZScan` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZSCAN command:
+    ///   the key, the cursor, and the optional MATCH and COUNT options.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZScan)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZScan, CommandError> {
+        let args = ScanArgs::parse(args, "zscan")?;
+        Ok(ZScan { args })
+    }
+
+    /// Executes the ZSCAN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - An `Array` of the next cursor as a `BulkString` ("0" once the iteration is complete)
+    ///   and an `Array` of the members and their scores of the page
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        let args = &self.args;
+        match db.zscan(&args.key, args.cursor, args.pattern.as_deref(), args.count) {
+            Ok((next, page)) => RespType::Array(vec![
+                RespType::BulkString(next.to_string()),
+                RespType::Array(
+                    page.into_iter()
+                        .flat_map(|(m, score)| {
+                            [
+                                RespType::BulkString(m),
+                                RespType::BulkString(score.to_string()),
+                            ]
+                        })
+                        .collect(),
+                ),
+            ]),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
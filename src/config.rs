@@ -0,0 +1,332 @@
+// src/config.rs
+
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::resp::frame::{DEFAULT_MAX_ARRAY_LEN, DEFAULT_MAX_BULK_LEN};
+
+/// Server options set at startup, which are shared across all the connections.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Whether the DEBUG command can be executed by clients.
+    pub enable_debug_command: bool,
+    /// Lowercased names of the DEBUG subcommands which are acknowledged with `OK` without doing
+    /// anything, for compatibility with tooling which sends Redis internal DEBUG subcommands.
+    pub debug_noop_subcommands: Vec<String>,
+    /// The fraction of executed commands, between 0 and 1, which are logged at debug level.
+    pub log_sample: f64,
+    /// Whether the command access log includes the arguments of the commands.
+    /// Off by default, since the arguments can contain sensitive values.
+    pub log_values: bool,
+    /// How often a summary of the keyspace is logged. `None` if it isn't logged periodically.
+    pub report_interval: Option<Duration>,
+    /// Whether TCP_NODELAY is set on accepted connections, so small replies aren't delayed by Nagle's algorithm.
+    pub tcp_nodelay: bool,
+    /// Idle time after which TCP keepalive probes are sent on accepted connections.
+    /// `None` if keepalive isn't enabled.
+    pub tcp_keepalive: Option<Duration>,
+    /// Maximum number of commands per second accepted from each connection, as a token bucket
+    /// allowing bursts of up to one second worth of commands. `None` if commands aren't limited.
+    pub max_cmds_per_sec: Option<u32>,
+    /// Whether commands which modify the data are rejected, so that the server can only be read from.
+    pub readonly: bool,
+    /// Time without any complete command after which a connection is closed.
+    /// `None` if idle connections are kept open.
+    pub idle_timeout: Option<Duration>,
+    /// Whether the execution latency of every command is recorded, to be reported by LATENCY.
+    pub track_timing: bool,
+    /// Maximum length in bytes of a bulk string sent by a client.
+    pub proto_max_bulk_len: usize,
+    /// Maximum number of bulk strings in a command sent by a client.
+    pub proto_max_array_len: usize,
+    /// Maximum combined length in bytes of the two values compared by LCS.
+    pub lcs_max_len: usize,
+    /// Time after which a command walking a large number of elements, such as LRANGE, is aborted
+    /// with an error instead of holding the DB lock until it completes. `None` if commands aren't limited.
+    pub command_timeout: Option<Duration>,
+    /// The memory limit in bytes, as reported by INFO. `None` if there is no limit. MuDB doesn't
+    /// enforce it yet.
+    pub maxmemory: Option<usize>,
+    /// The eviction policy named like in Redis. It selects the access statistics reported by
+    /// OBJECT FREQ, but MuDB doesn't evict keys yet.
+    pub maxmemory_policy: MaxmemoryPolicy,
+}
+
+/// The eviction policies of Redis, as given to `--maxmemory-policy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum MaxmemoryPolicy {
+    /// Never evict keys.
+    #[default]
+    Noeviction,
+    /// Evict the least recently used keys.
+    AllkeysLru,
+    /// Evict the least recently used keys among the keys with an expiry time.
+    VolatileLru,
+    /// Evict the least frequently used keys.
+    AllkeysLfu,
+    /// Evict the least frequently used keys among the keys with an expiry time.
+    VolatileLfu,
+    /// Evict random keys.
+    AllkeysRandom,
+    /// Evict random keys among the keys with an expiry time.
+    VolatileRandom,
+    /// Evict the keys with an expiry time which expire the soonest.
+    VolatileTtl,
+}
+
+impl MaxmemoryPolicy {
+    /// Returns whether the policy picks keys by their access frequency, so that OBJECT FREQ
+    /// reports it.
+    pub fn is_lfu(self) -> bool {
+        matches!(
+            self,
+            MaxmemoryPolicy::AllkeysLfu | MaxmemoryPolicy::VolatileLfu
+        )
+    }
+}
+
+impl Default for Config {
+    /// The options of a server started without any command line flag.
+    fn default() -> Config {
+        Config {
+            enable_debug_command: false,
+            debug_noop_subcommands: vec![
+                String::from("quicklist-packed-threshold"),
+                String::from("stringmatch-len"),
+                String::from("change-repl-id"),
+            ],
+            log_sample: 1.0,
+            log_values: false,
+            report_interval: None,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_cmds_per_sec: None,
+            readonly: false,
+            idle_timeout: None,
+            track_timing: false,
+            proto_max_bulk_len: DEFAULT_MAX_BULK_LEN,
+            proto_max_array_len: DEFAULT_MAX_ARRAY_LEN,
+            lcs_max_len: DEFAULT_LCS_MAX_LEN,
+            command_timeout: None,
+            maxmemory: None,
+            maxmemory_policy: MaxmemoryPolicy::Noeviction,
+        }
+    }
+}
+
+/// Default for the `--lcs-max-len` option. LCS takes time and memory proportional to the product
+/// of the lengths of the values, so two 4kb values need a table of 64mb in the worst case.
+pub const DEFAULT_LCS_MAX_LEN: usize = 8 * 1024;
+
+/// Default for the `--list-max-listpack-size` option, the number of elements up to which
+/// a list is stored as a compact listpack.
+pub const DEFAULT_LIST_MAX_LISTPACK_SIZE: usize = 128;
+
+/// The command line arguments equivalent to a config file, see `config_file_args`.
+#[derive(Debug, Default)]
+pub struct ConfigFileArgs {
+    /// The arguments, to be placed before the ones given on the command line.
+    pub args: Vec<String>,
+    /// A message for each directive which was skipped because MuDB doesn't support it.
+    pub warnings: Vec<String>,
+}
+
+/// Read a Redis-style config file, with one `directive value` pair per line, and translate it into
+/// the equivalent command line arguments.
+///
+/// Blank lines and lines starting with `#` are ignored. Directives are named after the command
+/// line flags (`port 6380`, `readonly yes`, `tcp-nodelay no`), and boolean directives take `yes`
+/// or `no`. The arguments are meant to be placed before the ones given on the command line, so
+/// that the latter take precedence. Directives MuDB doesn't support, such as `requirepass`, are
+/// skipped with a warning, so that a config file written for Redis can be used as is. Invalid
+/// values of the supported directives are still errors.
+pub fn config_file_args(path: &str) -> anyhow::Result<ConfigFileArgs> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Could not read the config file {}: {}", path, e))?;
+
+    let mut args = Vec::new();
+    let mut warnings = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (directive, value) = match line.split_once(char::is_whitespace) {
+            Some((directive, value)) => (directive, unquote(value.trim())),
+            None => (line, ""),
+        };
+        let directive = directive.to_lowercase();
+        let bad_directive =
+            |reason: &str| anyhow::anyhow!("{}:{}: {} '{}'", path, i + 1, reason, line);
+
+        match directive.as_str() {
+            "enable-debug-command"
+            | "log-values"
+            | "readonly"
+            | "daemonize"
+            | "single-thread"
+            | "track-timing"
+            | "hash-ordered" => {
+                if parse_yes_no(value).ok_or_else(|| bad_directive("Expected yes or no in"))? {
+                    args.push(format!("--{}", directive));
+                }
+            }
+            "tcp-nodelay" => {
+                if !parse_yes_no(value).ok_or_else(|| bad_directive("Expected yes or no in"))? {
+                    args.push(String::from("--no-tcp-nodelay"));
+                }
+            }
+            "port"
+            | "debug-noop-subcommands"
+            | "log-sample"
+            | "log-format"
+            | "report-interval"
+            | "tcp-keepalive"
+            | "io-threads"
+            | "maxcmds-per-sec"
+            | "idle-timeout"
+            | "timeout"
+            | "pidfile"
+            | "proto-max-bulk-len"
+            | "proto-max-array-len"
+            | "lcs-max-len"
+            | "list-max-listpack-size"
+            | "command-timeout"
+            | "maxmemory"
+            | "maxmemory-policy" => {
+                if value.is_empty() {
+                    return Err(bad_directive("Missing value in"));
+                }
+                // `timeout` is the name Redis uses for the idle client timeout.
+                let flag = if directive == "timeout" {
+                    "idle-timeout"
+                } else {
+                    &directive
+                };
+                args.push(format!("--{}={}", flag, value));
+            }
+            _ => warnings.push(format!(
+                "{}:{}: Skipping unsupported directive '{}'",
+                path,
+                i + 1,
+                line
+            )),
+        }
+    }
+    Ok(ConfigFileArgs { args, warnings })
+}
+
+/// Parse a size in bytes, optionally followed by a unit like in Redis: `k`, `m` and `g` are
+/// powers of 1000, while `kb`, `mb` and `gb` are powers of 1024. Units are case insensitive.
+pub fn parse_size(s: &str) -> Result<usize, String> {
+    let lower = s.to_lowercase();
+    let digits_end = lower
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(lower.len());
+    let (number, unit) = lower.split_at(digits_end);
+
+    let multiplier: usize = match unit {
+        "" | "b" => 1,
+        "k" => 1000,
+        "kb" => 1024,
+        "m" => 1000 * 1000,
+        "mb" => 1024 * 1024,
+        "g" => 1000 * 1000 * 1000,
+        "gb" => 1024 * 1024 * 1024,
+        _ => {
+            return Err(format!(
+                "invalid unit '{}', expected one of kb, mb or gb",
+                unit
+            ))
+        }
+    };
+
+    number
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| String::from("must be a size in bytes, such as 1024, 64kb or 512mb"))
+}
+
+/// Parse the value of a boolean config directive.
+fn parse_yes_no(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Strip the double or single quotes around a config value, if any.
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a config file with the given contents to a fresh path, and returns the path.
+    fn write_config(name: &str, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!("mudb-{}-{}.conf", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn config_file_translates_directives_into_flags() {
+        let path = write_config(
+            "flags",
+            "# comment\n\nport 6381\nreadonly yes\ntcp-nodelay no\nmaxmemory 100mb\ntimeout \"30\"\n",
+        );
+
+        let file = config_file_args(&path).unwrap();
+        assert_eq!(
+            file.args,
+            [
+                "--port=6381",
+                "--readonly",
+                "--no-tcp-nodelay",
+                "--maxmemory=100mb",
+                "--idle-timeout=30",
+            ]
+        );
+        assert!(file.warnings.is_empty());
+        assert_eq!(parse_size("100mb"), Ok(100 * 1024 * 1024));
+    }
+
+    #[test]
+    fn config_file_skips_unsupported_directives() {
+        let path = write_config(
+            "unsupported",
+            "requirepass secret\nport 6381\nappendonly yes\n",
+        );
+
+        let file = config_file_args(&path).unwrap();
+        assert_eq!(file.args, ["--port=6381"]);
+        assert_eq!(file.warnings.len(), 2);
+        assert!(
+            file.warnings[0].ends_with(":1: Skipping unsupported directive 'requirepass secret'")
+        );
+        assert!(file.warnings[1].ends_with(":3: Skipping unsupported directive 'appendonly yes'"));
+    }
+
+    #[test]
+    fn config_file_rejects_invalid_values() {
+        let path = write_config("invalid", "readonly maybe\n");
+        assert!(config_file_args(&path).is_err());
+
+        let path = write_config("missing", "maxmemory\n");
+        assert!(config_file_args(&path).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+// src/daemon.rs
+
+use std::{fs, process};
+
+use anyhow::Result;
+use log::warn;
+
+/// Name of the command line flag which runs the server in the background.
+const DAEMONIZE_FLAG: &str = "--daemonize";
+
+/// Environment variable set on the background process, so that it doesn't daemonize again when
+/// `daemonize yes` comes from a config file rather than from the command line. Its value is the
+/// file descriptor over which the background process reports that the server started.
+const DAEMON_CHILD_ENV: &str = "MUDB_DAEMON_CHILD";
+
+/// Restart the server as a background process detached from the controlling terminal,
+/// and exit the current process once the server started.
+///
+/// The server runs a multi-threaded runtime, so it can't safely `fork` itself. Instead, the
+/// executable is started again with the same arguments (minus `--daemonize`), in a new session
+/// and with stdin and stdout redirected to `/dev/null`. The background process keeps the
+/// stderr of the current process until the server started, so that startup errors are still
+/// shown, and reports through a pipe when it is ready (see [`Readiness`]). The current process
+/// exits with a non-zero status if the background process exits without doing so.
+#[cfg(unix)]
+pub fn daemonize() -> Result<()> {
+    use std::{
+        env,
+        io::{self, Read},
+        os::{fd::AsRawFd, unix::process::CommandExt},
+        process::Stdio,
+    };
+
+    if env::var_os(DAEMON_CHILD_ENV).is_some() {
+        return Ok(());
+    }
+
+    let args: Vec<_> = env::args_os()
+        .skip(1)
+        .filter(|arg| arg != DAEMONIZE_FLAG)
+        .collect();
+
+    let (mut ready, ready_writer) = io::pipe()?;
+    let ready_fd = ready_writer.as_raw_fd();
+
+    let mut cmd = process::Command::new(env::current_exe()?);
+    cmd.args(args)
+        .env(DAEMON_CHILD_ENV, ready_fd.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null());
+
+    // SAFETY: setsid and fcntl are async-signal-safe, so they can be called between fork
+    // and exec.
+    unsafe {
+        cmd.pre_exec(move || {
+            if libc::setsid() == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            // The pipe is created close-on-exec, the background process must inherit its
+            // write end.
+            if libc::fcntl(ready_fd, libc::F_SETFD, 0) == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+
+    let mut child = cmd.spawn()?;
+    // Only the background process must hold the write end, so that reading gets to the end of
+    // the pipe if it exits.
+    drop(ready_writer);
+
+    let mut buf = [0; 1];
+    if let Ok(1) = ready.read(&mut buf) {
+        println!("MuDB is running in the background with PID {}", child.id());
+        process::exit(0);
+    }
+
+    let status = child.wait()?;
+    eprintln!("MuDB failed to start in the background ({})", status);
+    process::exit(status.code().filter(|code| *code != 0).unwrap_or(1));
+}
+
+/// Running in the background is only supported on Unix.
+#[cfg(not(unix))]
+pub fn daemonize() -> Result<()> {
+    eprintln!("{} is not supported on this platform", DAEMONIZE_FLAG);
+    process::exit(1);
+}
+
+/// Write end of the pipe over which a background process reports that the server started, to
+/// the process which started it with [`daemonize`].
+pub struct Readiness {
+    #[cfg(unix)]
+    pipe: std::io::PipeWriter,
+}
+
+impl Readiness {
+    /// Returns the pipe over which to report that the server started, if the current process
+    /// was started in the background by [`daemonize`].
+    ///
+    /// This must be called at most once, as the returned value owns the file descriptor.
+    pub fn from_env() -> Option<Readiness> {
+        #[cfg(unix)]
+        {
+            use std::os::fd::{FromRawFd, RawFd};
+
+            let fd: RawFd = std::env::var(DAEMON_CHILD_ENV).ok()?.parse().ok()?;
+            // SAFETY: fcntl only checks that the file descriptor is open.
+            if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+                return None;
+            }
+            // SAFETY: the file descriptor was inherited from `daemonize`, and nothing else in
+            // this process uses it.
+            let pipe = unsafe { std::io::PipeWriter::from_raw_fd(fd) };
+            Some(Readiness { pipe })
+        }
+        #[cfg(not(unix))]
+        None
+    }
+
+    /// Report that the server started, and detach stderr from the terminal the server was
+    /// started from.
+    pub fn notify(self) {
+        #[cfg(unix)]
+        {
+            use std::{io::Write, os::fd::AsRawFd};
+
+            let mut pipe = self.pipe;
+            if let Err(e) = pipe.write_all(b"1") {
+                warn!("Could not report that the server started: {}", e);
+            }
+            match fs::OpenOptions::new().write(true).open("/dev/null") {
+                // SAFETY: dup2 replaces stderr with a file descriptor which is open.
+                Ok(null) => unsafe {
+                    libc::dup2(null.as_raw_fd(), libc::STDERR_FILENO);
+                },
+                Err(e) => warn!("Could not redirect stderr to /dev/null: {}", e),
+            }
+        }
+    }
+}
+
+/// File holding the ID of the current process, which is removed when the value is dropped.
+pub struct PidFile {
+    path: String,
+}
+
+impl PidFile {
+    /// Write the ID of the current process to the given file.
+    pub fn create(path: &str) -> Result<PidFile> {
+        fs::write(path, format!("{}\n", process::id()))?;
+        Ok(PidFile {
+            path: path.to_string(),
+        })
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            warn!("Could not remove the PID file {}. Err: {}", self.path, e);
+        }
+    }
+}
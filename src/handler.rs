@@ -1,30 +1,93 @@
 // src/handler.rs
 
 use anyhow::Result;
-use futures::{SinkExt, StreamExt};
-use log::error;
-use tokio::net::TcpStream;
+use bytes::BytesMut;
+use futures::{FutureExt, SinkExt, StreamExt};
+use log::{debug, error, info, log_enabled, warn, Level};
+use tokio::{
+    io::AsyncReadExt,
+    net::TcpStream,
+    sync::broadcast::{self, error::RecvError},
+};
 use tokio_util::codec::Framed;
 
+use std::{
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
 use crate::{
+    client::ClientHandle,
     command::Command,
+    config::Config,
     resp::{frame::RespCommandFrame, types::RespType},
     storage::db::DB,
 };
 
+/// Size in bytes of the buffered responses to pipelined commands above which they are flushed
+/// before the next command is executed, even if more commands are ready to be read.
+const MAX_BUFFERED_RESPONSE_BYTES: usize = 64 * 1024;
+
+/// Number of pipelined commands after which their buffered responses are flushed, even if
+/// more commands are ready to be read.
+const MAX_PIPELINED_COMMANDS: usize = 1024;
+
+/// Size in bytes of the commands received from a client while it is blocked, above which the
+/// connection is no longer read until the blocking command completes.
+const MAX_BUFFERED_REQUEST_BYTES: usize = 64 * 1024;
+
 /// Handles RESP command frames over a single TCP connection.
 pub struct FrameHandler {
     /// The framed connection using `RespCommandFrame` as the codec.
     conn: Framed<TcpStream, RespCommandFrame>,
+    /// Server options shared across all the connections.
+    config: Arc<Config>,
+    /// Entry of the connection in the registry of connected clients.
+    client: ClientHandle,
+    /// Limits the rate of commands accepted from the connection. `None` if commands aren't limited.
+    rate_limiter: Option<RateLimiter>,
+    /// The lines describing the commands executed by all the connections, once the
+    /// connection is switched into MONITOR mode. `None` until then.
+    monitor: Option<broadcast::Receiver<String>>,
+}
+
+/// A token bucket limiting the number of commands per second accepted from a connection.
+/// The bucket holds up to one second worth of tokens, and is refilled based on the time
+/// elapsed since the last command, so no timer is needed.
+struct RateLimiter {
+    /// Number of tokens added per second, which is also the capacity of the bucket.
+    rate: f64,
+    /// Number of tokens currently available.
+    tokens: f64,
+    /// The time at which the bucket was last refilled.
+    last_refill: Instant,
 }
+
 impl FrameHandler {
     /// Creates a new `FrameHandler` instance.
     /// # Arguments
     ///
-    /// * `db` - Reference to the database where the key-value pairs are stored.
+    /// * `conn` - The framed TCP connection.
+    ///
+    /// * `config` - Server options shared across all the connections.
+    ///
+    /// * `client` - Entry of the connection in the registry of connected clients.
+    ///   The connection is removed from the registry when the handler is dropped.
     ///
-    pub fn new(conn: Framed<TcpStream, RespCommandFrame>) -> FrameHandler {
-        FrameHandler { conn }
+    pub fn new(
+        conn: Framed<TcpStream, RespCommandFrame>,
+        config: Arc<Config>,
+        client: ClientHandle,
+    ) -> FrameHandler {
+        let rate_limiter = config.max_cmds_per_sec.map(RateLimiter::new);
+
+        FrameHandler {
+            conn,
+            config,
+            client,
+            rate_limiter,
+            monitor: None,
+        }
     }
 
     /// Handles incoming RESP command frames.
@@ -33,6 +96,17 @@ impl FrameHandler {
     /// processes them, and sends back the responses. It continues until
     /// an error occurs or the connection is closed.
     ///
+    /// Pipelined commands are supported: all the frames that are already available
+    /// on the connection are executed in order and their responses are buffered,
+    /// and the buffer is flushed once there are no more frames ready to be read.
+    /// The buffer is also flushed along the way once it grows too large, so that a client
+    /// which pipelines commands without reading the responses is held back by TCP
+    /// backpressure, instead of making the server buffer all of them.
+    ///
+    /// The connection is also closed if the server is started with `--idle-timeout` and
+    /// no complete command is received for longer than the timeout, including when a client
+    /// stalls in the middle of a frame.
+    ///
     /// # Returns
     ///
     /// A `Result` indicating whether the operation succeeded or failed.
@@ -42,40 +116,438 @@ impl FrameHandler {
     /// This method will return an error if there's an issue with reading
     /// from or writing to the connection.
     pub async fn handle(mut self, db: &DB) -> Result<()> {
-        while let Some(resp_cmd) = self.conn.next().await {
-            match resp_cmd {
-                Ok(cmd_frame) => {
-                    println!("[DEBUG] Received frame: {:?}", cmd_frame);
-                    // Read the command from the frame.
-                    let resp_cmd = Command::from_resp_command_frame(cmd_frame);
-
-                    // Execute the command and get the RESP response.
-                    // If command fails, return RESP SimpleError as response.
-                    let response = match &resp_cmd {
-                        Ok(cmd) => {
-                            println!("[DEBUG] Executing command: {:?}", cmd);
-                            cmd.execute(db)
-                        },
-                        Err(e) => {
-                            println!("[DEBUG] Command parse error: {}", e);
-                            RespType::SimpleError(format!("{}", e))
-                        },
-                    };
-                    println!("[DEBUG] Sending response: {:?}", response);
-                    // Write the RESP response into the TCP stream.
-                    if let Err(e) = self.conn.send(response).await {
-                        error!("Error sending response: {}", e);
-                        break;
+        let close_signal = self.client.close_signal();
+        loop {
+            let resp_cmd = tokio::select! {
+                resp_cmd = self.conn.next() => resp_cmd,
+                _ = close_signal.notified() => {
+                    // The signal may have been sent while a blocking command was running,
+                    // so check that the connection is still idle.
+                    if !self.is_idle() {
+                        continue;
                     }
-                }
-                Err(e) => {
-                    error!("Error reading the request: {}", e);
+                    info!(client:% = self.client.addr(); "Closing idle connection {}", self.client.addr());
                     break;
                 }
+            };
+            let resp_cmd = match resp_cmd {
+                Some(resp_cmd) => resp_cmd,
+                None => break,
+            };
+
+            if !self.handle_frame(resp_cmd, db).await {
+                break;
+            }
+
+            // Drain the frames which can be read without waiting, before flushing the responses.
+            // Frames following a MONITOR command are left to the monitor mode.
+            let mut closed = false;
+            let mut pipelined = 1;
+            while self.monitor.is_none() {
+                if pipelined >= MAX_PIPELINED_COMMANDS
+                    || self.conn.write_buffer().len() >= MAX_BUFFERED_RESPONSE_BYTES
+                {
+                    self.conn.flush().await?;
+                    pipelined = 0;
+                }
+
+                let resp_cmd = match self.conn.next().now_or_never() {
+                    Some(resp_cmd) => resp_cmd,
+                    None => break,
+                };
+                match resp_cmd {
+                    Some(resp_cmd) => {
+                        if !self.handle_frame(resp_cmd, db).await {
+                            closed = true;
+                            break;
+                        }
+                        pipelined += 1;
+                    }
+                    None => {
+                        closed = true;
+                        break;
+                    }
+                }
+            }
+
+            // flush the buffered responses into the TCP stream.
+            self.conn.flush().await?;
+            if closed {
+                break;
+            }
+
+            if let Some(monitor) = self.monitor.take() {
+                return self.stream_monitor(monitor).await;
             }
         }
         // flush the buffer into the TCP stream.
         self.conn.flush().await?;
         Ok(())
     }
+
+    /// Executes a single command frame and buffers its response without flushing it.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the connection should be closed, because the client sent QUIT, or because
+    /// the frame could not be read or the response could not be written.
+    async fn handle_frame(
+        &mut self,
+        resp_cmd: Result<Vec<RespType>, std::io::Error>,
+        db: &DB,
+    ) -> bool {
+        match resp_cmd {
+            Ok(cmd_frame) => {
+                self.client.record_activity();
+                // Keep the name of the command for the logs, before the frame is consumed.
+                let cmd_name = Self::command_name(&cmd_frame);
+                // Describe the command for the access log before the frame is consumed.
+                let access_log = if self.should_log_access() {
+                    Some(self.describe_command(&cmd_frame))
+                } else {
+                    None
+                };
+
+                // Drop the command without executing it if the connection is over its rate limit.
+                if let Some(rate_limiter) = &mut self.rate_limiter {
+                    if !rate_limiter.try_acquire() {
+                        let response =
+                            RespType::SimpleError(String::from("ERR command rate limit exceeded"));
+                        if let Err(e) = self.conn.feed(response).await {
+                            error!(
+                                client:% = self.client.addr(), cmd = cmd_name.as_str();
+                                "Error sending response to {} for {}: {}",
+                                self.client.addr(),
+                                cmd_name,
+                                e
+                            );
+                            return false;
+                        }
+                        return true;
+                    }
+                }
+
+                // Describe the command for the connections in MONITOR mode before the frame is consumed.
+                let monitor_line = if self.client.registry().is_monitored() {
+                    Some(self.describe_for_monitor(&cmd_frame))
+                } else {
+                    None
+                };
+
+                // Read the command from the frame.
+                let resp_cmd = Command::from_resp_command_frame(cmd_frame);
+                match (&resp_cmd, monitor_line) {
+                    // Like in Redis, MONITOR itself isn't reported.
+                    (Ok(Command::Monitor(_)), _) | (Err(_), _) | (_, None) => {}
+                    (Ok(_), Some(line)) => self.client.registry().publish_monitor(line),
+                }
+                // The connection is closed after the reply to QUIT is flushed.
+                let quit = matches!(resp_cmd, Ok(Command::Quit(_)));
+
+                // Execute the command and get the RESP response.
+                // If command fails, return RESP SimpleError as response.
+                // `None` if the response was already written into the write buffer.
+                let response = match &resp_cmd {
+                    Ok(Command::Debug(_)) if !self.config.enable_debug_command => {
+                        Some(RespType::SimpleError(String::from(
+                            "ERR DEBUG command not allowed. Start the server with --enable-debug-command to enable it.",
+                        )))
+                    }
+                    Ok(Command::Debug(debug))
+                        if debug.is_noop(&self.config.debug_noop_subcommands) =>
+                    {
+                        Some(RespType::SimpleString(String::from("OK")))
+                    }
+                    Ok(Command::Latency(_)) if !self.config.track_timing => {
+                        Some(RespType::SimpleError(String::from(
+                            "ERR command timing is not tracked. Start the server with --track-timing to enable it.",
+                        )))
+                    }
+                    Ok(cmd) if self.config.readonly && cmd.is_write() => {
+                        Some(RespType::SimpleError(String::from(
+                            "READONLY You can't write against a read only server.",
+                        )))
+                    }
+                    Ok(Command::LRange(lrange)) => {
+                        // Ranges can be large, so they are encoded straight from the list into
+                        // the write buffer rather than copied into a `RespType` first.
+                        db.stats().command_processed();
+                        let started = Instant::now();
+                        lrange.apply_into(db, self.conn.write_buffer_mut(), self.config.command_timeout);
+                        self.record_execution(db, &cmd_name, access_log, started);
+                        None
+                    }
+                    Ok(cmd @ (Command::BLPop(_) | Command::BRPop(_))) => {
+                        db.stats().command_processed();
+                        let started = Instant::now();
+                        // Stop waiting as soon as the client disconnects, so that the task isn't
+                        // parked forever, and an element pushed later isn't popped for a client
+                        // which is gone. The disconnection is checked first if both are ready.
+                        let response = tokio::select! {
+                            biased;
+                            _ = Self::wait_for_disconnect(&mut self.conn) => {
+                                debug!(
+                                    client:% = self.client.addr(), cmd = cmd_name.as_str();
+                                    "Client {} disconnected while blocked in {}",
+                                    self.client.addr(),
+                                    cmd_name
+                                );
+                                return false;
+                            }
+                            response = cmd.execute(db, &self.client, &self.config) => response,
+                        };
+                        self.record_execution(db, &cmd_name, access_log, started);
+                        Some(response)
+                    }
+                    Ok(cmd) => {
+                        db.stats().command_processed();
+                        let started = Instant::now();
+                        let response = cmd.execute(db, &self.client, &self.config).await;
+                        self.record_execution(db, &cmd_name, access_log, started);
+                        Some(response)
+                    }
+                    Err(e) => Some(RespType::SimpleError(format!("{}", e))),
+                };
+                // A blocking command may have run for a while, so the connection wasn't idle meanwhile.
+                self.client.record_activity();
+                // Write the RESP response into the write buffer of the TCP stream.
+                if let Some(response) = response {
+                    if let Err(e) = self.conn.feed(response).await {
+                        error!(
+                            client:% = self.client.addr(), cmd = cmd_name.as_str();
+                            "Error sending response to {} for {}: {}",
+                            self.client.addr(),
+                            cmd_name,
+                            e
+                        );
+                        return false;
+                    }
+                }
+
+                // Subscribe before the reply is flushed, so no command executed after it is missed.
+                if matches!(resp_cmd, Ok(Command::Monitor(_))) {
+                    self.monitor = Some(self.client.subscribe_monitor());
+                }
+                !quit
+            }
+            Err(e) => {
+                error!(
+                    client:% = self.client.addr();
+                    "Error reading the request from {}: {}",
+                    self.client.addr(),
+                    e
+                );
+                // Like Redis, tell the client why the connection is closed if the request was
+                // malformed. The reply is flushed before the connection is closed.
+                if e.kind() == std::io::ErrorKind::InvalidData {
+                    let response = RespType::SimpleError(format!("ERR Protocol error: {}", e));
+                    let _ = self.conn.feed(response).await;
+                }
+                false
+            }
+        }
+    }
+
+    /// Reads from the connection while the client is blocked, and returns once the client has
+    /// closed it. The bytes received meanwhile, such as pipelined commands, are kept in the read
+    /// buffer of the connection, to be decoded once the blocking command completes.
+    ///
+    /// This is safe to cancel, since bytes are only ever read and appended to the read buffer
+    /// within the same poll.
+    async fn wait_for_disconnect(conn: &mut Framed<TcpStream, RespCommandFrame>) {
+        loop {
+            // Don't let a blocked client make the server buffer an unbounded amount of commands.
+            if conn.read_buffer().len() >= MAX_BUFFERED_REQUEST_BYTES {
+                return std::future::pending().await;
+            }
+
+            let mut buf = BytesMut::with_capacity(4 * 1024);
+            match conn.get_mut().read_buf(&mut buf).await {
+                Ok(0) | Err(_) => return,
+                Ok(_) => conn.read_buffer_mut().extend_from_slice(&buf),
+            }
+        }
+    }
+
+    /// Streams the commands executed by all the connections to a connection in MONITOR mode,
+    /// until it sends QUIT or is closed. Any other command is rejected.
+    async fn stream_monitor(mut self, mut monitor: broadcast::Receiver<String>) -> Result<()> {
+        loop {
+            tokio::select! {
+                line = monitor.recv() => match line {
+                    Ok(line) => self.conn.send(RespType::SimpleString(line)).await?,
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!(
+                            client:% = self.client.addr();
+                            "MONITOR client {} is too slow, {} commands were not sent",
+                            self.client.addr(),
+                            missed
+                        );
+                    }
+                    Err(RecvError::Closed) => return Ok(()),
+                },
+                resp_cmd = self.conn.next() => {
+                    let cmd_frame = match resp_cmd {
+                        Some(Ok(cmd_frame)) => cmd_frame,
+                        Some(Err(e)) => {
+                            error!(
+                                client:% = self.client.addr();
+                                "Error reading the request from {}: {}",
+                                self.client.addr(),
+                                e
+                            );
+                            return Ok(());
+                        }
+                        None => return Ok(()),
+                    };
+                    self.client.record_activity();
+
+                    if let Ok(Command::Quit(quit)) = Command::from_resp_command_frame(cmd_frame) {
+                        self.conn.send(quit.apply()).await?;
+                        return Ok(());
+                    }
+                    let response = RespType::SimpleError(String::from(
+                        "ERR only QUIT is allowed once the connection is in MONITOR mode",
+                    ));
+                    self.conn.send(response).await?;
+                }
+            }
+        }
+    }
+
+    /// Returns whether the connection has gone without a complete command for longer than
+    /// the `--idle-timeout` option.
+    fn is_idle(&self) -> bool {
+        self.config
+            .idle_timeout
+            .is_some_and(|timeout| self.client.idle_time() > timeout)
+    }
+
+    /// Records the latency of a command executed at `started` if `--track-timing` is set,
+    /// and writes it to the access log if it was sampled.
+    fn record_execution(
+        &self,
+        db: &DB,
+        cmd_name: &str,
+        access_log: Option<String>,
+        started: Instant,
+    ) {
+        let elapsed = started.elapsed();
+        if self.config.track_timing {
+            db.stats().record_latency(cmd_name, elapsed);
+        }
+
+        if let Some(access_log) = access_log {
+            let elapsed_us = elapsed.as_micros() as u64;
+            debug!(
+                client:% = self.client.addr(), cmd = cmd_name, elapsed_us = elapsed_us;
+                "{} client={} elapsed_us={}",
+                access_log,
+                self.client.addr(),
+                elapsed_us
+            );
+        }
+    }
+
+    /// Returns whether the next command should be written to the access log.
+    /// The access log is written at debug level, for a sample of the commands
+    /// set by the `--log-sample` option.
+    fn should_log_access(&self) -> bool {
+        log_enabled!(Level::Debug)
+            && (self.config.log_sample >= 1.0 || rand::random::<f64>() < self.config.log_sample)
+    }
+
+    /// Returns the uppercased name of the command in a frame, or `?` if the frame has no name.
+    fn command_name(cmd_frame: &[RespType]) -> String {
+        match cmd_frame.first() {
+            Some(RespType::BulkString(name)) => name.to_uppercase(),
+            _ => String::from("?"),
+        }
+    }
+
+    /// Describes a command frame for the connections in MONITOR mode, like Redis does: the Unix
+    /// time in microseconds, the address of the client, and the command name and arguments.
+    /// The arguments are only included if the `--log-values` option is set, else their number is.
+    fn describe_for_monitor(&self, cmd_frame: &[RespType]) -> String {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let name = Self::command_name(cmd_frame).to_lowercase();
+        let argc = cmd_frame.len().saturating_sub(1);
+
+        let args = if !self.config.log_values {
+            match argc {
+                0 => String::new(),
+                1 => String::from(" (1 arg hidden)"),
+                _ => format!(" ({} args hidden)", argc),
+            }
+        } else {
+            cmd_frame
+                .iter()
+                .skip(1)
+                .map(|arg| match arg {
+                    RespType::BulkString(s) => format!(" {:?}", s),
+                    _ => format!(" {:?}", arg),
+                })
+                .collect()
+        };
+        format!(
+            "{}.{:06} [{}] {:?}{}",
+            now.as_secs(),
+            now.subsec_micros(),
+            self.client.addr(),
+            name,
+            args
+        )
+    }
+
+    /// Describes a command frame for the access log, with the command name and number of arguments.
+    /// The arguments themselves are only included if the `--log-values` option is set.
+    fn describe_command(&self, cmd_frame: &[RespType]) -> String {
+        let name = Self::command_name(cmd_frame);
+        let argc = cmd_frame.len().saturating_sub(1);
+
+        if !self.config.log_values {
+            return format!("cmd={} argc={}", name, argc);
+        }
+
+        let args: Vec<String> = cmd_frame
+            .iter()
+            .skip(1)
+            .map(|arg| match arg {
+                RespType::BulkString(s) => format!("{:?}", s),
+                _ => format!("{:?}", arg),
+            })
+            .collect();
+        format!("cmd={} argc={} args=[{}]", name, argc, args.join(" "))
+    }
+}
+
+impl RateLimiter {
+    /// Creates a full bucket allowing the given number of commands per second.
+    fn new(max_cmds_per_sec: u32) -> RateLimiter {
+        RateLimiter {
+            rate: max_cmds_per_sec as f64,
+            tokens: max_cmds_per_sec as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes a token from the bucket for the next command.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the bucket is empty, and the command should be rejected.
+    fn try_acquire(&mut self) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.last_refill = now;
+
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
 }
@@ -0,0 +1,14 @@
+// src/lib.rs
+
+// The server is split into a library, so that the integration tests under `tests/`
+// can start it, and the `mudb` binary which parses the options and runs it.
+pub mod client;
+pub mod command;
+pub mod config;
+pub mod daemon;
+pub mod handler;
+pub mod logging;
+pub mod resp;
+pub mod server;
+pub mod stats;
+pub mod storage;
@@ -0,0 +1,83 @@
+// src/logging.rs
+
+use std::{fmt::Write as _, io::Write as _};
+
+use clap::ValueEnum;
+use log::kv::{self, VisitSource};
+
+/// Format of the log records written to stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human readable lines, as printed by `env_logger`.
+    Plain,
+    /// One JSON object per line, for log aggregation.
+    Json,
+}
+
+/// Initialize the logger. The records to log are selected with the `RUST_LOG` environment
+/// variable in both formats.
+pub fn init(format: LogFormat) {
+    let mut builder = env_logger::Builder::from_default_env();
+    if format == LogFormat::Json {
+        builder.format(|buf, record| {
+            let mut line = String::from("{");
+            write_field(&mut line, "timestamp", &buf.timestamp_millis().to_string());
+            line.push(',');
+            write_field(&mut line, "level", record.level().as_str());
+            line.push(',');
+            write_field(&mut line, "target", record.target());
+            line.push(',');
+            write_field(&mut line, "message", &record.args().to_string());
+
+            // Structured fields attached to the record, such as the client address.
+            let _ = record.key_values().visit(&mut JsonFields(&mut line));
+            line.push('}');
+
+            writeln!(buf, "{}", line)
+        });
+    }
+    builder.init();
+}
+
+/// Appends the structured fields of a log record to a JSON object.
+struct JsonFields<'a>(&'a mut String);
+
+impl<'kvs> VisitSource<'kvs> for JsonFields<'_> {
+    fn visit_pair(&mut self, key: kv::Key<'kvs>, value: kv::Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.push(',');
+        match value.to_i64() {
+            Some(n) => {
+                write_json_string(self.0, key.as_str());
+                let _ = write!(self.0, ":{}", n);
+            }
+            None => write_field(self.0, key.as_str(), &value.to_string()),
+        }
+        Ok(())
+    }
+}
+
+/// Appends a `"key":"value"` pair to a JSON object.
+fn write_field(out: &mut String, key: &str, value: &str) {
+    write_json_string(out, key);
+    out.push(':');
+    write_json_string(out, value);
+}
+
+/// Appends a string to the output as a quoted and escaped JSON string.
+fn write_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
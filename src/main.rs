@@ -1,16 +1,13 @@
-// Include the server module defined in server.rs
-mod server;
-mod resp;
-pub mod handler;
-mod command;
-mod storage;
-
-
 // Import necessary crates and modules
-use crate::server::Server;
+use mudb::{
+    config, daemon, logging, storage,
+    config::{Config, MaxmemoryPolicy, DEFAULT_LCS_MAX_LEN, DEFAULT_LIST_MAX_LISTPACK_SIZE}, logging::LogFormat, resp::frame::{DEFAULT_MAX_ARRAY_LEN, DEFAULT_MAX_BULK_LEN},
+    server::Server,
+};
 use anyhow::Result;
-use log::info;
+use log::{info, warn};
 use clap::Parser;
+use std::{env, future::Future, time::Duration};
 use tokio::net::TcpListener;
 
 
@@ -22,21 +19,188 @@ const DEFAULT_PORT: u16 = 6380;
     version,
     author,
     about = "A RESP-based in-memory cache server.",
-    long_about = "MuDB is a lightweight, Redis-inspired in-memory database server written in Rust.\n\nRun this binary to start the MuDB server.\n\nExample usage:\n  mudb --port 6380\n\nFeatures:\n  - RESP protocol support\n  - In-memory key-value and list storage\n  - Colorful ASCII bull banner on startup\n\nTo interact with the server, use the mudb-cli client.\n\nSee README for more info."
+    long_about = "MuDB is a lightweight, Redis-inspired in-memory database server written in Rust.\n\nRun this binary to start the MuDB server.\n\nExample usage:\n  mudb --port 6380\n\nFeatures:\n  - RESP protocol support\n  - In-memory key-value and list storage\n  - Colorful ASCII bull banner on startup\n\nTo interact with the server, use the mudb-cli client.\n\nSee README for more info.",
+    args_override_self = true
 )]
 struct Cli {
+    /// Config file with one `directive value` pair per line (e.g. `port 6380`). Directives are
+    /// named after the command line flags, which override the values from the file.
+    #[arg(long, value_name = "PATH")]
+    config: Option<String>,
+
     /// Port to be bound to MuDB server
     #[arg(long)]
     port: Option<u16>,
+
+    /// Allow clients to run the DEBUG command (e.g. DEBUG SLEEP). Don't enable this in production.
+    #[arg(long)]
+    enable_debug_command: bool,
+
+    /// Comma separated DEBUG subcommands which reply OK without doing anything, for compatibility with Redis test tooling.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "quicklist-packed-threshold,stringmatch-len,change-repl-id"
+    )]
+    debug_noop_subcommands: Vec<String>,
+
+    /// Fraction of commands (between 0 and 1) logged when RUST_LOG=mudb::handler=debug is set.
+    #[arg(long, default_value_t = 1.0, value_parser = parse_log_sample)]
+    log_sample: f64,
+
+    /// Include command arguments in the command log. They may contain secrets, so this is off by default.
+    #[arg(long)]
+    log_values: bool,
+
+    /// Format of the logs: human readable lines, or one JSON object per line for log aggregation.
+    #[arg(long, value_enum, default_value_t = LogFormat::Plain)]
+    log_format: LogFormat,
+
+    /// Log a summary of the keyspace (key count, memory estimate and connected clients) every N seconds.
+    #[arg(long, value_name = "SECS")]
+    report_interval: Option<u64>,
+
+    /// Don't set TCP_NODELAY on client connections. Nagle's algorithm then batches small replies,
+    /// which can save packets on slow links but adds latency to single command round-trips.
+    #[arg(long)]
+    no_tcp_nodelay: bool,
+
+    /// Enable TCP keepalive on client connections, sending probes after SECS seconds of inactivity.
+    /// Lets the server notice dead peers. 0 disables keepalive (the default).
+    #[arg(long, value_name = "SECS", default_value_t = 0)]
+    tcp_keepalive: u64,
+
+    /// Number of worker threads used to serve clients. 0 (the default) starts one per CPU core.
+    /// More threads than cores only adds context switches, while fewer leaves cores idle under load.
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    io_threads: usize,
+
+    /// Serve all the clients from a single thread. This has the least overhead and a deterministic
+    /// scheduling order, which suits tests and tiny deployments, but commands from different
+    /// clients can no longer run in parallel.
+    #[arg(long, conflicts_with = "io_threads")]
+    single_thread: bool,
+
+    /// Maximum number of commands per second accepted from each client connection. Commands over
+    /// the limit are rejected with an error instead of being executed. The limit applies to every
+    /// connection separately, not to the server as a whole. 0 means unlimited (the default).
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    maxcmds_per_sec: u32,
+
+    /// Reject every command which modifies the data (SET, LPUSH, EXPIRE, ...), while still
+    /// serving reads. Useful for exposing a read-only endpoint.
+    #[arg(long)]
+    readonly: bool,
+
+    /// Close client connections which haven't sent a complete command for SECS seconds. This also
+    /// catches clients stalling in the middle of a command. 0 keeps idle connections open (the default).
+    #[arg(long, value_name = "SECS", default_value_t = 0)]
+    idle_timeout: u64,
+
+    /// Abort commands which walk a large number of elements (currently LRANGE) once they have run
+    /// for MS milliseconds, replying with an error instead of holding up the other clients.
+    /// 0 lets them run to completion (the default).
+    #[arg(long, value_name = "MS", default_value_t = 0)]
+    command_timeout: u64,
+
+    /// Record a histogram of the execution latency of each command, reported by LATENCY HISTORY.
+    /// Off by default, since it adds a little overhead to every command.
+    #[arg(long)]
+    track_timing: bool,
+
+    /// Maximum length in bytes of a bulk string (a single argument) sent by a client. Accepts units
+    /// such as 64kb or 512mb (the default). Clients announcing a longer argument are disconnected
+    /// with a protocol error.
+    #[arg(
+        long,
+        value_name = "SIZE",
+        default_value_t = DEFAULT_MAX_BULK_LEN,
+        value_parser = config::parse_size
+    )]
+    proto_max_bulk_len: usize,
+
+    /// Maximum number of arguments in a command sent by a client. Clients announcing more
+    /// arguments are disconnected with a protocol error.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_ARRAY_LEN)]
+    proto_max_array_len: usize,
+
+    /// Maximum combined length in bytes of the two values compared by LCS. Accepts the same units
+    /// as --proto-max-bulk-len. LCS needs time and memory proportional to the product of the
+    /// lengths of the values, so larger values are rejected with an error.
+    #[arg(
+        long,
+        value_name = "SIZE",
+        default_value_t = DEFAULT_LCS_MAX_LEN,
+        value_parser = config::parse_size
+    )]
+    lcs_max_len: usize,
+
+    /// Keep the fields of hashes in sorted order, so that HKEYS and HVALS list them in a stable
+    /// order. Lookups in large hashes are slower. Off by default.
+    #[arg(long)]
+    hash_ordered: bool,
+
+    /// Maximum number of elements of a list stored as a compact listpack. Longer lists, and
+    /// lists with an element longer than 64 bytes, are converted to a quicklist, which is
+    /// faster to push to and pop from at both ends.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_LIST_MAX_LISTPACK_SIZE)]
+    list_max_listpack_size: usize,
+
+    /// Memory limit reported by INFO, with the same units as --proto-max-bulk-len (e.g. 100mb).
+    /// It isn't enforced yet. 0 means no limit (the default).
+    #[arg(long, value_name = "SIZE", default_value_t = 0, value_parser = config::parse_size)]
+    maxmemory: usize,
+
+    /// Eviction policy, named like in Redis. Keys aren't evicted yet, but the LFU policies
+    /// (allkeys-lfu and volatile-lfu) let OBJECT FREQ report the access frequency of the keys.
+    #[arg(long, value_enum, default_value_t = MaxmemoryPolicy::Noeviction)]
+    maxmemory_policy: MaxmemoryPolicy,
+
+    /// Run the server in the background, detached from the terminal (Unix only).
+    #[arg(long)]
+    daemonize: bool,
+
+    /// File to which the process ID of the server is written once it starts.
+    #[arg(long)]
+    pidfile: Option<String>,
 }
 
+/// Parse the --log-sample ratio, which must be between 0 and 1.
+fn parse_log_sample(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(ratio) if (0.0..=1.0).contains(&ratio) => Ok(ratio),
+        _ => Err(String::from("must be a number between 0 and 1")),
+    }
+}
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize the logger.
-    // This sets up logging based on the RUST_LOG environment variable
-    env_logger::init();
+/// Collect the command line arguments, with the ones read from the `--config` file (if any)
+/// inserted before them. Later flags override earlier ones, so the command line wins over the file.
+/// Also returns the warnings about the directives of the file which were skipped, to be logged
+/// once the logger is initialized.
+fn args_with_config_file() -> Result<(Vec<String>, Vec<String>)> {
+    let mut args: Vec<String> = env::args().collect();
+
+    let mut config_path = None;
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        if arg == "--config" {
+            config_path = iter.next().cloned();
+        } else if let Some(path) = arg.strip_prefix("--config=") {
+            config_path = Some(path.to_string());
+        }
+    }
 
+    let mut warnings = Vec::new();
+    if let Some(path) = config_path {
+        let file = config::config_file_args(&path)?;
+        args.splice(1..1, file.args);
+        warnings = file.warnings;
+    }
+    Ok((args, warnings))
+}
+
+
+fn main() -> Result<()> {
     // Print MuDB bull and sign
     println!(r#"
               
@@ -52,9 +216,45 @@ async fn main() -> Result<()> {
     "#);
 
     // Get port from --port CLI parameter. Defaults to 6379
-    let cli = Cli::parse();
+    let (args, config_warnings) = args_with_config_file()?;
+    let cli = Cli::parse_from(args);
+
+    // Initialize the logger.
+    // This sets up logging based on the RUST_LOG environment variable
+    logging::init(cli.log_format);
+    for warning in config_warnings {
+        warn!("{}", warning);
+    }
+
     let port = cli.port.unwrap_or(DEFAULT_PORT);
 
+    // Restart in the background if requested. The server keeps running in the foreground
+    // by default, which is what Docker and systemd expect.
+    if cli.daemonize {
+        daemon::daemonize()?;
+    }
+
+    // Build the runtime based on the threading options.
+    let runtime = if cli.single_thread {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+    } else {
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        if cli.io_threads > 0 {
+            builder.worker_threads(cli.io_threads);
+        }
+        builder.enable_all().build()?
+    };
+
+    // Taken before starting the runtime, as it owns a file descriptor inherited from the
+    // process which started the server in the background.
+    let readiness = daemon::Readiness::from_env();
+    runtime.block_on(start_server(cli, port, readiness))
+}
+
+/// Bind the TCP listener and run the server until the program is terminated.
+async fn start_server(cli: Cli, port: u16, readiness: Option<daemon::Readiness>) -> Result<()> {
     // Define the address and port for the TCP server to listen on
     // Here we're using localhost (127.0.0.1) and port 6379 (commonly used for Redis)
     let addr = format!("127.0.0.1:{}", port);
@@ -71,15 +271,88 @@ async fn main() -> Result<()> {
         Err(e) => panic!("Could not bind the TCP listener to {}. Err: {}", &addr, e),
     };
     // initialize shared storage
-    let shared_storage = storage::db::Storage::new(storage::db::DB::new());
+    let shared_storage = storage::db::Storage::new(storage::db::DB::with_options(cli.hash_ordered, cli.list_max_listpack_size));
+
+    let config = Config {
+        enable_debug_command: cli.enable_debug_command,
+        debug_noop_subcommands: cli
+            .debug_noop_subcommands
+            .iter()
+            .map(|s| s.to_lowercase())
+            .collect(),
+        log_sample: cli.log_sample,
+        log_values: cli.log_values,
+        report_interval: cli
+            .report_interval
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs),
+        tcp_nodelay: !cli.no_tcp_nodelay,
+        tcp_keepalive: Some(cli.tcp_keepalive)
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs),
+        max_cmds_per_sec: Some(cli.maxcmds_per_sec).filter(|n| *n > 0),
+        readonly: cli.readonly,
+        idle_timeout: Some(cli.idle_timeout)
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs),
+        track_timing: cli.track_timing,
+        proto_max_bulk_len: cli.proto_max_bulk_len,
+        proto_max_array_len: cli.proto_max_array_len,
+        lcs_max_len: cli.lcs_max_len,
+        command_timeout: Some(cli.command_timeout)
+            .filter(|ms| *ms > 0)
+            .map(Duration::from_millis),
+        maxmemory: Some(cli.maxmemory).filter(|bytes| *bytes > 0),
+        maxmemory_policy: cli.maxmemory_policy,
+    };
+
+    // The PID file is removed when the server stops
+    let _pidfile = match &cli.pidfile {
+        Some(pidfile) => match daemon::PidFile::create(pidfile) {
+            Ok(pidfile) => Some(pidfile),
+            Err(e) => panic!("Could not write the PID file {}. Err: {}", pidfile, e),
+        },
+        None => None,
+    };
+
+    // Stop gracefully when asked to, so that the PID file is removed
+    let shutdown = shutdown_signal()?;
+
+    // Let the process which started the server in the background exit
+    if let Some(readiness) = readiness {
+        readiness.notify();
+    }
 
     // Create a new instance of the Server with the bound TcpListener
-    let mut server = Server::new(listener, shared_storage);
+    let mut server = Server::new(listener, shared_storage, config);
     // Run the server to start accepting and handling connections
-    // This will run indefinitely until the program is terminated
-    server.run().await?;
+    // This runs until the program is asked to terminate
+    tokio::select! {
+        res = server.run() => res?,
+        _ = shutdown => info!("Shutting down"),
+    }
 
-    // This Ok(()) is technically unreachable as server.run() loops infinitely,
-    // but it's needed to satisfy the Result return type of main()
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Listen for the signals asking the program to terminate, Ctrl-C or SIGTERM. The signals are
+/// handled from the time this returns, even before the returned future is polled.
+fn shutdown_signal() -> Result<impl Future<Output = ()>> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut sigint = signal(SignalKind::interrupt())?;
+        let mut sigterm = signal(SignalKind::terminate())?;
+        Ok(async move {
+            tokio::select! {
+                _ = sigint.recv() => {},
+                _ = sigterm.recv() => {},
+            }
+        })
+    }
+    #[cfg(not(unix))]
+    Ok(async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+}
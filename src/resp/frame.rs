@@ -20,26 +20,66 @@ use super::RespError;
 /// ```
 /// use tokio::net::TcpStream;
 /// use tokio_util::codec::Framed;
-/// use crate::resp::frame::RespCommandFrame;
+/// use mudb::resp::frame::{RespCommandFrame, DEFAULT_MAX_ARRAY_LEN, DEFAULT_MAX_BULK_LEN};
 ///
 /// async fn handle_connection(stream: TcpStream) {
-///     let mut framed = Framed::new(stream, RespCommandFrame::new());
+///     let codec = RespCommandFrame::with_limits(DEFAULT_MAX_BULK_LEN, DEFAULT_MAX_ARRAY_LEN);
+///     let mut framed = Framed::new(stream, codec);
 ///     // Now you can use `framed` to send and receive Nimblecache commands as `RespType` objects
 /// }
 /// ```
 pub struct RespCommandFrame {
     /// Builder for appending the bulk strings in the command array.
     cmd_builder: Option<CommandBuilder>,
+    /// Maximum length in bytes of a bulk string in a command.
+    max_bulk_len: usize,
+    /// Maximum number of bulk strings in a command.
+    max_array_len: usize,
 }
 
+/// Default maximum length of a bulk string, 512 MB like in Redis.
+pub const DEFAULT_MAX_BULK_LEN: usize = 512 * 1024 * 1024;
+
+/// Default maximum number of bulk strings in a command, the same as in Redis.
+pub const DEFAULT_MAX_ARRAY_LEN: usize = i32::MAX as usize;
+
 impl RespCommandFrame {
-    /// Creates a new `RespCommandFrame`.
+    /// Creates a new `RespCommandFrame`, which rejects commands larger than the given limits.
+    ///
+    /// The limits are checked against the lengths declared in the command, before its content
+    /// is received, so that a client announcing a huge command can't make the server buffer it.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_bulk_len` - Maximum length in bytes of a bulk string in a command.
+    ///
+    /// * `max_array_len` - Maximum number of bulk strings in a command.
     ///
     /// # Returns
     ///
     /// A new instance of `RespCommandFrame` with no command builder initialized.
-    pub fn new() -> RespCommandFrame {
-        RespCommandFrame { cmd_builder: None }
+    pub fn with_limits(max_bulk_len: usize, max_array_len: usize) -> RespCommandFrame {
+        RespCommandFrame {
+            cmd_builder: None,
+            max_bulk_len,
+            max_array_len,
+        }
+    }
+
+    /// Writes the header of a RESP array with the given number of elements to the output buffer.
+    ///
+    /// Together with `encode_bulk_string`, this lets large array responses be written element
+    /// by element straight from the stored data, instead of first building a `RespType::Array`
+    /// holding a copy of every element.
+    pub fn encode_array_header(len: usize, dst: &mut bytes::BytesMut) {
+        dst.put_slice(format!("*{}\r\n", len).as_bytes());
+    }
+
+    /// Writes a RESP bulk string to the output buffer.
+    pub fn encode_bulk_string(s: &str, dst: &mut bytes::BytesMut) {
+        dst.put_slice(format!("${}\r\n", s.len()).as_bytes());
+        dst.put_slice(s.as_bytes());
+        dst.put_slice(b"\r\n");
     }
 }
 
@@ -70,18 +110,19 @@ impl Decoder for RespCommandFrame {
         // A command in RESP protocol should always be an array of Bulk Strings.
         // Check the first 2 bytes to validate if its a RESP array.
         if self.cmd_builder.is_none() {
-            let (cmd_len, bytes_read) = match RespType::parse_array_len(src.clone()) {
-                Ok(arr_len) => match arr_len {
-                    Some((len, bytes_read)) => (len, bytes_read),
-                    None => return Ok(None),
-                },
-                Err(e) => {
-                    return Err(Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        FrameError::from(e),
-                    ));
-                }
-            };
+            let (cmd_len, bytes_read) =
+                match RespType::parse_array_len(&src[..], self.max_array_len) {
+                    Ok(arr_len) => match arr_len {
+                        Some((len, bytes_read)) => (len, bytes_read),
+                        None => return Ok(None),
+                    },
+                    Err(e) => {
+                        return Err(Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            FrameError::from(e),
+                        ));
+                    }
+                };
 
             // initilize command builder, if its a valid RESP array.
             self.cmd_builder = Some(CommandBuilder::new(cmd_len));
@@ -90,21 +131,23 @@ impl Decoder for RespCommandFrame {
             src.advance(bytes_read);
         }
 
-        // Read all bytes in buffer
+        // Read all bytes in buffer. Only the bytes of the current command are consumed, so
+        // any following (pipelined) commands stay in the buffer for the next call to `decode`.
         while !src.is_empty() {
             // Validate and check the length of next bulk string
-            let (bulkstr_len, bytes_read) = match RespType::parse_bulk_string_len(src.clone()) {
-                Ok(bulkstr_len) => match bulkstr_len {
-                    Some((len, bytes_read)) => (len, bytes_read),
-                    None => return Ok(None),
-                },
-                Err(e) => {
-                    return Err(Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        FrameError::from(e),
-                    ));
-                }
-            };
+            let (bulkstr_len, bytes_read) =
+                match RespType::parse_bulk_string_len(&src[..], self.max_bulk_len) {
+                    Ok(bulkstr_len) => match bulkstr_len {
+                        Some((len, bytes_read)) => (len, bytes_read),
+                        None => return Ok(None),
+                    },
+                    Err(e) => {
+                        return Err(Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            FrameError::from(e),
+                        ));
+                    }
+                };
 
             // A bulk string has the below format
             //
@@ -112,13 +155,13 @@ impl Decoder for RespCommandFrame {
             //
             // Check if the buffer contains the required number of bytes to parse
             // the bulk string (including the CRLF at the end)
-            let bulkstr_bytes = bulkstr_len + bytes_read + 2;
+            let bulkstr_bytes = bulkstr_len.saturating_add(bytes_read + 2);
             if src.len() < bulkstr_bytes {
                 return Ok(None);
             }
 
             // now that its sure the buffer has all the bytes required to parse the bulk string, parse it.
-            let (bulkstr, bytes_read) = match RespType::new_bulk_string(src.clone()) {
+            let (bulkstr, bytes_read) = match RespType::new_bulk_string(&src[..]) {
                 Ok((resp_type, bytes_read)) => (resp_type, bytes_read),
                 Err(e) => {
                     return Err(Error::new(
@@ -236,4 +279,61 @@ impl fmt::Display for FrameError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.err.fmt(f)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+
+    use super::*;
+
+    #[test]
+    fn huge_bulk_string_is_rejected_before_it_is_buffered() {
+        let mut codec = RespCommandFrame::with_limits(DEFAULT_MAX_BULK_LEN, DEFAULT_MAX_ARRAY_LEN);
+        let mut src = BytesMut::from(&b"*2\r\n$3\r\nGET\r\n$1000000000000\r\n"[..]);
+
+        let err = codec.decode(&mut src).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "invalid bulk length");
+    }
+
+    #[test]
+    fn huge_array_is_rejected() {
+        let mut codec = RespCommandFrame::with_limits(DEFAULT_MAX_BULK_LEN, DEFAULT_MAX_ARRAY_LEN);
+        let mut src = BytesMut::from(&b"*99999999999\r\n"[..]);
+
+        let err = codec.decode(&mut src).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "invalid multibulk length");
+    }
+
+    #[test]
+    fn empty_bulk_strings_are_decoded() {
+        let mut codec = RespCommandFrame::with_limits(DEFAULT_MAX_BULK_LEN, DEFAULT_MAX_ARRAY_LEN);
+        let mut src = BytesMut::from(&b"*3\r\n$3\r\nSET\r\n$0\r\n\r\n$0\r\n\r\n*1\r\n"[..]);
+
+        let frame = codec.decode(&mut src).unwrap().unwrap();
+        assert_eq!(
+            frame,
+            vec![
+                RespType::BulkString(String::from("SET")),
+                RespType::BulkString(String::new()),
+                RespType::BulkString(String::new()),
+            ]
+        );
+        // exactly the bytes of the frame are consumed
+        assert_eq!(&src[..], b"*1\r\n");
+    }
+
+    #[test]
+    fn empty_bulk_string_waits_for_its_crlf() {
+        let mut codec = RespCommandFrame::with_limits(DEFAULT_MAX_BULK_LEN, DEFAULT_MAX_ARRAY_LEN);
+        let mut src = BytesMut::from(&b"*2\r\n$4\r\nECHO\r\n$0\r\n"[..]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+
+        src.extend_from_slice(b"\r\n");
+        let frame = codec.decode(&mut src).unwrap().unwrap();
+        assert_eq!(frame[1], RespType::BulkString(String::new()));
+        assert!(src.is_empty());
+    }
+}
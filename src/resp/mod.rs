@@ -1,6 +1,13 @@
 pub mod types;
 pub mod frame;
 
+/// Returns whether an error message starts with an error code, such as `ERR` or `WRONGTYPE`:
+/// an all uppercase first word. Clients branch on the code, so error replies should have one.
+pub fn has_error_code(msg: &str) -> bool {
+    let code = msg.split(' ').next().unwrap_or_default();
+    code.len() > 1 && code.chars().all(|c| c.is_ascii_uppercase())
+}
+
 /// Represents errors that can occur during RESP parsing.
 #[derive(Debug)]
 pub enum RespError {
@@ -2,11 +2,16 @@ use bytes::{Bytes, BytesMut};
 
 use super::RespError;
 
+/// Maximum size in bytes of the length prefix of an array or bulk string, such as `$5\r\n`.
+/// A `usize` has at most 20 digits, so a longer prefix without a CRLF can't be valid, and the
+/// rest of it doesn't need to be buffered.
+const MAX_LENGTH_PREFIX: usize = 32;
+
 /// Nimblecache supports Redis Serialization Protocol or RESP.
 /// This enum is a wrapper for the different RESP types.
 /// Please refer <https://redis.io/docs/latest/develop/reference/protocol-spec/> for more info
 /// on the RESP protocol.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum RespType {
     /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#simple-strings>
     SimpleString(String),
@@ -16,6 +21,8 @@ pub enum RespType {
     NullBulkString,
     /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#arrays>
     Array(Vec<RespType>),
+    /// Null array representation in RESP2. It's an Array with length of negative one (-1).
+    NullArray,
     /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#simple-errors>
     SimpleError(String),
     /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#integers>
@@ -29,7 +36,7 @@ impl RespType {
     /// Example BulkString: `$5\r\nhello\r\n`
     ///
     /// # BulkString Parts:
-    /// ```
+    /// ```text
     ///     $      |            5           | \r\n |    hello     | \r\n
     /// identifier | string length in bytes | CRLF | string value | CRLF
     /// ```
@@ -39,10 +46,12 @@ impl RespType {
     /// - That slice of bytes are then parsed into an int. That will be the string length in bytes (let's say `bulkstr_len`)
     /// - `bulkstr_len` number of bytes are read from the buffer again from where it was stopped previously.
     /// - This 2nd slice of bytes is then parsed into an UTF-8 string.
+    /// - The string value must be followed by CRLF. An empty string (`$0\r\n\r\n`) is just the
+    ///   length line followed by that CRLF.
     ///
     /// Note: The first byte in the buffer is skipped since it's just an identifier for the
     /// RESP type and is not the part of the actual value itself.
-    pub fn new_bulk_string(buffer: BytesMut) -> Result<(RespType, usize), RespError> {
+    pub fn new_bulk_string(buffer: &[u8]) -> Result<(RespType, usize), RespError> {
         let (bulkstr_len, bytes_consumed) =
             if let Some((buf_data, len)) = Self::read_till_crlf(&buffer[1..]) {
                 let bulkstr_len = Self::parse_usize_from_buf(buf_data)?;
@@ -53,8 +62,12 @@ impl RespType {
                 )));
             };
 
-        let bulkstr_end_idx = bytes_consumed + bulkstr_len;
-        if bulkstr_end_idx >= buffer.len() {
+        // the value must be followed by CRLF. Checking for it, rather than only skipping two
+        // bytes, keeps a wrong length from silently shifting the framing of the next values.
+        let bulkstr_end_idx = bytes_consumed.saturating_add(bulkstr_len);
+        if bulkstr_end_idx.saturating_add(2) > buffer.len()
+            || &buffer[bulkstr_end_idx..bulkstr_end_idx + 2] != b"\r\n"
+        {
             return Err(RespError::InvalidBulkString(String::from(
                 "Invalid value for bulk string length",
             )));
@@ -69,9 +82,89 @@ impl RespType {
         }
     }
 
+    /// Parse a single RESP2 value of any type from the start of the given bytes, such as a reply
+    /// read by a client. Arrays are parsed recursively. This will return the parsed RESP value and
+    /// the number of bytes read from the buffer, so that the caller can advance its buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The bytes to parse. A `&mut BytesMut` can be passed as is.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some((RespType, usize)))` - The parsed value and the number of bytes read.
+    /// * `Ok(None)` - If the buffer doesn't hold a complete value yet.
+    /// * `Err(RespError)` - If the bytes aren't a valid RESP2 value.
+    pub fn parse(buf: &[u8]) -> Result<Option<(RespType, usize)>, RespError> {
+        let type_byte = match buf.first() {
+            Some(b) => *b,
+            None => return Ok(None),
+        };
+        let (line, line_len) = match Self::read_till_crlf(&buf[1..]) {
+            Some((line, len)) => (line, len + 1),
+            None => return Ok(None),
+        };
+
+        match type_byte {
+            b'+' => Ok(Some((RespType::SimpleString(Self::utf8(line)?), line_len))),
+            b'-' => Ok(Some((RespType::SimpleError(Self::utf8(line)?), line_len))),
+            b':' => match Self::utf8(line)?.parse::<i64>() {
+                Ok(i) => Ok(Some((RespType::Integer(i), line_len))),
+                Err(_) => Err(RespError::Other(String::from(
+                    "Invalid value for an integer",
+                ))),
+            },
+            b'$' if line == b"-1" => Ok(Some((RespType::NullBulkString, line_len))),
+            b'$' => {
+                let len = Self::parse_usize_from_buf(line)?;
+                let end = line_len.saturating_add(len);
+                if buf.len() < end.saturating_add(2) {
+                    return Ok(None);
+                }
+                if &buf[end..end + 2] != b"\r\n" {
+                    return Err(RespError::InvalidBulkString(String::from(
+                        "Invalid value for bulk string length",
+                    )));
+                }
+                match String::from_utf8(buf[line_len..end].to_vec()) {
+                    Ok(bs) => Ok(Some((RespType::BulkString(bs), end + 2))),
+                    Err(_) => Err(RespError::InvalidBulkString(String::from(
+                        "Bulk string value is not a valid UTF-8 string",
+                    ))),
+                }
+            }
+            b'*' if line == b"-1" => Ok(Some((RespType::NullArray, line_len))),
+            b'*' => {
+                let len = Self::parse_usize_from_buf(line)?;
+                let mut items = Vec::new();
+                let mut read = line_len;
+                for _ in 0..len {
+                    match Self::parse(&buf[read..])? {
+                        Some((item, item_len)) => {
+                            items.push(item);
+                            read += item_len;
+                        }
+                        None => return Ok(None),
+                    }
+                }
+                Ok(Some((RespType::Array(items), read)))
+            }
+            _ => Err(RespError::Other(format!(
+                "Unknown RESP type identifier '{}'",
+                type_byte as char
+            ))),
+        }
+    }
+
+    // Parse an UTF-8 string from bytes
+    fn utf8(buf: &[u8]) -> Result<String, RespError> {
+        String::from_utf8(buf.to_vec())
+            .map_err(|_| RespError::Other(String::from("Invalid UTF-8 string")))
+    }
+
     /// Convert the RESP value into its byte values.
     pub fn to_bytes(&self) -> Bytes {
-        return match self {
+        match self {
             RespType::SimpleString(ss) => Bytes::from_iter(format!("+{}\r\n", ss).into_bytes()),
             RespType::BulkString(bs) => {
                 let bulkstr_bytes = format!("${}\r\n{}\r\n", bs.len(), bs).into_bytes();
@@ -86,9 +179,10 @@ impl RespType {
 
                 Bytes::from_iter(arr_bytes)
             }
+            RespType::NullArray => Bytes::from("*-1\r\n"),
             RespType::SimpleError(es) => Bytes::from_iter(format!("-{}\r\n", es).into_bytes()),
             RespType::Integer(i) => Bytes::from_iter(format!(":{}\r\n", i).into_bytes()),
-        };
+        }
     }
 
     /// Parses the length of a RESP array from the given byte buffer.
@@ -98,7 +192,9 @@ impl RespType {
     ///
     /// # Arguments
     ///
-    /// * `src` - A `BytesMut` containing the bytes to parse.
+    /// * `src` - A byte slice containing the bytes to parse.
+    ///
+    /// * `max_len` - The maximum number of elements accepted in the array.
     ///
     /// # Returns
     ///
@@ -106,10 +202,19 @@ impl RespType {
     ///   - The parsed length of the array
     ///   - The number of bytes read from the input
     /// * `Ok(None)` - If there's not enough data in the buffer to parse the length
-    /// * `Err(RespError)` - If the input is not a valid RESP array prefix or if parsing fails
-    pub fn parse_array_len(src: BytesMut) -> Result<Option<(usize, usize)>, RespError> {
-        let (array_prefix_bytes, bytes_read) = match Self::read_till_crlf(&src[..]) {
+    /// * `Err(RespError)` - If the input is not a valid RESP array prefix, if parsing fails
+    ///   or if the length is over `max_len`
+    pub fn parse_array_len(
+        src: &[u8],
+        max_len: usize,
+    ) -> Result<Option<(usize, usize)>, RespError> {
+        let (array_prefix_bytes, bytes_read) = match Self::read_till_crlf(src) {
             Some((b, size)) => (b, size),
+            None if src.len() > MAX_LENGTH_PREFIX => {
+                return Err(RespError::InvalidArray(String::from(
+                    "invalid multibulk length",
+                )));
+            }
             None => return Ok(None),
         };
 
@@ -120,6 +225,9 @@ impl RespType {
         }
 
         match Self::parse_usize_from_buf(&array_prefix_bytes[1..]) {
+            Ok(len) if len > max_len => Err(RespError::InvalidArray(String::from(
+                "invalid multibulk length",
+            ))),
             Ok(len) => Ok(Some((len, bytes_read))),
             Err(e) => Err(e),
         }
@@ -132,7 +240,9 @@ impl RespType {
     ///
     /// # Arguments
     ///
-    /// * `src` - A `BytesMut` containing the bytes to parse.
+    /// * `src` - A byte slice containing the bytes to parse.
+    ///
+    /// * `max_len` - The maximum length of the bulk string in bytes.
     ///
     /// # Returns
     ///
@@ -140,11 +250,20 @@ impl RespType {
     ///   - The parsed length of the bulk string
     ///   - The number of bytes read from the input
     /// * `Ok(None)` - If there's not enough data in the buffer to parse the length
-    /// * `Err(RespError)` - If the input is not a valid RESP bulk string prefix or if parsing fails
+    /// * `Err(RespError)` - If the input is not a valid RESP bulk string prefix, if parsing fails
+    ///   or if the length is over `max_len`
     ///
-    pub fn parse_bulk_string_len(src: BytesMut) -> Result<Option<(usize, usize)>, RespError> {
-        let (bulkstr_prefix_bytes, bytes_read) = match Self::read_till_crlf(&src[..]) {
+    pub fn parse_bulk_string_len(
+        src: &[u8],
+        max_len: usize,
+    ) -> Result<Option<(usize, usize)>, RespError> {
+        let (bulkstr_prefix_bytes, bytes_read) = match Self::read_till_crlf(src) {
             Some((b, size)) => (b, size),
+            None if src.len() > MAX_LENGTH_PREFIX => {
+                return Err(RespError::InvalidBulkString(String::from(
+                    "invalid bulk length",
+                )));
+            }
             None => return Ok(None),
         };
 
@@ -155,6 +274,9 @@ impl RespType {
         }
 
         match Self::parse_usize_from_buf(&bulkstr_prefix_bytes[1..]) {
+            Ok(len) if len > max_len => Err(RespError::InvalidBulkString(String::from(
+                "invalid bulk length",
+            ))),
             Ok(len) => Ok(Some((len, bytes_read))),
             Err(e) => Err(e),
         }
@@ -177,7 +299,7 @@ impl RespType {
     /// Example SimpleString: `+OK\r\n`
     ///
     /// # SimpleString Parts:
-    /// ```
+    /// ```text
     ///      +      |      OK      | \r\n
     ///  identifier | string value | CRLF
     /// ```
@@ -223,4 +345,75 @@ impl RespType {
             Err(_) => Err(RespError::Other(String::from("Invalid UTF-8 string"))),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resp::frame::{DEFAULT_MAX_ARRAY_LEN, DEFAULT_MAX_BULK_LEN};
+
+    #[test]
+    fn bulk_string_len_over_limit_is_rejected() {
+        let res = RespType::parse_bulk_string_len(b"$1000000000000\r\n", DEFAULT_MAX_BULK_LEN);
+        assert!(matches!(res, Err(RespError::InvalidBulkString(_))));
+
+        let res = RespType::parse_bulk_string_len(b"$11\r\n", 10);
+        assert!(matches!(res, Err(RespError::InvalidBulkString(_))));
+        let res = RespType::parse_bulk_string_len(b"$10\r\n", 10);
+        assert!(matches!(res, Ok(Some((10, 5)))));
+    }
+
+    #[test]
+    fn array_len_over_limit_is_rejected() {
+        let res = RespType::parse_array_len(b"*99999999999\r\n", DEFAULT_MAX_ARRAY_LEN);
+        assert!(matches!(res, Err(RespError::InvalidArray(_))));
+
+        let res = RespType::parse_array_len(b"*4\r\n", 3);
+        assert!(matches!(res, Err(RespError::InvalidArray(_))));
+        let res = RespType::parse_array_len(b"*3\r\n", 3);
+        assert!(matches!(res, Ok(Some((3, 4)))));
+    }
+
+    #[test]
+    fn len_overflowing_usize_is_rejected() {
+        let prefix = b"$99999999999999999999999999\r\n";
+        assert!(RespType::parse_bulk_string_len(prefix, usize::MAX).is_err());
+
+        let prefix = b"*99999999999999999999999999\r\n";
+        assert!(RespType::parse_array_len(prefix, usize::MAX).is_err());
+    }
+
+    #[test]
+    fn len_prefix_without_crlf_is_rejected_once_too_long() {
+        // a prefix which can still be completed waits for more bytes
+        assert!(matches!(
+            RespType::parse_bulk_string_len(b"$1000", DEFAULT_MAX_BULK_LEN),
+            Ok(None)
+        ));
+
+        let prefix = format!("${}", "9".repeat(MAX_LENGTH_PREFIX + 1));
+        assert!(RespType::parse_bulk_string_len(prefix.as_bytes(), DEFAULT_MAX_BULK_LEN).is_err());
+
+        let prefix = format!("*{}", "9".repeat(MAX_LENGTH_PREFIX + 1));
+        assert!(RespType::parse_array_len(prefix.as_bytes(), DEFAULT_MAX_ARRAY_LEN).is_err());
+    }
+
+    #[test]
+    fn empty_bulk_string_is_decoded() {
+        let (value, len) = RespType::new_bulk_string(b"$0\r\n\r\n").unwrap();
+        assert_eq!(value, RespType::BulkString(String::new()));
+        assert_eq!(len, 6);
+
+        // the bytes which follow belong to the next value
+        let (value, len) = RespType::parse(b"$0\r\n\r\n+OK\r\n").unwrap().unwrap();
+        assert_eq!(value, RespType::BulkString(String::new()));
+        assert_eq!(len, 6);
+    }
+
+    #[test]
+    fn bulk_string_must_be_followed_by_crlf() {
+        assert!(RespType::new_bulk_string(b"$0\r\nXY").is_err());
+        assert!(RespType::new_bulk_string(b"$1\r\nab\r\n").is_err());
+        assert!(RespType::new_bulk_string(b"$0\r\n\r").is_err());
+    }
+}
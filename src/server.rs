@@ -4,77 +4,297 @@
 // This file implements a simple asynchronous echo server using Tokio.
 // The server accepts multiple TCP clients, prompts for input, and echoes each line
 // back to the client as a comment. It is designed to be single-threaded and easy to understand.
-use std::sync::Arc;
-use anyhow::{Error, Result};
-use log::error;
+use std::{
+    io::{self, ErrorKind},
+    net::SocketAddr,
+    time::Duration,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use anyhow::Result;
+use log::{debug, error, info, warn};
+use socket2::{SockRef, TcpKeepalive};
 use tokio::net::{TcpListener, TcpStream};
 use tokio_util::codec::Framed;
 
-use crate::{handler::FrameHandler, resp::frame::RespCommandFrame, storage::db::Storage};
+use crate::{client::ClientRegistry, config::Config, handler::FrameHandler, resp::frame::RespCommandFrame, storage::db::{Storage, DB}};
 /// The Server struct holds:
 ///
 /// * the tokio TcpListener which listens for incoming TCP connections.
 ///
 /// * Shared storage
 ///
+/// * Server options
+///
+/// * The counter used to assign IDs to client connections
+///
+/// * The registry of connected clients
+///
 #[derive(Debug)]
 pub struct Server {
     // TCP listener for incoming connections
     listener: TcpListener,
     // Shared storage for key-value pairs
     storage: Storage,
+    // Server options shared across all connections
+    config: Arc<Config>,
+    // ID of the last accepted connection
+    last_client_id: AtomicU64,
+    // Metadata of the connected clients
+    clients: Arc<ClientRegistry>,
 }
 
 impl Server {
     /// Create a new Server instance with the given TcpListener.
-    pub fn new(listener: TcpListener, storage: Storage) -> Server {
-        Server { listener, storage }
+    pub fn new(listener: TcpListener, storage: Storage, config: Config) -> Server {
+        Server {
+            listener,
+            storage,
+            config: Arc::new(config),
+            last_client_id: AtomicU64::new(0),
+            clients: Arc::new(ClientRegistry::new()),
+        }
     }
 
     /// Run the server: accept and handle multiple clients asynchronously.
     /// Each client is prompted for input and receives an echo of their input as a comment.
     pub async fn run(&mut self) -> Result<()> {
-        let db = self.storage.db().clone();
+        let db = self.storage.db();
+
+        match db.dbsize() {
+            Ok(size) => info!("DB loaded: {} keys", size),
+            Err(e) => error!("Failed to read the DB size: {}", e),
+        }
+        if let Some(interval) = self.config.report_interval {
+            Self::spawn_keyspace_reporter(Arc::clone(&db), interval);
+        }
+        if let Some(timeout) = self.config.idle_timeout {
+            Self::spawn_idle_reaper(Arc::clone(&self.clients), timeout);
+        }
+        Self::spawn_active_expirer(Arc::clone(&db));
+
+        let mut backoff = AcceptBackoff::new();
         loop {
-            // Accept a new TCP connection (or panic on error)
-            let sock = match self.accept_conn().await {
-                Ok(stream) => stream,
+            // Accept a new TCP connection. Failures are usually transient, such as running out
+            // of file descriptors, so the server waits a bit and tries again rather than exiting.
+            let (sock, addr) = match self.accept_conn().await {
+                Ok(conn) => {
+                    backoff.reset();
+                    conn
+                }
+                Err(e) if is_fatal_accept_error(&e) => {
+                    error!("Failed to accept connections: {}", e);
+                    return Err(e.into());
+                }
+                Err(e) if is_connection_error(&e) => {
+                    // Only this connection failed, the next one can be accepted right away.
+                    debug!("Failed to accept a connection: {}", e);
+                    continue;
+                }
                 Err(e) => {
-                    error!("{}", e);
-                    panic!("Error accepting connection");
+                    let delay = backoff.next_delay();
+                    error!("Failed to accept a connection, retrying in {:?}: {}", delay, e);
+                    tokio::time::sleep(delay).await;
+                    continue;
                 }
             };
 
             
             // Use RespCommandFrame codec to read incoming TCP messages as Redis command frames,
             // and to write RespType values into outgoing TCP messages.
-            let resp_command_frame = Framed::with_capacity(sock, RespCommandFrame::new(), 8 * 1024);
+            let codec = RespCommandFrame::with_limits(
+                self.config.proto_max_bulk_len,
+                self.config.proto_max_array_len,
+            );
+            let resp_command_frame = Framed::with_capacity(sock, codec, 8 * 1024);
+
+            // Assign a unique ID to the connection and add it to the registry of connected clients.
+            // The connection is removed from the registry when the handle is dropped at the end of the task.
+            let client_id = self.last_client_id.fetch_add(1, Ordering::Relaxed) + 1;
+            let client = self.clients.register(client_id, addr);
 
-            // Clone the Arc of DB for passing it to the tokio task.
+            // Clone the Arc of DB and config for passing them to the tokio task.
             let db = Arc::clone(&db);
+            let config = Arc::clone(&self.config);
              // Spawn a new asynchronous task to handle the connection.
              // This allows the server to handle multiple connections concurrently.
              tokio::spawn(async move {
-                let handler = FrameHandler::new(resp_command_frame);
+                // Count the connection as open until the task ends, even if it panics.
+                let _conn_guard = db.stats().connection_opened();
+                let handler = FrameHandler::new(resp_command_frame, config, client);
                 if let Err(e) = handler.handle(db.as_ref()).await {
-                    error!("Failed to handle command: {}", e);
+                    error!(client:% = addr; "Failed to handle connection from {}: {}", addr, e);
                 }
                 // The connection is closed automatically when `sock` goes out of scope.
             });
         }
     }
 
-    /// Accept a new incoming TCP connection and return the TcpStream.
-    /// Returns an error if the accept fails.
-    async fn accept_conn(&mut self) -> Result<TcpStream> {
-        loop {
-            // Wait for an incoming connection.
-            // The `accept()` method returns a tuple of (TcpStream, SocketAddr),
-            // but we only need the TcpStream.
-            match self.listener.accept().await {
-                Ok((sock, _)) => return Ok(sock),
-                Err(e) => return Err(Error::from(e)),
+    /// Spawn a task which logs a one-line summary of the keyspace at the given interval:
+    /// the number of keys, the approximate memory used and the number of connected clients.
+    fn spawn_keyspace_reporter(db: Arc<DB>, interval: Duration) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // the first tick completes immediately, and the startup log already covers it
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                match (db.dbsize(), db.used_memory()) {
+                    (Ok(keys), Ok(memory)) => info!(
+                        "Keyspace: {} keys, ~{} bytes used, {} connected clients",
+                        keys,
+                        memory,
+                        db.stats().connected_clients()
+                    ),
+                    (Err(e), _) | (_, Err(e)) => error!("Failed to read the keyspace: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Spawn a task which periodically asks the connections idle for longer than the timeout
+    /// to close. Connections are checked every second, or more often for shorter timeouts.
+    fn spawn_idle_reaper(clients: Arc<ClientRegistry>, timeout: Duration) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(timeout.min(Duration::from_secs(1)));
+            loop {
+                ticker.tick().await;
+                let closed = clients.close_idle(timeout);
+                if closed > 0 {
+                    debug!("Asked {} idle connections to close", closed);
+                }
+            }
+        });
+    }
+
+    /// Spawn a task which removes expired keys in the background, so that the keys which are
+    /// never accessed again don't stay in memory. Like in Redis, each run visits a few keys and
+    /// carries on while many of them turn out to be expired, within a time budget so that it
+    /// doesn't hold the write lock for long. It can be turned off with `DEBUG SET-ACTIVE-EXPIRE 0`.
+    fn spawn_active_expirer(db: Arc<DB>) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(ACTIVE_EXPIRE_INTERVAL);
+            let mut cursor = 0;
+            loop {
+                ticker.tick().await;
+                if !db.active_expire() {
+                    continue;
+                }
+
+                let start = tokio::time::Instant::now();
+                loop {
+                    let (visited, removed) =
+                        match db.expire_cycle(cursor, ACTIVE_EXPIRE_KEYS_PER_LOOP) {
+                            Ok((next, visited, removed)) => {
+                                cursor = next;
+                                (visited, removed)
+                            }
+                            Err(e) => {
+                                error!("Failed to remove expired keys: {}", e);
+                                break;
+                            }
+                        };
+                    if removed > 0 {
+                        debug!("Removed {} expired keys", removed);
+                    }
+                    // Stop once few of the keys were expired, or the whole keyspace was visited.
+                    if removed * 4 <= visited
+                        || cursor == 0
+                        || start.elapsed() >= ACTIVE_EXPIRE_TIME_BUDGET
+                    {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Apply the TCP options from the server config to an accepted connection.
+    /// Failing to set an option isn't fatal, the connection is still served.
+    fn configure_socket(&self, sock: &TcpStream) {
+        if self.config.tcp_nodelay {
+            if let Err(e) = sock.set_nodelay(true) {
+                warn!("Failed to set TCP_NODELAY: {}", e);
+            }
+        }
+
+        if let Some(idle) = self.config.tcp_keepalive {
+            let keepalive = TcpKeepalive::new().with_time(idle);
+            if let Err(e) = SockRef::from(sock).set_tcp_keepalive(&keepalive) {
+                warn!("Failed to enable TCP keepalive: {}", e);
             }
         }
     }
+
+    /// Accept a new incoming TCP connection and return the TcpStream along with the peer address.
+    /// Returns an error if the accept fails.
+    async fn accept_conn(&mut self) -> io::Result<(TcpStream, SocketAddr)> {
+        // Wait for an incoming connection.
+        let (sock, addr) = self.listener.accept().await?;
+        self.storage.db().stats().connection_accepted();
+        self.configure_socket(&sock);
+        Ok((sock, addr))
+    }
+}
+
+/// The delay before retrying after accepting a connection failed, starting at
+/// `ACCEPT_BACKOFF_MIN` and doubling on each consecutive failure up to `ACCEPT_BACKOFF_MAX`.
+#[derive(Debug)]
+struct AcceptBackoff {
+    delay: Duration,
+}
+
+/// How often the background task removing expired keys runs.
+const ACTIVE_EXPIRE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The number of keys checked for expiry by each step of the background task.
+const ACTIVE_EXPIRE_KEYS_PER_LOOP: usize = 20;
+
+/// The longest time a run of the background task keeps removing expired keys.
+const ACTIVE_EXPIRE_TIME_BUDGET: Duration = Duration::from_millis(25);
+
+/// Delay before retrying after the first accept failure.
+const ACCEPT_BACKOFF_MIN: Duration = Duration::from_millis(5);
+
+/// Longest delay between two accept attempts.
+const ACCEPT_BACKOFF_MAX: Duration = Duration::from_secs(1);
+
+impl AcceptBackoff {
+    fn new() -> AcceptBackoff {
+        AcceptBackoff {
+            delay: ACCEPT_BACKOFF_MIN,
+        }
+    }
+
+    /// Returns the delay to wait before the next attempt, and doubles the following one.
+    fn next_delay(&mut self) -> Duration {
+        let delay = self.delay;
+        self.delay = (self.delay * 2).min(ACCEPT_BACKOFF_MAX);
+        delay
+    }
+
+    /// Start again from the shortest delay, once a connection was accepted.
+    fn reset(&mut self) {
+        self.delay = ACCEPT_BACKOFF_MIN;
+    }
+}
+
+/// Returns whether an accept error only concerns the connection being accepted, such as a
+/// client resetting the connection before it is accepted.
+fn is_connection_error(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        ErrorKind::ConnectionAborted
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionRefused
+            | ErrorKind::Interrupted
+    )
+}
+
+/// Returns whether an accept error means the listener itself is unusable (`EINVAL`, the socket
+/// isn't listening), so that retrying can't succeed.
+fn is_fatal_accept_error(e: &io::Error) -> bool {
+    e.kind() == ErrorKind::InvalidInput
 }
\ No newline at end of file
@@ -0,0 +1,256 @@
+// src/stats.rs
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+/// Number of buckets per power of two in a latency histogram. The bucket a latency falls in
+/// is known to within 1/16th, so the reported percentiles are within ~6% of the actual values.
+const LATENCY_SUB_BUCKETS: u64 = 16;
+
+/// Number of buckets needed to cover latencies up to `u64::MAX` microseconds.
+const LATENCY_BUCKETS: usize = 976;
+
+/// The Stats struct holds server wide counters, reported by the STATS command.
+///
+/// The counters are atomics rather than being behind a lock, so that updating them
+/// doesn't contend with the commands accessing the data.
+#[derive(Debug, Default)]
+pub struct Stats {
+    /// Number of connections accepted since the server started.
+    total_connections: AtomicU64,
+    /// Number of connections which are currently open.
+    connected_clients: AtomicU64,
+    /// Number of commands executed since the server started.
+    total_commands: AtomicU64,
+    /// Execution latency histograms, by lowercased command name. Only filled in if the
+    /// server is started with `--track-timing`.
+    latencies: Mutex<HashMap<String, LatencyHistogram>>,
+    /// Replication ID of the server, a random 40 characters hex string generated at startup.
+    /// MuDB has no replication, but clients aware of it expect the ID in INFO.
+    replid: String,
+}
+
+/// A histogram of command latencies in microseconds, with buckets growing exponentially
+/// like an HDR histogram, so that it has a small fixed size whatever the latencies are.
+#[derive(Debug)]
+struct LatencyHistogram {
+    /// Number of latencies recorded in each bucket.
+    counts: Vec<u64>,
+    /// Total number of latencies recorded.
+    total: u64,
+    /// Highest latency recorded.
+    max: u64,
+}
+
+/// Summary of the latencies of a command, as reported by the LATENCY command.
+#[derive(Debug, Clone, Copy)]
+pub struct LatencySummary {
+    /// Number of executions of the command.
+    pub calls: u64,
+    /// Median latency in microseconds.
+    pub p50_us: u64,
+    /// 99th percentile latency in microseconds.
+    pub p99_us: u64,
+    /// Highest latency in microseconds.
+    pub max_us: u64,
+}
+
+/// Marks a connection as open for as long as it is alive.
+/// The connection is counted as closed when the guard is dropped.
+#[derive(Debug)]
+pub struct ConnectionGuard<'a> {
+    stats: &'a Stats,
+}
+
+impl Stats {
+    /// Create a new `Stats` instance with all the counters set to zero, and a new replication ID.
+    pub fn new() -> Stats {
+        let replid = (0..40)
+            .map(|_| char::from_digit(rand::random::<u32>() % 16, 16).unwrap())
+            .collect();
+        Stats {
+            replid,
+            ..Stats::default()
+        }
+    }
+
+    /// Returns the replication ID generated when the server started.
+    pub fn replid(&self) -> &str {
+        &self.replid
+    }
+
+    /// Count a newly accepted connection.
+    pub fn connection_accepted(&self) {
+        self.total_connections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a connection as open until the returned guard is dropped.
+    pub fn connection_opened(&self) -> ConnectionGuard<'_> {
+        self.connected_clients.fetch_add(1, Ordering::Relaxed);
+        ConnectionGuard { stats: self }
+    }
+
+    /// Count an executed command.
+    pub fn command_processed(&self) {
+        self.total_commands.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the number of connections which are currently open.
+    pub fn connected_clients(&self) -> u64 {
+        self.connected_clients.load(Ordering::Relaxed)
+    }
+
+    /// Record the time taken to execute a command.
+    pub fn record_latency(&self, cmd_name: &str, elapsed: Duration) {
+        let mut latencies = match self.latencies.lock() {
+            Ok(latencies) => latencies,
+            Err(e) => e.into_inner(),
+        };
+
+        let micros = elapsed.as_micros().min(u64::MAX as u128) as u64;
+        latencies
+            .entry(cmd_name.to_lowercase())
+            .or_insert_with(LatencyHistogram::new)
+            .record(micros);
+    }
+
+    /// Returns the latency summary of the given commands, or of every command executed
+    /// so far if no names are given. Commands which haven't been executed are skipped.
+    pub fn latency_summaries(&self, cmd_names: &[String]) -> Vec<(String, LatencySummary)> {
+        let latencies = match self.latencies.lock() {
+            Ok(latencies) => latencies,
+            Err(e) => e.into_inner(),
+        };
+
+        let mut summaries: Vec<(String, LatencySummary)> = if cmd_names.is_empty() {
+            latencies
+                .iter()
+                .map(|(name, histogram)| (name.clone(), histogram.summary()))
+                .collect()
+        } else {
+            cmd_names
+                .iter()
+                .filter_map(|name| {
+                    let name = name.to_lowercase();
+                    let summary = latencies.get(&name)?.summary();
+                    Some((name, summary))
+                })
+                .collect()
+        };
+        if cmd_names.is_empty() {
+            summaries.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+        summaries
+    }
+
+    /// Clears the latencies recorded so far.
+    ///
+    /// # Returns
+    ///
+    /// The number of commands whose latencies were cleared.
+    pub fn reset_latencies(&self) -> usize {
+        let mut latencies = match self.latencies.lock() {
+            Ok(latencies) => latencies,
+            Err(e) => e.into_inner(),
+        };
+
+        let reset = latencies.len();
+        latencies.clear();
+        reset
+    }
+
+    /// Returns the current value of each counter, along with its name.
+    pub fn snapshot(&self) -> Vec<(&'static str, u64)> {
+        vec![
+            (
+                "total_connections_received",
+                self.total_connections.load(Ordering::Relaxed),
+            ),
+            (
+                "connected_clients",
+                self.connected_clients.load(Ordering::Relaxed),
+            ),
+            (
+                "total_commands_processed",
+                self.total_commands.load(Ordering::Relaxed),
+            ),
+        ]
+    }
+}
+
+impl LatencyHistogram {
+    /// Create an empty histogram.
+    fn new() -> LatencyHistogram {
+        LatencyHistogram {
+            counts: vec![0; LATENCY_BUCKETS],
+            total: 0,
+            max: 0,
+        }
+    }
+
+    /// Returns the index of the bucket holding the given latency. Latencies below
+    /// `LATENCY_SUB_BUCKETS` have a bucket each, and every following power of two is split
+    /// into `LATENCY_SUB_BUCKETS` buckets.
+    fn bucket(micros: u64) -> usize {
+        if micros < LATENCY_SUB_BUCKETS {
+            return micros as usize;
+        }
+        let exp = 63 - micros.leading_zeros() as u64;
+        let sub_bucket = (micros >> (exp - 4)) & (LATENCY_SUB_BUCKETS - 1);
+        (((exp - 3) * LATENCY_SUB_BUCKETS) + sub_bucket) as usize
+    }
+
+    /// Returns the highest latency which falls in the given bucket.
+    fn bucket_max(bucket: usize) -> u64 {
+        let bucket = bucket as u64;
+        if bucket < LATENCY_SUB_BUCKETS {
+            return bucket;
+        }
+        let exp = bucket / LATENCY_SUB_BUCKETS + 3;
+        let sub_bucket = bucket % LATENCY_SUB_BUCKETS;
+        let min = (LATENCY_SUB_BUCKETS + sub_bucket) << (exp - 4);
+        min + ((1 << (exp - 4)) - 1)
+    }
+
+    /// Record a latency in microseconds.
+    fn record(&mut self, micros: u64) {
+        self.counts[Self::bucket(micros)] += 1;
+        self.total += 1;
+        self.max = self.max.max(micros);
+    }
+
+    /// Returns the latency below which the given percentage of the recorded latencies fall.
+    fn percentile(&self, percent: u64) -> u64 {
+        let rank = (self.total * percent).div_ceil(100).max(1);
+        let mut seen = 0;
+        for (bucket, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Self::bucket_max(bucket).min(self.max);
+            }
+        }
+        self.max
+    }
+
+    /// Summarize the recorded latencies.
+    fn summary(&self) -> LatencySummary {
+        LatencySummary {
+            calls: self.total,
+            p50_us: self.percentile(50),
+            p99_us: self.percentile(99),
+            max_us: self.max,
+        }
+    }
+}
+
+impl Drop for ConnectionGuard<'_> {
+    fn drop(&mut self) {
+        self.stats.connected_clients.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -1,41 +1,205 @@
 use std::{
-    collections::{HashMap, VecDeque},
-    sync::{Arc, RwLock},
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, OnceLock, PoisonError, RwLock,
+    },
+    mem::size_of,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use super::DBError;
+use futures::future::select_all;
+use rand::seq::SliceRandom;
+use tokio::sync::Notify;
+
+use crate::{config::DEFAULT_LIST_MAX_LISTPACK_SIZE, stats::Stats};
+
+use super::{
+    dump, glob,
+    hash::Hash,
+    keyspace::{self, Keyspace},
+    list::{self, List},
+    sorted_set::{LexBound, ScoreBound, SortedSet},
+    DBError,
+};
 
 
 /// The Storage struct is designed to act as a wrapper around the core database,
 /// allowing it to be shared across multiple connections. The database is encapsulated within an Arc,
 /// to enable concurrent access.
-
+///
+/// Sharing contract: the server creates exactly one `DB` at startup, and every connection works
+/// on that instance through a clone of the `Arc`, so a write made on one connection is visible
+/// to the reads of every other connection as soon as its lock is released. Cloning `Storage`
+/// or calling `Storage::db` only clones the `Arc`. `DB` itself doesn't implement `Clone`, so
+/// a copy of the data can't be made by accident.
 #[derive(Debug, Clone)]
 pub struct Storage {
     db: Arc<DB>,
 }
 
 /// The DB struct is the component that houses the actual data,
-/// which is stored in a RwLock wrapped around a HashMap. This ensures thread-safe read and write operations.
+/// which is stored in a RwLock wrapped around a Keyspace. This ensures thread-safe read and write operations.
 #[derive(Debug)]
 pub struct DB {
-    data: RwLock<HashMap<String, Entry>>,
+    data: RwLock<Keyspace>,
+    /// Per-key notifiers used to wake up clients blocked on an empty list (BLPOP, BRPOP).
+    /// An entry only exists while at least one client is waiting on the key.
+    list_notifiers: Mutex<HashMap<String, Arc<Notify>>>,
+    /// Server wide counters, such as the number of connected clients.
+    stats: Stats,
+    /// Whether new hashes keep their fields in sorted order (`--hash-ordered`).
+    hash_ordered: bool,
+    /// The maximum number of elements of a list stored as a listpack (`--list-max-listpack-size`).
+    list_max_listpack_size: usize,
+    /// Whether the server's background task removes expired keys (`DEBUG SET-ACTIVE-EXPIRE`).
+    active_expire: AtomicBool,
 }
 
 /// The Entry struct represents the value associated with a particular key in the database.
 /// This struct encapsulates the Value enum, which allows for different types of data to be stored.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Entry {
     value: Value,
+    /// The time after which the entry is considered deleted. `None` if the key doesn't expire.
+    /// Expired entries are removed the next time the key is accessed, or by `DB::expire_cycle`.
+    expires_at: Option<Instant>,
+    /// The time at which the key was last read or written, in milliseconds on the `clock_ms` clock.
+    /// This is an atomic, so that it can be updated by reads done under the read lock.
+    last_access: AtomicU64,
+    /// The access frequency of the key, as reported by OBJECT FREQ: a logarithmic counter of
+    /// the accesses in the lowest 8 bits, and the time it was last updated in minutes on the
+    /// `clock_ms` clock in the bits above. The counter decays as the key goes unused.
+    lfu: AtomicU64,
 }
 
 /// The `Value` enum allows for storing various types of data associated with a key.
-/// Currently, it supports only String and List data type. But it can be expanded in the future
-/// to support more data types as needed (like Hash, SortedSet etc).
+/// Currently, it supports String, List, Hash, Set and SortedSet data types. But it can be expanded
+/// in the future to support more data types as needed.
 #[derive(Debug, Clone)]
 pub enum Value {
     String(String),
-    List(VecDeque<String>)
+    List(List),
+    Hash(Hash),
+    Set(HashSet<String>),
+    SortedSet(SortedSet),
+}
+
+/// Hashes with at most this many fields are reported as "listpack" encoded.
+const HASH_MAX_LISTPACK_ENTRIES: usize = 128;
+
+/// Hashes whose fields and values are all at most this many bytes long are reported as "listpack" encoded.
+const HASH_MAX_LISTPACK_VALUE: usize = 64;
+
+/// Sets of integers with at most this many members are reported as "intset" encoded.
+const SET_MAX_INTSET_ENTRIES: usize = 512;
+
+/// Sets with at most this many members are reported as "listpack" encoded.
+const SET_MAX_LISTPACK_ENTRIES: usize = 128;
+
+/// Sets whose members are all at most this many bytes long are reported as "listpack" encoded.
+const SET_MAX_LISTPACK_VALUE: usize = 64;
+
+/// Sorted sets with at most this many members are reported as "listpack" encoded.
+const ZSET_MAX_LISTPACK_ENTRIES: usize = 128;
+
+/// Sorted sets whose members are all at most this many bytes long are reported as "listpack" encoded.
+const ZSET_MAX_LISTPACK_VALUE: usize = 64;
+
+/// The access frequency counter of a new key, so that it isn't the least frequently used key
+/// right away.
+const LFU_INIT_VAL: u8 = 5;
+
+/// How hard it is for the access frequency counter to grow. With a factor of 10, the counter
+/// saturates at 255 after about a million accesses, like in Redis.
+const LFU_LOG_FACTOR: f64 = 10.0;
+
+/// The number of minutes after which an unused key's access frequency counter is decremented.
+const LFU_DECAY_TIME: u64 = 1;
+
+/// The largest number of fields HRANDFIELD returns for a negative count, which can repeat
+/// fields. Redis streams these replies, but MuDB builds them in memory first, so an unbounded
+/// count could exhaust the memory of the server even on a hash with a single field.
+const HRANDFIELD_MAX_REPEATED: u64 = 1024 * 1024;
+
+/// Values with more than this many elements are freed on a background thread by UNLINK.
+const LAZYFREE_THRESHOLD: usize = 64;
+
+/// Identifies the end of a list an operation is applied to.
+#[derive(Debug, Clone, Copy)]
+pub enum ListEnd {
+    /// The head (left side) of the list.
+    Head,
+    /// The tail (right side) of the list.
+    Tail,
+}
+
+/// Conditions under which the expiry time of a key is updated, as given by the NX, XX, GT and LT
+/// options of the EXPIRE family of commands. A key without an expiry time is treated as having an
+/// infinite time to live when comparing with GT and LT. The default has no conditions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExpireCondition {
+    /// Only update the expiry time if the key doesn't have one.
+    pub nx: bool,
+    /// Only update the expiry time if the key already has one.
+    pub xx: bool,
+    /// Only update the expiry time if the new one is later than the current one.
+    pub gt: bool,
+    /// Only update the expiry time if the new one is earlier than the current one.
+    pub lt: bool,
+}
+
+/// Internal details of a key, as reported by the DEBUG OBJECT command.
+#[derive(Debug, Clone)]
+pub struct ObjectDetails {
+    /// The name of the internal representation of the value, as reported by OBJECT ENCODING.
+    pub encoding: &'static str,
+    /// The length, in bytes, of the value serialized by the DUMP command.
+    pub serialized_length: usize,
+    /// The time elapsed since the key was last read or written.
+    pub idle_time: Duration,
+    /// The time left until the key expires. `None` if the key doesn't expire.
+    pub ttl: Option<Duration>,
+    /// The number of elements of a list, hash, set or sorted set. `None` for a string.
+    pub elements: Option<usize>,
+    /// For a list stored as a quicklist, the maximum number of elements of a listpack
+    /// (`--list-max-listpack-size`). `None` for any other value.
+    pub quicklist_listpack_max: Option<usize>,
+}
+
+/// Key counts of a DB, as reported in the keyspace section of the INFO command.
+#[derive(Debug, Clone)]
+pub struct KeyspaceStats {
+    /// The number of keys.
+    pub keys: usize,
+    /// The number of keys with an expiry time.
+    pub expires: usize,
+    /// The average time left until the keys with an expiry time expire. Zero if no key expires.
+    pub avg_ttl: Duration,
+}
+
+/// Identifies how the expiry time of a key is changed by an operation.
+#[derive(Debug, Clone, Copy)]
+pub enum ExpiryOp {
+    /// The expiry time is left unchanged.
+    Keep,
+    /// The expiry time is removed, so the key doesn't expire.
+    Persist,
+    /// The key expires after the given duration from now.
+    ExpireIn(Duration),
+}
+
+impl ExpireCondition {
+    /// Returns whether a key whose current expiry time is `current_ms` can be given the
+    /// expiry time `new_ms`. Both are Unix times in milliseconds, `None` meaning no expiry.
+    fn allows(&self, current_ms: Option<i64>, new_ms: i64) -> bool {
+        match current_ms {
+            None => !self.xx && !self.gt,
+            Some(current_ms) => {
+                !self.nx && (!self.gt || new_ms > current_ms) && (!self.lt || new_ms < current_ms)
+            }
+        }
+    }
 }
 
 impl Storage {
@@ -53,14 +217,65 @@ impl Storage {
     }
 }
 
+/// The notifiers a client blocked on lists is waiting on. They are released when the wait
+/// ends, including when it is cancelled because the client disconnected.
+struct ListWaiter<'a> {
+    db: &'a DB,
+    keys: &'a [String],
+    notifiers: Vec<Arc<Notify>>,
+}
+
+impl Drop for ListWaiter<'_> {
+    fn drop(&mut self) {
+        self.notifiers.clear();
+        for k in self.keys {
+            self.db.release_list_notifier(k);
+        }
+    }
+}
+
+impl Default for DB {
+    fn default() -> DB {
+        DB::new()
+    }
+}
+
 impl DB {
     /// Create a new instance of DB.
     pub fn new() -> DB {
+        DB::with_options(false, DEFAULT_LIST_MAX_LISTPACK_SIZE)
+    }
+
+    /// Create a new instance of DB, whose hashes keep their fields in sorted order if
+    /// `hash_ordered` is set, and whose lists are stored as listpacks as long as they have at
+    /// most `list_max_listpack_size` elements. Only the hashes created from then on are affected.
+    pub fn with_options(hash_ordered: bool, list_max_listpack_size: usize) -> DB {
         DB {
-            data: RwLock::new(HashMap::new()),
+            data: RwLock::new(Keyspace::new()),
+            list_notifiers: Mutex::new(HashMap::new()),
+            stats: Stats::new(),
+            hash_ordered,
+            list_max_listpack_size,
+            active_expire: AtomicBool::new(true),
         }
     }
 
+    /// Returns whether expired keys are removed in the background, see `DB::expire_cycle`.
+    pub fn active_expire(&self) -> bool {
+        self.active_expire.load(Ordering::Relaxed)
+    }
+
+    /// Turn the removal of expired keys in the background on or off. When it is off, expired
+    /// keys are only removed when they are accessed.
+    pub fn set_active_expire(&self, enabled: bool) {
+        self.active_expire.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns the server wide counters.
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
     /// Get the string value stored against a key.
     ///
     /// # Arguments
@@ -69,18 +284,26 @@ impl DB {
     ///
     /// # Returns
     ///
-    /// * `Ok(Option<String>)` - `Some(String)` if key is found in DB, else `None`
+    /// * `Ok(Option<String>)` - `Some(String)` if key is found in DB, else `None`.
+    ///   A key whose expiry time has passed is never returned, and is removed from the DB.
     /// * `Err(DBError)` - if key already exists and has non-string data.
-
     pub fn get(&self, k: &str) -> Result<Option<String>, DBError> {
         let data = match self.data.read() {
             Ok(data) => data,
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
-        let entry = match data.get(k) {
+        let entry = match Self::lookup(&data, k) {
             Some(entry) => entry,
-            None => return Ok(None),
+            None => {
+                // The expiry time is checked under the read lock, so an expired value is never
+                // returned. Remove the entry right away rather than on the next write to the key.
+                if data.contains_key(k) {
+                    drop(data);
+                    self.purge_expired(k)?;
+                }
+                return Ok(None);
+            }
         };
 
         match &entry.value {
@@ -89,6 +312,80 @@ impl DB {
         }
     }
 
+    /// Get the string value stored against a key and delete the key, in a single operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which lookup is performed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<String>)` - `Some(String)` with the deleted value if key is found in DB, else `None`
+    /// * `Err(DBError)` - if key already exists and has non-string data. The key isn't deleted in this case.
+    pub fn getdel(&self, k: &str) -> Result<Option<String>, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, k);
+        match data.get(k) {
+            Some(entry) => match &entry.value {
+                Value::String(_) => {}
+                _ => return Err(DBError::WrongType),
+            },
+            None => return Ok(None),
+        }
+
+        match data.remove(k) {
+            Some(Entry {
+                value: Value::String(s),
+                ..
+            }) => Ok(Some(s)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Get the string value stored against a key, and change the expiry time of the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which lookup is performed.
+    ///
+    /// * `expiry_op` - How the expiry time of the key is changed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<String>)` - `Some(String)` if key is found in DB, else `None`
+    /// * `Err(DBError)` - if key already exists and has non-string data. The expiry time isn't changed in this case.
+    pub fn getex(&self, k: &str, expiry_op: ExpiryOp) -> Result<Option<String>, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, k);
+        let entry = match data.get_mut(k) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        entry.touch();
+
+        let value = match &entry.value {
+            Value::String(s) => s.to_string(),
+            _ => return Err(DBError::WrongType),
+        };
+
+        match expiry_op {
+            ExpiryOp::Keep => {}
+            ExpiryOp::Persist => entry.expires_at = None,
+            // a delay too large to be represented is treated as never expiring
+            ExpiryOp::ExpireIn(delay) => entry.expires_at = Instant::now().checked_add(delay),
+        }
+
+        Ok(Some(value))
+    }
+
     /// Set a string value against a key.
     ///
     /// # Arguments
@@ -97,32 +394,266 @@ impl DB {
     ///
     /// * `v` - The value to be set against the key.
     ///
+    /// * `ttl` - Time after which the key expires. `None` if the key doesn't expire. Any expiry
+    ///   time the key previously had is discarded either way.
+    ///
     /// # Returns
     ///
-    /// * `Ok(())` - If value is successfully added against the key.
+    /// * `Ok(Option<String>)` - If value is successfully added against the key. Contains the
+    ///   value previously stored against the key, if any.
     /// * `Err(DBError)` - if key already exists and has non-string data.
-    pub fn set(&self, k: String, v: Value) -> Result<(), DBError> {
+    pub fn set(&self, k: String, v: Value, ttl: Option<Duration>) -> Result<Option<String>, DBError> {
         let mut data = match self.data.write() {
             Ok(data) => data,
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
-        let entry = match data.get(k.as_str()) {
-            Some(entry) => Some(entry),
-            None => None,
-        };
-
-        if entry.is_some() {
-            match entry.unwrap().value {
+        Self::remove_expired(&mut data, &k);
+        if let Some(entry) = data.get(k.as_str()) {
+            match entry.value {
                 Value::String(_) => {}
                 _ => return Err(DBError::WrongType),
             }
         }
 
+        let mut entry = Entry::new(v);
+        // a delay too large to be represented is treated as never expiring
+        entry.expires_at = ttl.and_then(|ttl| Instant::now().checked_add(ttl));
+
         // since you already own k, you dont need to clone it
-        data.insert(k, Entry::new(v));
+        match data.insert(k, entry) {
+            Some(Entry {
+                value: Value::String(prev),
+                ..
+            }) => Ok(Some(prev)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Set a string value against a key, only if the current value equals `expected`.
+    /// The comparison and the update happen under a single lock, so no other write can
+    /// happen in between. The expiry time of the key is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which value is to be set.
+    ///
+    /// * `expected` - The value the key must currently hold.
+    ///
+    /// * `new` - The value to be set against the key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - If the value was set.
+    /// * `Ok(false)` - If the key doesn't exist, or holds a different value.
+    /// * `Err(DBError)` - if key already exists and has non-string data.
+    pub fn cas(&self, k: &str, expected: &str, new: String) -> Result<bool, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, k);
+        let entry = match data.get_mut(k) {
+            Some(entry) => entry,
+            None => return Ok(false),
+        };
+        entry.touch();
+
+        match &mut entry.value {
+            Value::String(s) if s == expected => {
+                *s = new;
+                Ok(true)
+            }
+            Value::String(_) => Ok(false),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Append a string to the value stored at key. If the key is not present in the DB, it is
+    /// set to the given string. The read of the current value and the write of the
+    /// concatenation happen under a single lock, so concurrent appends can't interleave.
+    /// The expiry time of the key is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which value is stored.
+    ///
+    /// * `v` - The string to be appended.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The length of the value after the append, in bytes.
+    /// * `Err(DBError)` - if key already exists and has non-string data.
+    pub fn append(&self, k: String, v: &str) -> Result<usize, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, &k);
+        let entry = data
+            .get_or_insert_with(k, || Entry::new(Value::String(String::new())));
+        entry.touch();
+
+        match &mut entry.value {
+            Value::String(s) => {
+                s.push_str(v);
+                Ok(s.len())
+            }
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Get a substring of the string value stored against a key, for the GETRANGE and SUBSTR
+    /// commands. Offsets are in bytes and both are included. Negative offsets count from the end
+    /// of the string, -1 being the last byte, and offsets past either end are rounded to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which lookup is performed.
+    ///
+    /// * `start` - Offset of the first byte of the substring.
+    ///
+    /// * `end` - Offset of the last byte of the substring.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The substring, empty if the key doesn't exist or the range is empty.
+    ///   A range which splits a multi-byte character has the partial character replaced
+    ///   with U+FFFD, since values must be valid UTF-8.
+    /// * `Err(DBError)` - if key already exists and has non-string data.
+    pub fn getrange(&self, k: &str, start: i64, end: i64) -> Result<String, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let s = match Self::lookup(&data, k) {
+            Some(entry) => match &entry.value {
+                Value::String(s) => s.as_bytes(),
+                _ => return Err(DBError::WrongType),
+            },
+            None => return Ok(String::new()),
+        };
+
+        // Same rounding as Redis. Unlike LRANGE, an end before the head is rounded to the
+        // first byte rather than making the range empty, unless both offsets are negative
+        // and out of order.
+        let len = s.len() as i64;
+        if s.is_empty() || (start < 0 && end < 0 && start > end) {
+            return Ok(String::new());
+        }
+        let start = if start < 0 { start + len } else { start }.max(0);
+        let end = if end < 0 { end + len } else { end }.clamp(0, len - 1);
+        if start > end {
+            return Ok(String::new());
+        }
+
+        Ok(String::from_utf8_lossy(&s[start as usize..=end as usize]).into_owned())
+    }
+
+    /// Find the longest common subsequence of the strings stored at two keys. Missing keys are
+    /// treated as empty strings. The subsequence is made of characters, so it is always valid UTF-8.
+    ///
+    /// The values are copied out of the DB before the subsequence is computed, so the lock isn't
+    /// held while it runs. Computing it takes time proportional to the product of the lengths of
+    /// the values, which is why their combined size is capped.
+    ///
+    /// # Arguments
+    ///
+    /// * `k1` - The key of the first string.
+    ///
+    /// * `k2` - The key of the second string.
+    ///
+    /// * `len_only` - Whether only the length of the subsequence is needed. This takes less memory.
+    ///
+    /// * `max_len` - The maximum combined size of the two values, in bytes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((usize, Option<String>))` - The length of the subsequence in characters, and the
+    ///   subsequence itself unless `len_only` is set.
+    /// * `Err(DBError)` - if one of the keys has non-string data, or the values are too large.
+    pub fn lcs(
+        &self,
+        k1: &str,
+        k2: &str,
+        len_only: bool,
+        max_len: usize,
+    ) -> Result<(usize, Option<String>), DBError> {
+        let (a, b) = {
+            let data = match self.data.read() {
+                Ok(data) => data,
+                Err(e) => return Err(DBError::Other(format!("{}", e))),
+            };
+
+            let mut values = [k1, k2].into_iter().map(|k| match Self::lookup(&data, k) {
+                Some(entry) => match &entry.value {
+                    Value::String(s) => Ok(s.as_str()),
+                    _ => Err(DBError::WrongType),
+                },
+                None => Ok(""),
+            });
+            let a = values.next().unwrap()?;
+            let b = values.next().unwrap()?;
+
+            if a.len().saturating_add(b.len()) > max_len {
+                return Err(DBError::Other(format!(
+                    "ERR LCS input is too large, the combined length of the values is limited to {} bytes",
+                    max_len
+                )));
+            }
+            (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>())
+        };
+
+        if len_only {
+            // Only the previous row of the table is needed for the length.
+            let mut prev = vec![0u32; b.len() + 1];
+            let mut row = vec![0u32; b.len() + 1];
+            for ca in a.iter() {
+                for (j, cb) in b.iter().enumerate() {
+                    row[j + 1] = if ca == cb {
+                        prev[j] + 1
+                    } else {
+                        prev[j + 1].max(row[j])
+                    };
+                }
+                std::mem::swap(&mut prev, &mut row);
+            }
+            return Ok((prev[b.len()] as usize, None));
+        }
+
+        // table[i * width + j] is the length of the LCS of a[..i] and b[..j].
+        let width = b.len() + 1;
+        let mut table = vec![0u32; (a.len() + 1) * width];
+        for (i, ca) in a.iter().enumerate() {
+            for (j, cb) in b.iter().enumerate() {
+                table[(i + 1) * width + j + 1] = if ca == cb {
+                    table[i * width + j] + 1
+                } else {
+                    table[i * width + j + 1].max(table[(i + 1) * width + j])
+                };
+            }
+        }
+
+        // Walk the table back from the end to collect the subsequence.
+        let mut lcs = Vec::with_capacity(table[a.len() * width + b.len()] as usize);
+        let (mut i, mut j) = (a.len(), b.len());
+        while i > 0 && j > 0 {
+            if a[i - 1] == b[j - 1] {
+                lcs.push(a[i - 1]);
+                i -= 1;
+                j -= 1;
+            } else if table[(i - 1) * width + j] >= table[i * width + j - 1] {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+        }
+        lcs.reverse();
 
-        return Ok(());
+        Ok((lcs.len(), Some(lcs.into_iter().collect())))
     }
 
     /// Add new elements to the head of a list.
@@ -145,28 +676,27 @@ impl DB {
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
-        let entry = match data.get_mut(k.as_str()) {
-            Some(entry) => Some(entry),
-            None => None,
-        };
-
-        match entry {
+        Self::remove_expired(&mut data, &k);
+        match data.get_mut(k.as_str()) {
             Some(e) => {
+                e.touch();
                 let val = &mut e.value;
                 match val {
                     Value::List(l) => {
                         for each in v.iter().cloned() {
-                            l.push_front(each);
+                            l.push_front(each, self.list_max_listpack_size);
                         }
+                        self.notify_list_waiters(&k);
                         Ok(l.len())
                     }
                     _ => Err(DBError::WrongType),
                 }
             }
             None => {
-                let list = VecDeque::from(v);
+                let list = List::from_vec(v, self.list_max_listpack_size);
                 let l_len = list.len();
                 data.insert(k.to_string(), Entry::new(Value::List(list)));
+                self.notify_list_waiters(&k);
 
                 Ok(l_len)
             }
@@ -193,120 +723,2402 @@ impl DB {
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
-        let entry = match data.get_mut(k.as_str()) {
-            Some(entry) => Some(entry),
-            None => None,
-        };
-
-        match entry {
+        Self::remove_expired(&mut data, &k);
+        match data.get_mut(k.as_str()) {
             Some(e) => {
+                e.touch();
                 let val = &mut e.value;
                 match val {
                     Value::List(l) => {
                         for each in v.iter().cloned() {
-                            l.push_back(each);
+                            l.push_back(each, self.list_max_listpack_size);
                         }
+                        self.notify_list_waiters(&k);
                         Ok(l.len())
                     }
                     _ => Err(DBError::WrongType),
                 }
             }
             None => {
-                let list = VecDeque::from(v);
+                let list = List::from_vec(v, self.list_max_listpack_size);
                 let l_len = list.len();
                 data.insert(k.to_string(), Entry::new(Value::List(list)));
+                self.notify_list_waiters(&k);
 
                 Ok(l_len)
             }
         }
     }
 
-    /// Returns the specified number of elements of the list stored at key, based on the start and stop indices.
-    /// These offsets can also be negative numbers indicating offsets starting at the end of the list.
-    /// For example, -1 is the last element of the list, -2 the penultimate, and so on.
-    /// Please note that the item at stop index is also included in the result.
-    ///
-    /// If the specified key is not found, an empty list is returned.
+    /// Set the given fields to their respective values in the hash stored at key.
+    /// If the key is not present in the DB, an empty hash is initialized against the key
+    /// before setting the fields.
     ///
     /// # Arguments
     ///
-    /// * `k` - The key on which list is stored.
-    ///
-    /// * `start_idx` - The start index.
+    /// * `k` - The key on which hash is stored.
     ///
-    /// * `stop_idx` - The end index.
+    /// * `fields` - The field-value pairs to be set in the hash.
     ///
     /// # Returns
     ///
-    /// * `Ok(Vec<String>)` - If values are retrieved successfully from the list.
-    /// * `Err(DBError)` - if key already exists and has non-list data.
-    pub fn lrange(&self, k: String, start_idx: i64, stop_idx: i64) -> Result<Vec<String>, DBError> {
-        let data = match self.data.read() {
+    /// * `Ok(usize)` - The number of fields that were added (fields that were updated are not counted).
+    /// * `Err(DBError)` - if key already exists and has non-hash data.
+    pub fn hset(&self, k: String, fields: Vec<(String, String)>) -> Result<usize, DBError> {
+        let mut data = match self.data.write() {
             Ok(data) => data,
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
-        let entry = match data.get(k.as_str()) {
-            Some(entry) => entry,
-            None => return Ok(vec![]),
-        };
+        Self::remove_expired(&mut data, &k);
+        let entry = data
+            .get_or_insert_with(k, || Entry::new(Value::Hash(Hash::new(self.hash_ordered))));
+        entry.touch();
 
-        match &entry.value {
-            Value::List(l) => {
-                let l_len = l.len() as i64;
-                let (rounded_start_idx, rounded_stop_idx) =
-                    Self::round_list_indices(l_len, start_idx, stop_idx);
-                    println!("LRANGE: key={}, list_len={}, start_idx={}, stop_idx={}, rounded=({}, {})", k, l_len, start_idx, stop_idx, rounded_start_idx, rounded_stop_idx);
-                    let slice: Vec<String> = l.range(rounded_start_idx..rounded_stop_idx)
-                        .cloned()
-                        .collect();
-                    println!("LRANGE result: {:?}", slice);
-                    Ok(slice)
+        match &mut entry.value {
+            Value::Hash(h) => {
+                let mut added = 0;
+                for (field, value) in fields {
+                    if h.insert(field, value).is_none() {
+                        added += 1;
+                    }
+                }
+                Ok(added)
             }
             _ => Err(DBError::WrongType),
         }
     }
 
-    /// Round index to 0, if the given index value is less than zero.
-    /// Round index to list length, if the given index value is greater then the list length.
-    fn round_list_index(list_len: i64, idx: i64) -> usize {
-        // Redis semantics: negative means from end, -1 is last element
-        let mut idx = idx;
-        if idx < 0 {
-            idx = list_len + idx;
-        }
-        if idx < 0 {
-            idx = 0;
-        }
-        if idx >= list_len {
-            idx = list_len - 1;
-        }
-        idx as usize
-    }
+    /// Set a field to the given value in the hash stored at key, only if the field doesn't
+    /// exist yet. If the key is not present in the DB, a hash is created against the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which hash is stored.
+    ///
+    /// * `field` - The field to be set.
+    ///
+    /// * `value` - The value of the field.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the field was set, `false` if it already existed.
+    /// * `Err(DBError)` - if key already exists and has non-hash data.
+    pub fn hsetnx(&self, k: String, field: String, value: String) -> Result<bool, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
 
-    /// Round the start and stop indices using `Self::round_list_index` method and return them as
-    /// a tuple.
-    /// Special condition: If stop index is lower than start index, return (0, 0).
-    fn round_list_indices(list_len: i64, start_idx: i64, stop_idx: i64) -> (usize, usize) {
-        // Redis semantics: inclusive range
-        if list_len == 0 {
-            return (0, 0);
-        }
-        let start = Self::round_list_index(list_len, start_idx);
-        let mut stop = Self::round_list_index(list_len, stop_idx);
-        // Clamp stop to list_len - 1
-        if stop >= list_len as usize {
-            stop = list_len as usize - 1;
-        }
-        if start > stop {
-            return (0, 0);
+        Self::remove_expired(&mut data, &k);
+        let entry = data
+            .get_or_insert_with(k, || Entry::new(Value::Hash(Hash::new(self.hash_ordered))));
+        entry.touch();
+
+        match &mut entry.value {
+            Value::Hash(h) => {
+                if h.contains_key(&field) {
+                    return Ok(false);
+                }
+                h.insert(field, value);
+                Ok(true)
+            }
+            _ => Err(DBError::WrongType),
         }
-        (start, stop + 1) // Rust range is exclusive, so add 1
     }
-}
 
-impl Entry {
-    pub fn new(value: Value) -> Entry {
-        Entry { value }
-    }
-}
\ No newline at end of file
+    /// Get the values of the given fields in the hash stored at key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which hash is stored.
+    ///
+    /// * `fields` - The fields to be looked up.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<String>>)` - The value of each field, in the order of `fields`. `None` for fields
+    ///   which don't exist. If the key doesn't exist, all the values are `None`.
+    /// * `Err(DBError)` - if key already exists and has non-hash data.
+    pub fn hmget(&self, k: &str, fields: &[String]) -> Result<Vec<Option<String>>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok(vec![None; fields.len()]),
+        };
+
+        match &entry.value {
+            Value::Hash(h) => Ok(fields.iter().map(|f| h.get(f).cloned()).collect()),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Get all the field names of the hash stored at key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which hash is stored.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - The field names of the hash. Empty if the key doesn't exist.
+    /// * `Err(DBError)` - if key already exists and has non-hash data.
+    pub fn hkeys(&self, k: &str) -> Result<Vec<String>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok(vec![]),
+        };
+
+        match &entry.value {
+            Value::Hash(h) => Ok(h.keys().cloned().collect()),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Get all the values of the hash stored at key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which hash is stored.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - The values of the hash. Empty if the key doesn't exist.
+    /// * `Err(DBError)` - if key already exists and has non-hash data.
+    pub fn hvals(&self, k: &str) -> Result<Vec<String>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok(vec![]),
+        };
+
+        match &entry.value {
+            Value::Hash(h) => Ok(h.values().cloned().collect()),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Get random fields, along with their values, from the hash stored at key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which hash is stored.
+    ///
+    /// * `count` - The number of fields to return. If positive, the fields are distinct, and
+    ///   at most all the fields of the hash are returned. If negative, exactly `-count` fields
+    ///   are returned and the same field may be returned more than once. Since the whole reply
+    ///   is built in memory, `-count` can't be more than `HRANDFIELD_MAX_REPEATED`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(String, String)>)` - The field-value pairs. Empty if the key doesn't exist.
+    /// * `Err(DBError)` - if key already exists and has non-hash data, or if a negative count
+    ///   asks for too many fields.
+    pub fn hrandfield(&self, k: &str, count: i64) -> Result<Vec<(String, String)>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok(vec![]),
+        };
+
+        let h = match &entry.value {
+            Value::Hash(h) => h,
+            _ => return Err(DBError::WrongType),
+        };
+
+        let mut rng = rand::thread_rng();
+        let all: Vec<(&String, &String)> = h.iter().collect();
+        let fields: Vec<(&String, &String)> = if count >= 0 {
+            // at most all the fields, so a huge count doesn't allocate more than the hash
+            let count = count.min(all.len() as i64) as usize;
+            all.choose_multiple(&mut rng, count).copied().collect()
+        } else if all.is_empty() {
+            vec![]
+        } else {
+            if count.unsigned_abs() > HRANDFIELD_MAX_REPEATED {
+                return Err(DBError::Other(format!(
+                    "ERR count is out of range, at most {} fields can be returned with a negative count",
+                    HRANDFIELD_MAX_REPEATED
+                )));
+            }
+            (0..count.unsigned_abs())
+                .filter_map(|_| all.choose(&mut rng).copied())
+                .collect()
+        };
+
+        Ok(fields
+            .into_iter()
+            .map(|(f, v)| (f.to_string(), v.to_string()))
+            .collect())
+    }
+
+    /// Returns a page of the hash stored at key, for iterating it with a cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which hash is stored.
+    ///
+    /// * `cursor` - The cursor returned by the previous call, or 0 to start a new iteration.
+    ///
+    /// * `pattern` - If set, only the fields matching this glob-style pattern are returned.
+    ///
+    /// * `count` - The number of fields visited by the call, before they are filtered by `pattern`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((u64, Vec<(String, String)>))` - The cursor for the next call, 0 once the iteration is
+    ///   complete, and the fields of the page. A missing key is an empty hash.
+    /// * `Err(DBError)` - if key already exists and has non-hash data.
+    pub fn hscan(
+        &self,
+        k: &str,
+        cursor: u64,
+        pattern: Option<&str>,
+        count: usize,
+    ) -> Result<(u64, Vec<(String, String)>), DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok((0, vec![])),
+        };
+
+        match &entry.value {
+            Value::Hash(h) => {
+                let (next, page) = Self::scan_page(h.iter(), cursor, pattern, count);
+                Ok((next, page.into_iter().map(|(f, v)| (f, v.to_string())).collect()))
+            }
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Set the expiry time of the given fields of the hash stored at key, to the given number
+    /// of milliseconds from now. Fields whose time to live is 0 are deleted immediately, and the
+    /// key is deleted once it has no fields left.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which hash is stored.
+    ///
+    /// * `ttl_ms` - The time to live of the fields, in milliseconds. Must not be negative.
+    ///
+    /// * `condition` - The conditions under which the expiry time of each field is updated.
+    ///
+    /// * `fields` - The fields on which expiry is set.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<i64>)` - The outcome for each field, in the order of `fields`, as replied by
+    ///   HEXPIRE: -2 if the field (or the key) doesn't exist, 0 if the conditions weren't met,
+    ///   1 if the expiry time was set, 2 if the field was deleted.
+    /// * `Err(DBError)` - if key already exists and has non-hash data.
+    pub fn hexpire(
+        &self,
+        k: &str,
+        ttl_ms: i64,
+        condition: ExpireCondition,
+        fields: &[String],
+    ) -> Result<Vec<i64>, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, k);
+        let entry = match data.get_mut(k) {
+            Some(entry) => entry,
+            None => return Ok(vec![-2; fields.len()]),
+        };
+        entry.touch();
+        let h = match &mut entry.value {
+            Value::Hash(h) => h,
+            _ => return Err(DBError::WrongType),
+        };
+
+        // `ExpireCondition` compares the expiry times, which compare the same way as the
+        // times to live, so the times to live are passed to it directly.
+        let now = Instant::now();
+        let mut outcomes = Vec::with_capacity(fields.len());
+        for field in fields {
+            let current_ms = match h.ttl(field) {
+                Some(ttl) => ttl.map(|ttl| ttl.as_millis() as i64),
+                None => {
+                    outcomes.push(-2);
+                    continue;
+                }
+            };
+            if !condition.allows(current_ms, ttl_ms) {
+                outcomes.push(0);
+            } else if ttl_ms == 0 {
+                h.remove(field);
+                outcomes.push(2);
+            } else {
+                if let Some(expires_at) = now.checked_add(Duration::from_millis(ttl_ms as u64)) {
+                    h.set_expiry(field, expires_at);
+                }
+                outcomes.push(1);
+            }
+        }
+
+        if h.is_empty() {
+            data.remove(k);
+        }
+        Ok(outcomes)
+    }
+
+    /// Returns the time left until the given fields of the hash stored at key expire.
+    /// Looking up the times to live doesn't count as an access.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which hash is stored.
+    ///
+    /// * `fields` - The fields on which lookup is performed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<Option<Duration>>>)` - For each field, in the order of `fields`: `None` if
+    ///   the field (or the key) doesn't exist, `Some(None)` if the field doesn't expire, else
+    ///   `Some(Some(Duration))` with the time left.
+    /// * `Err(DBError)` - if key already exists and has non-hash data.
+    pub fn httl(&self, k: &str, fields: &[String]) -> Result<Vec<Option<Option<Duration>>>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        match data.get(k).filter(|e| !e.is_expired()).map(|e| &e.value) {
+            Some(Value::Hash(h)) => Ok(fields.iter().map(|f| h.ttl(f)).collect()),
+            Some(_) => Err(DBError::WrongType),
+            None => Ok(vec![None; fields.len()]),
+        }
+    }
+
+    /// Add the given members to the set stored at key.
+    /// If the key is not present in the DB, an empty set is initialized against the key
+    /// before adding the members.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which set is stored.
+    ///
+    /// * `members` - The members to be added to the set.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of members that were added (members already present are not counted).
+    /// * `Err(DBError)` - if key already exists and has non-set data.
+    pub fn sadd(&self, k: String, members: Vec<String>) -> Result<usize, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, &k);
+        let entry = data
+            .get_or_insert_with(k, || Entry::new(Value::Set(HashSet::new())));
+        entry.touch();
+
+        match &mut entry.value {
+            Value::Set(set) => Ok(members.into_iter().filter(|m| set.insert(m.clone())).count()),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Check whether each of the given members belongs to the set stored at key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which set is stored.
+    ///
+    /// * `members` - The members to be looked up.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<bool>)` - Whether each member belongs to the set, in the order of `members`.
+    ///   If the key doesn't exist, all the values are `false`.
+    /// * `Err(DBError)` - if key already exists and has non-set data.
+    pub fn smismember(&self, k: &str, members: &[String]) -> Result<Vec<bool>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok(vec![false; members.len()]),
+        };
+
+        match &entry.value {
+            Value::Set(set) => Ok(members.iter().map(|m| set.contains(m)).collect()),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Store the intersection of the sets stored at the given keys in the destination key.
+    ///
+    /// Like in Redis, the destination is overwritten whatever its type, and loses its expiry
+    /// time. The sources are read and the destination is written under the same write lock,
+    /// so no other command can observe or modify the keys in between.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest` - The key on which the intersection is stored.
+    ///
+    /// * `keys` - The keys on which the sets to intersect are stored.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of members in the intersection. If it is empty, the
+    ///   destination key is deleted. A missing source key is an empty set.
+    /// * `Err(DBError)` - if any of the source keys has non-set data.
+    pub fn sinterstore(&self, dest: String, keys: &[String]) -> Result<usize, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let mut sets = Vec::with_capacity(keys.len());
+        let mut missing = false;
+        for k in keys {
+            match Self::lookup(&data, k).map(|entry| &entry.value) {
+                Some(Value::Set(set)) => sets.push(set),
+                Some(_) => return Err(DBError::WrongType),
+                None => missing = true,
+            }
+        }
+
+        // iterate over the smallest set, and keep the members found in all the others
+        sets.sort_by_key(|set| set.len());
+        let result: HashSet<String> = match sets.split_first() {
+            Some((smallest, others)) if !missing => smallest
+                .iter()
+                .filter(|m| others.iter().all(|set| set.contains(*m)))
+                .cloned()
+                .collect(),
+            _ => HashSet::new(),
+        };
+
+        let len = result.len();
+        data.remove(&dest);
+        if len > 0 {
+            data.insert(dest, Entry::new(Value::Set(result)));
+        }
+
+        Ok(len)
+    }
+
+    /// Returns a page of the set stored at key, for iterating it with a cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which set is stored.
+    ///
+    /// * `cursor` - The cursor returned by the previous call, or 0 to start a new iteration.
+    ///
+    /// * `pattern` - If set, only the members matching this glob-style pattern are returned.
+    ///
+    /// * `count` - The number of members visited by the call, before they are filtered by `pattern`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((u64, Vec<String>))` - The cursor for the next call, 0 once the iteration is
+    ///   complete, and the members of the page. A missing key is an empty set.
+    /// * `Err(DBError)` - if key already exists and has non-set data.
+    pub fn sscan(
+        &self,
+        k: &str,
+        cursor: u64,
+        pattern: Option<&str>,
+        count: usize,
+    ) -> Result<(u64, Vec<String>), DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok((0, vec![])),
+        };
+
+        match &entry.value {
+            Value::Set(set) => {
+                let members = set.iter().map(|m| (m, ()));
+                let (next, page) = Self::scan_page(members, cursor, pattern, count);
+                Ok((next, page.into_iter().map(|(m, _)| m).collect()))
+            }
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Add the given members with their scores to the sorted set stored at key.
+    /// If the key doesn't exist, a new sorted set is created. The score of members that
+    /// are already present is updated.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which sorted set is stored.
+    ///
+    /// * `members` - The (score, member) pairs to be added. Scores must not be NaN.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of members that were added (updated members are not counted).
+    /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+    pub fn zadd(&self, k: String, members: Vec<(f64, String)>) -> Result<usize, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, &k);
+        let entry = data
+            .get_or_insert_with(k, || Entry::new(Value::SortedSet(SortedSet::new())));
+        entry.touch();
+
+        match &mut entry.value {
+            Value::SortedSet(zset) => Ok(members
+                .into_iter()
+                .filter(|(score, member)| zset.insert(member.clone(), *score))
+                .count()),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Remove the given members from the sorted set stored at key.
+    /// The key is deleted once its last member is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which sorted set is stored.
+    ///
+    /// * `members` - The members to be removed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of members that were removed. 0 if the key doesn't exist.
+    /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+    pub fn zrem(&self, k: &str, members: &[String]) -> Result<usize, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, k);
+        let entry = match data.get_mut(k) {
+            Some(entry) => entry,
+            None => return Ok(0),
+        };
+        entry.touch();
+
+        let (removed, is_empty) = match &mut entry.value {
+            Value::SortedSet(zset) => {
+                let removed = members.iter().filter(|m| zset.remove(m)).count();
+                (removed, zset.is_empty())
+            }
+            _ => return Err(DBError::WrongType),
+        };
+
+        if is_empty {
+            data.remove(k);
+        }
+
+        Ok(removed)
+    }
+
+    /// Returns the number of members of the sorted set stored at key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which sorted set is stored.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of members. 0 if the key doesn't exist.
+    /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+    pub fn zcard(&self, k: &str) -> Result<usize, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok(0),
+        };
+
+        match &entry.value {
+            Value::SortedSet(zset) => Ok(zset.len()),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Increment the score of a member of the sorted set stored at key by `delta`.
+    /// If the member doesn't exist, it is added with `delta` as its score. If the key doesn't
+    /// exist, a new sorted set is created.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which sorted set is stored.
+    ///
+    /// * `delta` - The amount to increment the score by. Must not be NaN.
+    ///
+    /// * `member` - The member whose score is incremented.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - The new score of the member.
+    /// * `Err(DBError)` - if key already exists and has non-sorted set data, or if the
+    ///   resulting score is NaN (e.g. when adding `-inf` to `+inf`).
+    pub fn zincrby(&self, k: &str, delta: f64, member: &str) -> Result<f64, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, k);
+        let current = match data.get(k) {
+            Some(entry) => match &entry.value {
+                Value::SortedSet(zset) => zset.score(member),
+                _ => return Err(DBError::WrongType),
+            },
+            None => None,
+        };
+
+        let score = current.unwrap_or(0.0) + delta;
+        if score.is_nan() {
+            return Err(DBError::Other(String::from(
+                "ERR resulting score is not a number (NaN)",
+            )));
+        }
+
+        let entry = data
+            .get_or_insert_with(k.to_string(), || Entry::new(Value::SortedSet(SortedSet::new())));
+        entry.touch();
+
+        match &mut entry.value {
+            Value::SortedSet(zset) => {
+                zset.insert(member.to_string(), score);
+                Ok(score)
+            }
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Return the rank of a member of the sorted set stored at key, with the scores ordered
+    /// from low to high. Members with equal scores are ordered lexicographically.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which sorted set is stored.
+    ///
+    /// * `member` - The member whose rank is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(usize))` - The zero-based rank of the member.
+    /// * `Ok(None)` - if the key or the member doesn't exist.
+    /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+    pub fn zrank(&self, k: &str, member: &str) -> Result<Option<usize>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+
+        match &entry.value {
+            Value::SortedSet(zset) => Ok(zset.rank(member)),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Return the rank of a member of the sorted set stored at key, with the scores ordered
+    /// from high to low. Members with equal scores are ordered lexicographically, in reverse.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which sorted set is stored.
+    ///
+    /// * `member` - The member whose rank is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(usize))` - The zero-based rank of the member.
+    /// * `Ok(None)` - if the key or the member doesn't exist.
+    /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+    pub fn zrevrank(&self, k: &str, member: &str) -> Result<Option<usize>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+
+        match &entry.value {
+            Value::SortedSet(zset) => Ok(zset.rev_rank(member)),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Return the members of the sorted set stored at key whose score falls between `min` and `max`.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which sorted set is stored.
+    ///
+    /// * `min` - The lower bound of the score range.
+    ///
+    /// * `max` - The upper bound of the score range.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(String, f64)>)` - The matching members with their scores, ordered by ascending
+    ///   score. Empty if the key doesn't exist.
+    /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+    pub fn zrangebyscore(
+        &self,
+        k: &str,
+        min: ScoreBound,
+        max: ScoreBound,
+    ) -> Result<Vec<(String, f64)>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok(vec![]),
+        };
+
+        match &entry.value {
+            Value::SortedSet(zset) => Ok(zset.range_by_score(min, max)),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Return the members of the sorted set stored at key which fall between `min` and `max`
+    /// when compared lexicographically. The result is only meaningful if all the members
+    /// of the sorted set have the same score.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which sorted set is stored.
+    ///
+    /// * `min` - The lower bound of the range.
+    ///
+    /// * `max` - The upper bound of the range.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - The matching members, in the order of the sorted set.
+    ///   Empty if the key doesn't exist.
+    /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+    pub fn zrangebylex(&self, k: &str, min: &LexBound, max: &LexBound) -> Result<Vec<String>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok(vec![]),
+        };
+
+        match &entry.value {
+            Value::SortedSet(zset) => Ok(zset.range_by_lex(min, max)),
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Returns a page of the sorted set stored at key, for iterating it with a cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which sorted set is stored.
+    ///
+    /// * `cursor` - The cursor returned by the previous call, or 0 to start a new iteration.
+    ///
+    /// * `pattern` - If set, only the members matching this glob-style pattern are returned.
+    ///
+    /// * `count` - The number of members visited by the call, before they are filtered by `pattern`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((u64, Vec<(String, f64)>))` - The cursor for the next call, 0 once the iteration is
+    ///   complete, and the members of the page. A missing key is an empty sorted set.
+    /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+    pub fn zscan(
+        &self,
+        k: &str,
+        cursor: u64,
+        pattern: Option<&str>,
+        count: usize,
+    ) -> Result<(u64, Vec<(String, f64)>), DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok((0, vec![])),
+        };
+
+        match &entry.value {
+            Value::SortedSet(zset) => {
+                let (next, page) = Self::scan_page(zset.iter(), cursor, pattern, count);
+                Ok((next, page.into_iter().map(|(m, score)| (m, *score)).collect()))
+            }
+            _ => Err(DBError::WrongType),
+        }
+    }
+
+    /// Removes and returns an element from the first non-empty list among the given keys.
+    /// Keys are checked in the order they are given, and a list is deleted once its
+    /// last element is popped.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys on which lists are stored.
+    ///
+    /// * `end` - The end of the list from which the element is popped.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some((String, String)))` - The key and the popped element, if a non-empty list was found.
+    /// * `Ok(None)` - If none of the keys hold a non-empty list.
+    /// * `Err(DBError)` - if one of the keys has non-list data.
+    pub fn pop_first(&self, keys: &[String], end: ListEnd) -> Result<Option<(String, String)>, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        for k in keys {
+            Self::remove_expired(&mut data, k);
+            let l = match data.get_mut(k.as_str()) {
+                Some(entry) => {
+                    entry.touch();
+                    match &mut entry.value {
+                        Value::List(l) => l,
+                        _ => return Err(DBError::WrongType),
+                    }
+                }
+                None => continue,
+            };
+
+            let elem = match end {
+                ListEnd::Head => l.pop_front(),
+                ListEnd::Tail => l.pop_back(),
+            };
+
+            if let Some(elem) = elem {
+                if l.is_empty() {
+                    data.remove(k.as_str());
+                }
+                return Ok(Some((k.to_string(), elem)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Pops up to `count` elements from the first non-empty list among the given keys.
+    /// Keys are checked in the order they are given, and a list is deleted once its
+    /// last element is popped.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys on which lists are stored.
+    ///
+    /// * `end` - The end of the list from which the elements are popped.
+    ///
+    /// * `count` - The maximum number of elements to pop.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some((String, Vec<String>)))` - The key and the popped elements, in the order they were popped.
+    /// * `Ok(None)` - If none of the keys hold a non-empty list.
+    /// * `Err(DBError)` - if one of the keys has non-list data.
+    pub fn lmpop(
+        &self,
+        keys: &[String],
+        end: ListEnd,
+        count: usize,
+    ) -> Result<Option<(String, Vec<String>)>, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        for k in keys {
+            Self::remove_expired(&mut data, k);
+            let l = match data.get_mut(k.as_str()) {
+                Some(entry) => {
+                    entry.touch();
+                    match &mut entry.value {
+                        Value::List(l) => l,
+                        _ => return Err(DBError::WrongType),
+                    }
+                }
+                None => continue,
+            };
+
+            let n = count.min(l.len());
+            if n == 0 {
+                continue;
+            }
+            let elems: Vec<String> = match end {
+                ListEnd::Head => l.drain(0..n),
+                ListEnd::Tail => l.drain(l.len() - n..l.len()).into_iter().rev().collect(),
+            };
+
+            if l.is_empty() {
+                data.remove(k.as_str());
+            }
+            return Ok(Some((k.to_string(), elems)));
+        }
+
+        Ok(None)
+    }
+
+    /// Same as `pop_first`, but if all the lists are empty, waits until an element is pushed
+    /// to one of them or until the timeout elapses. The wait can be cancelled by dropping the
+    /// returned future, in which case no element is popped.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys on which lists are stored.
+    ///
+    /// * `end` - The end of the list from which the element is popped.
+    ///
+    /// * `timeout` - Maximum time to wait for an element. `None` waits indefinitely.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some((String, String)))` - The key and the popped element.
+    /// * `Ok(None)` - If the timeout elapsed before any element was available.
+    /// * `Err(DBError)` - if one of the keys has non-list data.
+    pub async fn blocking_pop(
+        &self,
+        keys: &[String],
+        end: ListEnd,
+        timeout: Option<Duration>,
+    ) -> Result<Option<(String, String)>, DBError> {
+        let deadline = timeout.map(|t| tokio::time::Instant::now() + t);
+        let waiter = ListWaiter {
+            db: self,
+            keys,
+            notifiers: keys.iter().map(|k| self.list_notifier(k)).collect(),
+        };
+        let notifiers = &waiter.notifiers;
+
+        loop {
+            // Register for notifications before checking the lists, so that a push happening
+            // between the check and the wait is not missed.
+            let notified: Vec<_> = notifiers.iter().map(|n| Box::pin(n.notified())).collect();
+
+            match self.pop_first(keys, end) {
+                Ok(None) => {}
+                res => return res,
+            }
+
+            let wait = select_all(notified);
+            match deadline {
+                Some(deadline) => {
+                    if tokio::time::timeout_at(deadline, wait).await.is_err() {
+                        return Ok(None);
+                    }
+                }
+                None => {
+                    wait.await;
+                }
+            }
+        }
+    }
+
+    /// Returns the entry stored against the key if it hasn't expired, and records the access.
+    fn lookup<'a>(data: &'a Keyspace, k: &str) -> Option<&'a Entry> {
+        let entry = data.get(k).filter(|e| !e.is_expired())?;
+        entry.touch();
+        Some(entry)
+    }
+
+    /// Removes the entry stored against the key if it has expired.
+    fn remove_expired(data: &mut Keyspace, k: &str) {
+        if data.get(k).is_some_and(|e| e.is_expired()) {
+            data.remove(k);
+        }
+    }
+
+    /// Removes the expired keys among the next `count` keys of the keyspace, so that keys which
+    /// are never accessed again are freed too. The keys are visited in the same order as SCAN,
+    /// and successive calls walk the whole keyspace by passing the returned cursor to the next one.
+    ///
+    /// # Arguments
+    ///
+    /// * `cursor` - The cursor returned by the previous call, or 0 to start from the first key.
+    ///
+    /// * `count` - The number of keys visited by the call.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((u64, usize, usize))` - The cursor for the next call, 0 once the whole keyspace has
+    ///   been visited, the number of keys visited and the number of keys removed.
+    /// * `Err(DBError)` - if the DB could not be written.
+    pub fn expire_cycle(&self, cursor: u64, count: usize) -> Result<(u64, usize, usize), DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let (next, keys) = data.scan(cursor, count);
+        let visited = keys.len();
+        let expired: Vec<String> = keys
+            .into_iter()
+            .filter(|k| data.get(k).is_some_and(|e| e.is_expired()))
+            .cloned()
+            .collect();
+        for k in &expired {
+            data.remove(k);
+        }
+
+        Ok((next, visited, expired.len()))
+    }
+
+    /// Returns a page of the keys of the DB, for iterating the keyspace with a cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `cursor` - The cursor returned by the previous call, or 0 to start a new iteration.
+    ///
+    /// * `pattern` - If set, only the keys matching this glob-style pattern are returned.
+    ///
+    /// * `count` - The number of keys visited by the call, before they are filtered by `pattern`
+    ///   and `type_name`.
+    ///
+    /// * `type_name` - If set, only the keys whose value has this type, as named by Redis
+    ///   ("string", "list", "hash", "set" or "zset"), are returned. An unknown type matches no key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((u64, Vec<String>))` - The cursor for the next call, 0 once the iteration is
+    ///   complete, and the keys of the page.
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn scan(
+        &self,
+        cursor: u64,
+        pattern: Option<&str>,
+        count: usize,
+        type_name: Option<&str>,
+    ) -> Result<(u64, Vec<String>), DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        // The keyspace keeps its keys in scan order, so only the keys of the page are visited.
+        let (next, keys) = data.scan(cursor, count);
+        let page = keys
+            .into_iter()
+            .filter(|k| pattern.is_none_or(|p| glob::glob_match(p, k)))
+            .filter_map(|k| data.get(k).map(|entry| (k, entry)))
+            .filter(|(_, entry)| !entry.is_expired())
+            .filter(|(_, entry)| type_name.is_none_or(|name| name == entry.value.type_name()))
+            .map(|(k, _)| k.to_string())
+            .collect();
+        Ok((next, page))
+    }
+
+    /// Returns a page of the items of a collection, for the HSCAN, SSCAN and ZSCAN commands.
+    ///
+    /// Items are visited in the order of `keyspace::scan_position`, and the cursor is the position
+    /// of the first item of the next page. Positions don't change as other items are added or
+    /// removed, so an iteration returns every item present for its whole duration. The page is
+    /// selected in a single pass over the items, keeping only the `count` lowest positions past
+    /// the cursor, so the collection is neither copied nor sorted. The pattern is applied after
+    /// the page is taken, like in Redis, so a page can be empty even though the iteration isn't
+    /// complete.
+    fn scan_page<'a, T>(
+        items: impl Iterator<Item = (&'a String, T)>,
+        cursor: u64,
+        pattern: Option<&str>,
+        count: usize,
+    ) -> (u64, Vec<(String, T)>) {
+        // One item more than the page is kept, to know where the next page starts.
+        let mut page: BTreeMap<(u64, &String), T> = BTreeMap::new();
+        for (key, value) in items {
+            let position = keyspace::scan_position(key);
+            if position < cursor {
+                continue;
+            }
+            page.insert((position, key), value);
+            if page.len() > count.saturating_add(1) {
+                page.pop_last();
+            }
+        }
+
+        let next = if page.len() > count {
+            page.pop_last().map_or(0, |((position, _), _)| position)
+        } else {
+            0
+        };
+        let page = page
+            .into_iter()
+            .filter(|((_, key), _)| pattern.is_none_or(|p| glob::glob_match(p, key)))
+            .map(|((_, key), value)| (key.to_string(), value))
+            .collect();
+        (next, page)
+    }
+
+    /// Removes the entry stored against the key if it has expired, taking the write lock.
+    /// The expiry time is checked again under the write lock, since the key may have been
+    /// set again since it was found expired.
+    fn purge_expired(&self, k: &str) -> Result<(), DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, k);
+        Ok(())
+    }
+
+    /// Moves the entry stored against the source key to the destination key, waking up
+    /// any clients blocked on the destination key if a list was moved.
+    fn move_entry(&self, data: &mut Keyspace, src: &str, dst: &str) {
+        if let Some(entry) = data.remove(src) {
+            let is_list = matches!(entry.value, Value::List(_));
+            data.insert(dst.to_string(), entry);
+            if is_list {
+                self.notify_list_waiters(dst);
+            }
+        }
+    }
+
+    /// Returns the current Unix time in milliseconds.
+    fn unix_time_ms() -> i64 {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(now) => now.as_millis() as i64,
+            Err(_) => 0,
+        }
+    }
+
+    /// Locks the notifiers of the blocked clients. The map is never left half-updated, so a
+    /// lock poisoned by a panicking connection task can still be used.
+    fn lock_list_notifiers(&self) -> MutexGuard<'_, HashMap<String, Arc<Notify>>> {
+        self.list_notifiers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the notifier for the given key, creating it if no client is waiting on the key yet.
+    fn list_notifier(&self, k: &str) -> Arc<Notify> {
+        let mut notifiers = self.lock_list_notifiers();
+        notifiers.entry(k.to_string()).or_default().clone()
+    }
+
+    /// Drops the notifier for the given key, if no other client is waiting on the key.
+    fn release_list_notifier(&self, k: &str) {
+        let mut notifiers = self.lock_list_notifiers();
+        if let Some(n) = notifiers.get(k) {
+            if Arc::strong_count(n) == 1 {
+                notifiers.remove(k);
+            }
+        }
+    }
+
+    /// Wakes up all the clients blocked on the list stored at the given key.
+    fn notify_list_waiters(&self, k: &str) {
+        let notifiers = self.lock_list_notifiers();
+        if let Some(n) = notifiers.get(k) {
+            n.notify_waiters();
+        }
+    }
+
+    /// Copy the value stored against the source key to the destination key.
+    /// The value is deep-cloned, so later changes to one key don't affect the other.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The key from which the value is copied.
+    ///
+    /// * `dst` - The key to which the value is copied.
+    ///
+    /// * `replace` - Whether an existing value against the destination key should be overwritten.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - If the value was copied.
+    /// * `Ok(false)` - If the source key doesn't exist, or the destination key exists and `replace` is false.
+    /// * `Err(DBError)` - if the DB could not be written.
+    pub fn copy(&self, src: &str, dst: &str, replace: bool) -> Result<bool, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, src);
+        Self::remove_expired(&mut data, dst);
+        let entry = match data.get(src) {
+            Some(entry) => entry.clone(),
+            None => return Ok(false),
+        };
+
+        if !replace && data.contains_key(dst) {
+            return Ok(false);
+        }
+
+        let is_list = matches!(entry.value, Value::List(_));
+        entry.touch();
+        data.insert(dst.to_string(), entry);
+        if is_list {
+            self.notify_list_waiters(dst);
+        }
+
+        Ok(true)
+    }
+
+    /// Rename the source key to the destination key, overwriting any value stored against
+    /// the destination key. The expiry time of the source key is carried over.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The key to be renamed.
+    ///
+    /// * `dst` - The new name of the key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the key was renamed.
+    /// * `Err(DBError)` - if the source key doesn't exist, or the DB could not be written.
+    pub fn rename(&self, src: &str, dst: &str) -> Result<(), DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, src);
+        Self::remove_expired(&mut data, dst);
+        if !data.contains_key(src) {
+            return Err(DBError::Other(String::from("ERR no such key")));
+        }
+
+        self.move_entry(&mut data, src, dst);
+
+        Ok(())
+    }
+
+    /// Rename the source key to the destination key, only if the destination key doesn't exist.
+    /// The check and the rename happen under a single lock, so a concurrent write to the
+    /// destination key can't be overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The key to be renamed.
+    ///
+    /// * `dst` - The new name of the key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - If the key was renamed.
+    /// * `Ok(false)` - If the destination key already exists.
+    /// * `Err(DBError)` - if the source key doesn't exist, or the DB could not be written.
+    pub fn renamenx(&self, src: &str, dst: &str) -> Result<bool, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, src);
+        Self::remove_expired(&mut data, dst);
+        if !data.contains_key(src) {
+            return Err(DBError::Other(String::from("ERR no such key")));
+        }
+
+        if data.contains_key(dst) {
+            return Ok(false);
+        }
+
+        self.move_entry(&mut data, src, dst);
+
+        Ok(true)
+    }
+
+    /// Serialize the value stored against a key, for the DUMP command.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key whose value is serialized.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<String>)` - `Some(String)` with the serialized value if key is found in DB, else `None`
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn dump(&self, k: &str) -> Result<Option<String>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Ok(Self::lookup(&data, k).map(|entry| dump::serialize(&entry.value)))
+    }
+
+    /// Store a value deserialized from a DUMP payload against a key, for the RESTORE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which the value is stored.
+    ///
+    /// * `v` - The value to be stored.
+    ///
+    /// * `ttl` - The time after which the key expires. `None` if the key doesn't expire.
+    ///
+    /// * `replace` - Whether an existing value against the key should be overwritten.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the value was stored.
+    /// * `Err(DBError)` - if the key exists and `replace` is false, or the DB could not be written.
+    pub fn restore(&self, k: &str, v: Value, ttl: Option<Duration>, replace: bool) -> Result<(), DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, k);
+        if !replace && data.contains_key(k) {
+            return Err(DBError::Other(String::from("BUSYKEY Target key name already exists.")));
+        }
+
+        let is_list = matches!(v, Value::List(_));
+        // a restored hash is a new hash, so it follows the `--hash-ordered` mode
+        let v = match v {
+            Value::Hash(h) => Value::Hash(h.into_ordered(self.hash_ordered)),
+            // a restored list is stored like a list built by pushing its elements
+            Value::List(l) => Value::List(l.reencode(self.list_max_listpack_size)),
+            v => v,
+        };
+        let mut entry = Entry::new(v);
+        // a delay too large to be represented is treated as never expiring
+        entry.expires_at = ttl.and_then(|ttl| Instant::now().checked_add(ttl));
+        data.insert(k.to_string(), entry);
+        if is_list {
+            self.notify_list_waiters(k);
+        }
+
+        Ok(())
+    }
+
+    /// Set the expiry time of a key to an absolute Unix timestamp.
+    /// If the timestamp is already in the past, the key is deleted immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which expiry is set.
+    ///
+    /// * `unix_ms` - The Unix time, in milliseconds, at which the key expires.
+    ///
+    /// * `condition` - The conditions under which the expiry time is updated.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - If the expiry was set (or the key was deleted).
+    /// * `Ok(false)` - If the key doesn't exist, or the conditions weren't met.
+    /// * `Err(DBError)` - if the DB could not be written.
+    pub fn expire_at(&self, k: &str, unix_ms: i64, condition: ExpireCondition) -> Result<bool, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Self::remove_expired(&mut data, k);
+        let entry = match data.get_mut(k) {
+            Some(entry) => entry,
+            None => return Ok(false),
+        };
+        entry.touch();
+
+        // `expires_at` is a monotonic `Instant`, so convert the wall-clock deadline into
+        // a delay from now.
+        let now = Instant::now();
+        let now_ms = Self::unix_time_ms();
+        let current_ms = entry
+            .expires_at
+            .map(|t| now_ms.saturating_add(t.saturating_duration_since(now).as_millis() as i64));
+        if !condition.allows(current_ms, unix_ms) {
+            return Ok(false);
+        }
+
+        if unix_ms <= now_ms {
+            data.remove(k);
+        } else {
+            // a delay too large to be represented is treated as never expiring
+            let delay = Duration::from_millis((unix_ms - now_ms) as u64);
+            entry.expires_at = now.checked_add(delay);
+        }
+
+        Ok(true)
+    }
+
+    /// Set the expiry time of a key to the given number of milliseconds from now.
+    /// If the time to live isn't positive, the key is deleted immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which expiry is set.
+    ///
+    /// * `ttl_ms` - The time to live of the key, in milliseconds.
+    ///
+    /// * `condition` - The conditions under which the expiry time is updated.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - If the expiry was set (or the key was deleted).
+    /// * `Ok(false)` - If the key doesn't exist, or the conditions weren't met.
+    /// * `Err(DBError)` - if the DB could not be written.
+    pub fn expire(&self, k: &str, ttl_ms: i64, condition: ExpireCondition) -> Result<bool, DBError> {
+        self.expire_at(k, Self::unix_time_ms().saturating_add(ttl_ms), condition)
+    }
+
+    /// Returns the absolute Unix time at which a key expires. Looking up the expiry time
+    /// doesn't count as an access.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which lookup is performed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<Option<i64>>)` - `None` if the key is not found in DB, `Some(None)` if the key
+    ///   doesn't expire, else `Some(Some(i64))` with the Unix time in milliseconds at which it expires.
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn expire_time(&self, k: &str) -> Result<Option<Option<i64>>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        // `expires_at` is a monotonic `Instant`, so convert the delay from now into
+        // a wall-clock time. Sub-millisecond precision is kept until the end, so the
+        // deadline given to EXPIREAT / PEXPIREAT is returned unchanged.
+        Ok(data.get(k).filter(|e| !e.is_expired()).map(|entry| {
+            entry.expires_at.map(|t| {
+                let delay = t.saturating_duration_since(Instant::now());
+                match SystemTime::now().duration_since(UNIX_EPOCH) {
+                    Ok(now) => now.saturating_add(delay).as_millis() as i64,
+                    Err(_) => delay.as_millis() as i64,
+                }
+            })
+        }))
+    }
+
+    /// Returns the name of the internal representation of the value stored against a key,
+    /// as reported by the OBJECT ENCODING command.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which lookup is performed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<&str>)` - `Some(&str)` with the encoding name if key is found in DB, else `None`
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn object_encoding(&self, k: &str) -> Result<Option<&'static str>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Ok(data
+            .get(k)
+            .filter(|e| !e.is_expired())
+            .map(|entry| entry.value.encoding()))
+    }
+
+    /// Returns the time elapsed since the key was last read or written, as reported by the
+    /// OBJECT IDLETIME command. Looking up the idle time doesn't count as an access.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which lookup is performed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<Duration>)` - `Some(Duration)` with the idle time if key is found in DB, else `None`
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn object_idletime(&self, k: &str) -> Result<Option<Duration>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Ok(data
+            .get(k)
+            .filter(|e| !e.is_expired())
+            .map(|entry| entry.idle_time()))
+    }
+
+    /// Record an access to each of the given keys, without reading or changing their values.
+    /// This resets the idle time reported by OBJECT IDLETIME.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys to be touched.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of keys which exist. Keys given more than once are counted each time.
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn touch(&self, keys: &[String]) -> Result<usize, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Ok(keys
+            .iter()
+            .filter(|k| Self::lookup(&data, k).is_some())
+            .count())
+    }
+
+    /// Returns internal details of a key, as reported by the DEBUG OBJECT command.
+    /// Looking up the details doesn't count as an access.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which lookup is performed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<ObjectDetails>)` - `Some(ObjectDetails)` if key is found in DB, else `None`
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn debug_object(&self, k: &str) -> Result<Option<ObjectDetails>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Ok(data
+            .get(k)
+            .filter(|e| !e.is_expired())
+            .map(|entry| ObjectDetails {
+                encoding: entry.value.encoding(),
+                // the payload is hex encoded, so each byte takes two characters
+                serialized_length: dump::serialize(&entry.value).len() / 2,
+                idle_time: entry.idle_time(),
+                ttl: entry
+                    .expires_at
+                    .map(|t| t.saturating_duration_since(Instant::now())),
+                elements: match &entry.value {
+                    Value::String(_) => None,
+                    v => Some(v.element_count()),
+                },
+                quicklist_listpack_max: match &entry.value {
+                    Value::List(List::Quicklist(_)) => Some(self.list_max_listpack_size),
+                    _ => None,
+                },
+            }))
+    }
+
+    /// Returns the number of references to the value stored against a key, as reported by the
+    /// OBJECT REFCOUNT command. Values are never shared between keys, so this is always 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which lookup is performed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<usize>)` - `Some(1)` if key is found in DB, else `None`
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn object_refcount(&self, k: &str) -> Result<Option<usize>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Ok(data.get(k).filter(|e| !e.is_expired()).map(|_| 1))
+    }
+
+    /// Returns the access frequency counter of a key, as reported by the OBJECT FREQ command.
+    /// The key isn't counted as accessed.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(u8))` - The logarithmic access counter of the key, between 0 and 255.
+    /// * `Ok(None)` - if the key doesn't exist.
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn object_freq(&self, k: &str) -> Result<Option<u8>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Ok(data.get(k).filter(|e| !e.is_expired()).map(|e| e.lfu_counter()))
+    }
+
+    /// Returns the number of keys in the DB. Expired keys which haven't been removed yet aren't counted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of keys.
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn dbsize(&self) -> Result<usize, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Ok(data.values().filter(|e| !e.is_expired()).count())
+    }
+
+    /// Returns the key counts of the DB. Expired keys which haven't been removed yet aren't counted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(KeyspaceStats)` - The number of keys, of keys with an expiry time, and their average time to live.
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn keyspace_stats(&self) -> Result<KeyspaceStats, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let now = Instant::now();
+        let mut keys = 0;
+        let mut expires = 0;
+        let mut total_ttl_ms: u128 = 0;
+        for entry in data.values().filter(|e| !e.is_expired()) {
+            keys += 1;
+            if let Some(expires_at) = entry.expires_at {
+                expires += 1;
+                total_ttl_ms += expires_at.saturating_duration_since(now).as_millis();
+            }
+        }
+
+        let avg_ttl = match expires {
+            0 => Duration::ZERO,
+            n => Duration::from_millis((total_ttl_ms / n as u128) as u64),
+        };
+        Ok(KeyspaceStats {
+            keys,
+            expires,
+            avg_ttl,
+        })
+    }
+
+    /// Delete the given keys. Values with many elements are freed on a background thread
+    /// once they are removed from the DB, so the caller isn't held up freeing large lists or hashes.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys to be deleted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of keys which were deleted.
+    /// * `Err(DBError)` - if the DB could not be written.
+    pub fn unlink(&self, keys: &[String]) -> Result<usize, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let mut removed = 0;
+        let mut large: Vec<Entry> = vec![];
+        for k in keys {
+            Self::remove_expired(&mut data, k);
+            if let Some(entry) = data.remove(k) {
+                removed += 1;
+                if entry.value.element_count() > LAZYFREE_THRESHOLD {
+                    large.push(entry);
+                }
+            }
+        }
+        drop(data);
+
+        if !large.is_empty() {
+            tokio::task::spawn_blocking(move || drop(large));
+        }
+
+        Ok(removed)
+    }
+
+    /// Delete all the keys in the DB.
+    ///
+    /// # Arguments
+    ///
+    /// * `lazy` - Whether the deleted keys are freed on a background thread, so that flushing
+    ///   a large DB doesn't hold up the caller.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the keys were deleted.
+    /// * `Err(DBError)` - if the DB could not be written.
+    pub fn flush_all(&self, lazy: bool) -> Result<(), DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let flushed = std::mem::take(&mut *data);
+        drop(data);
+        if lazy {
+            tokio::task::spawn_blocking(move || drop(flushed));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the approximate number of bytes used to store all the keys and their values,
+    /// computed in the same way as `memory_usage`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The size in bytes.
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn used_memory(&self) -> Result<usize, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Ok(data
+            .iter()
+            .filter(|(_, e)| !e.is_expired())
+            .map(|(k, e)| size_of::<Entry>() + k.len() + e.value.approx_size())
+            .sum())
+    }
+
+    /// Returns the approximate number of bytes used to store a key and its value,
+    /// as reported by the MEMORY USAGE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which lookup is performed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<usize>)` - `Some(usize)` with the size in bytes if key is found in DB, else `None`
+    /// * `Err(DBError)` - if the DB could not be read.
+    pub fn memory_usage(&self, k: &str) -> Result<Option<usize>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        Ok(data
+            .get(k)
+            .filter(|e| !e.is_expired())
+            .map(|entry| size_of::<Entry>() + k.len() + entry.value.approx_size()))
+    }
+
+    /// Returns the specified number of elements of the list stored at key, based on the start and stop indices.
+    /// These offsets can also be negative numbers indicating offsets starting at the end of the list.
+    /// For example, -1 is the last element of the list, -2 the penultimate, and so on.
+    /// Please note that the item at stop index is also included in the result.
+    ///
+    /// If the specified key is not found, an empty list is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which list is stored.
+    ///
+    /// * `start_idx` - The start index.
+    ///
+    /// * `stop_idx` - The end index.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - If values are retrieved successfully from the list.
+    /// * `Err(DBError)` - if key already exists and has non-list data.
+    pub fn lrange(&self, k: String, start_idx: i64, stop_idx: i64) -> Result<Vec<String>, DBError> {
+        self.with_lrange(&k, start_idx, stop_idx, |elems| elems.cloned().collect())
+    }
+
+    /// Same as `lrange`, but instead of copying the elements, passes an iterator over them
+    /// to the given closure, which is called while the read lock is held. This lets large
+    /// ranges be encoded straight into a response without cloning the whole range.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which list is stored.
+    ///
+    /// * `start_idx` - Start index. Negative indices count from the end of the list.
+    ///
+    /// * `stop_idx` - Stop index, included in the range. Negative indices count from the end of the list.
+    ///
+    /// * `f` - Called with an iterator over the elements of the range, which is empty if the
+    ///   key doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(R)` - The value returned by the closure.
+    /// * `Err(DBError)` - if key already exists and has non-list data.
+    pub fn with_lrange<R>(
+        &self,
+        k: &str,
+        start_idx: i64,
+        stop_idx: i64,
+        f: impl FnOnce(list::Iter<'_>) -> R,
+    ) -> Result<R, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let empty = List::Listpack(vec![]);
+        let l = match Self::lookup(&data, k) {
+            Some(entry) => match &entry.value {
+                Value::List(l) => l,
+                _ => return Err(DBError::WrongType),
+            },
+            None => &empty,
+        };
+
+        let (rounded_start_idx, rounded_stop_idx) =
+            Self::round_list_indices(l.len() as i64, start_idx, stop_idx);
+        Ok(f(l.range(rounded_start_idx..rounded_stop_idx)))
+    }
+
+    /// Find the indices of the elements equal to the given value in the list stored against a key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which list is stored.
+    ///
+    /// * `v` - The value to be searched for.
+    ///
+    /// * `rank` - Which match to start from. 1 is the first match from the head, 2 the second
+    ///   and so on. Negative ranks search from the tail instead, -1 being the last match. Must not be 0.
+    ///
+    /// * `count` - The maximum number of indices to return, 0 meaning all the matches.
+    ///   `None` returns only the first match.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<usize>)` - The indices of the matches, in the order they were found. Empty if the
+    ///   key is not found or no element matches.
+    /// * `Err(DBError)` - if key already exists and has non-list data.
+    pub fn lpos(&self, k: &str, v: &str, rank: i64, count: Option<usize>) -> Result<Vec<usize>, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entry = match Self::lookup(&data, k) {
+            Some(entry) => entry,
+            None => return Ok(vec![]),
+        };
+
+        let l = match &entry.value {
+            Value::List(l) => l,
+            _ => return Err(DBError::WrongType),
+        };
+
+        let limit = match count {
+            Some(0) => usize::MAX,
+            Some(n) => n,
+            None => 1,
+        };
+        let skip = (rank.unsigned_abs() - 1) as usize;
+        let matches = l.iter().enumerate().filter(|(_, e)| *e == v).map(|(i, _)| i);
+
+        let indices = if rank > 0 {
+            matches.skip(skip).take(limit).collect()
+        } else {
+            matches.rev().skip(skip).take(limit).collect()
+        };
+
+        Ok(indices)
+    }
+
+    /// Convert the start and stop indices of a range into positions in a list and return them as
+    /// a tuple, with the stop position excluded so that it can be used as a Rust range.
+    /// Negative indices count from the end of the list, -1 being the last element.
+    ///
+    /// Like in Redis, a start index before the head is rounded to the head, and a stop index
+    /// after the tail is rounded to the tail. Indices can be anywhere in the `i64` range, so
+    /// they are only ever moved towards zero, which can't overflow.
+    /// Special condition: If the range doesn't cover any element of the list, return (0, 0).
+    fn round_list_indices(list_len: i64, start_idx: i64, stop_idx: i64) -> (usize, usize) {
+        // Adding the length to a negative index can't overflow.
+        let start = if start_idx < 0 { start_idx + list_len } else { start_idx };
+        let stop = if stop_idx < 0 { stop_idx + list_len } else { stop_idx };
+
+        let start = start.max(0);
+        if start > stop || start >= list_len {
+            return (0, 0);
+        }
+        let stop = stop.min(list_len - 1);
+        (start as usize, stop as usize + 1) // Rust range is exclusive, so add 1
+    }
+}
+
+impl Entry {
+    pub fn new(value: Value) -> Entry {
+        Entry {
+            value,
+            expires_at: None,
+            last_access: AtomicU64::new(clock_ms()),
+            lfu: AtomicU64::new(lfu_pack(clock_ms() / 60_000, LFU_INIT_VAL)),
+        }
+    }
+
+    /// Record that the key was just read or written.
+    pub fn touch(&self) {
+        let now = clock_ms();
+        self.last_access.store(now, Ordering::Relaxed);
+
+        // Same as Redis: the counter is first decayed, then incremented with a probability
+        // which gets lower as it grows, so that it measures the order of magnitude of the accesses.
+        let mut counter = self.lfu_counter();
+        if counter < u8::MAX {
+            let base = counter.saturating_sub(LFU_INIT_VAL) as f64;
+            if rand::random::<f64>() < 1.0 / (base * LFU_LOG_FACTOR + 1.0) {
+                counter += 1;
+            }
+        }
+        self.lfu
+            .store(lfu_pack(now / 60_000, counter), Ordering::Relaxed);
+    }
+
+    /// Returns the access frequency counter of the key, decremented once for every
+    /// `LFU_DECAY_TIME` minutes elapsed since it was last updated.
+    pub fn lfu_counter(&self) -> u8 {
+        lfu_decayed(self.lfu.load(Ordering::Relaxed), clock_ms() / 60_000)
+    }
+
+    /// Returns the time elapsed since the key was last read or written.
+    pub fn idle_time(&self) -> Duration {
+        let last_access = self.last_access.load(Ordering::Relaxed);
+        Duration::from_millis(clock_ms().saturating_sub(last_access))
+    }
+
+    /// Returns `true` if the entry has an expiry time which has already passed, or if it is
+    /// a hash whose fields have all expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|t| t <= Instant::now())
+            || matches!(&self.value, Value::Hash(h) if h.all_fields_expired())
+    }
+}
+
+impl Clone for Entry {
+    fn clone(&self) -> Entry {
+        Entry {
+            value: self.value.clone(),
+            expires_at: self.expires_at,
+            last_access: AtomicU64::new(self.last_access.load(Ordering::Relaxed)),
+            lfu: AtomicU64::new(self.lfu.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Packs the time in minutes at which an access frequency counter was updated with its value.
+fn lfu_pack(minutes: u64, counter: u8) -> u64 {
+    (minutes << 8) | counter as u64
+}
+
+/// Returns the value of a packed access frequency counter at the given time in minutes, once
+/// decremented for every `LFU_DECAY_TIME` minutes elapsed since it was updated.
+fn lfu_decayed(lfu: u64, now_minutes: u64) -> u8 {
+    let (counter, updated_minutes) = ((lfu & 0xff) as u8, lfu >> 8);
+    let periods = now_minutes.saturating_sub(updated_minutes) / LFU_DECAY_TIME;
+    counter.saturating_sub(periods.min(u8::MAX as u64) as u8)
+}
+
+/// Returns the number of milliseconds elapsed since the first time this function was called.
+/// A monotonic clock used to track the access times of the keys.
+fn clock_ms() -> u64 {
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_millis() as u64
+}
+
+impl Value {
+    /// Returns the approximate number of bytes used by the value, outside of the `Value` itself.
+    ///
+    /// This is the length of each of the strings stored in the value, plus a fixed overhead for
+    /// each element of a list, hash, set or sorted set. Allocator overhead and spare capacity
+    /// of the collections aren't counted.
+    pub fn approx_size(&self) -> usize {
+        match self {
+            Value::String(s) => s.len(),
+            Value::List(l) => l.iter().map(|e| size_of::<String>() + e.len()).sum(),
+            Value::Hash(h) => h
+                .iter()
+                .map(|(f, v)| 2 * size_of::<String>() + f.len() + v.len())
+                .sum(),
+            Value::Set(set) => set.iter().map(|m| size_of::<String>() + m.len()).sum(),
+            // members of a sorted set are stored twice, once in the lookup map and once in the score index
+            Value::SortedSet(zset) => zset
+                .iter()
+                .map(|(m, _)| 2 * (size_of::<String>() + size_of::<f64>() + m.len()))
+                .sum(),
+        }
+    }
+
+    /// Returns the name of the type of the value, matching the names used by Redis.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::List(_) => "list",
+            Value::Hash(_) => "hash",
+            Value::Set(_) => "set",
+            Value::SortedSet(_) => "zset",
+        }
+    }
+
+    /// Returns the number of elements in the value: 1 for a string, else the number of
+    /// elements of the list, hash, set or sorted set.
+    fn element_count(&self) -> usize {
+        match self {
+            Value::String(_) => 1,
+            Value::List(l) => l.len(),
+            Value::Hash(h) => h.len(),
+            Value::Set(set) => set.len(),
+            Value::SortedSet(zset) => zset.len(),
+        }
+    }
+
+    /// Returns the name of the encoding used for this value, matching the names used by Redis.
+    ///
+    /// - Strings are "int" when they can be parsed as a 64-bit integer, else "raw".
+    /// - Lists are "listpack" or "quicklist", depending on how they are currently stored.
+    /// - Hashes are "listpack" when they are small, else "hashtable".
+    /// - Sets are "intset" when they are small and only contain integers, "listpack" when they
+    ///   are small, else "hashtable".
+    /// - Sorted sets are "listpack" when they are small, else "skiplist".
+    pub fn encoding(&self) -> &'static str {
+        match self {
+            Value::String(s) => {
+                if s.parse::<i64>().is_ok() {
+                    "int"
+                } else {
+                    "raw"
+                }
+            }
+            Value::List(l) => l.encoding(),
+            Value::Hash(h) => {
+                if h.len() <= HASH_MAX_LISTPACK_ENTRIES
+                    && h.iter().all(|(f, v)| {
+                        f.len() <= HASH_MAX_LISTPACK_VALUE && v.len() <= HASH_MAX_LISTPACK_VALUE
+                    })
+                {
+                    "listpack"
+                } else {
+                    "hashtable"
+                }
+            }
+            Value::Set(set) => {
+                if set.len() <= SET_MAX_INTSET_ENTRIES
+                    && set.iter().all(|m| m.parse::<i64>().is_ok())
+                {
+                    "intset"
+                } else if set.len() <= SET_MAX_LISTPACK_ENTRIES
+                    && set.iter().all(|m| m.len() <= SET_MAX_LISTPACK_VALUE)
+                {
+                    "listpack"
+                } else {
+                    "hashtable"
+                }
+            }
+            Value::SortedSet(zset) => {
+                if zset.len() <= ZSET_MAX_LISTPACK_ENTRIES
+                    && zset.iter().all(|(m, _)| m.len() <= ZSET_MAX_LISTPACK_VALUE)
+                {
+                    "listpack"
+                } else {
+                    "skiplist"
+                }
+            }
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    /// Concurrent appends to the same key must not lose each other's writes, and must keep
+    /// the expiry time of the key.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_appends_are_not_lost() {
+        const TASKS: usize = 32;
+        const APPENDS_PER_TASK: usize = 200;
+
+        let db = Arc::new(DB::new());
+        db.set(
+            String::from("k"),
+            Value::String(String::new()),
+            Some(Duration::from_secs(60)),
+        )
+        .unwrap();
+
+        let tasks: Vec<_> = (0..TASKS)
+            .map(|i| {
+                let db = Arc::clone(&db);
+                tokio::spawn(async move {
+                    // tasks append values of different lengths, so a lost write changes the total
+                    let value = "x".repeat(i % 7 + 1);
+                    for _ in 0..APPENDS_PER_TASK {
+                        db.append(String::from("k"), &value).unwrap();
+                    }
+                    value.len() * APPENDS_PER_TASK
+                })
+            })
+            .collect();
+
+        let mut expected_len = 0;
+        for task in tasks {
+            expected_len += task.await.unwrap();
+        }
+
+        assert_eq!(db.get("k").unwrap().unwrap().len(), expected_len);
+        assert!(matches!(db.expire_time("k").unwrap(), Some(Some(_))));
+    }
+
+    /// Returns the keys returned by a whole SCAN iteration, calling `between_pages` after
+    /// each page.
+    fn scan_all(db: &DB, count: usize, mut between_pages: impl FnMut(usize)) -> Vec<String> {
+        let mut keys = Vec::new();
+        let mut cursor = 0;
+        let mut pages = 0;
+        loop {
+            let (next, page) = db.scan(cursor, None, count, None).unwrap();
+            assert!(page.len() <= count);
+            keys.extend(page);
+            if next == 0 {
+                return keys;
+            }
+            assert!(next > cursor, "the cursor must move forward");
+            cursor = next;
+            pages += 1;
+            between_pages(pages);
+        }
+    }
+
+    #[test]
+    fn scan_returns_every_key_once() {
+        let db = DB::new();
+        for i in 0..500 {
+            db.set(format!("key:{}", i), Value::String(String::new()), None)
+                .unwrap();
+        }
+
+        let mut keys = scan_all(&db, 7, |_| {});
+        keys.sort();
+        let mut expected: Vec<_> = (0..500).map(|i| format!("key:{}", i)).collect();
+        expected.sort();
+        assert_eq!(keys, expected);
+    }
+
+    /// Keys which are present for the whole iteration must be returned, even if other keys
+    /// are added and removed while it runs.
+    #[test]
+    fn scan_returns_stable_keys_despite_modifications() {
+        let db = DB::new();
+        for i in 0..300 {
+            db.set(format!("stable:{}", i), Value::String(String::new()), None)
+                .unwrap();
+            db.set(format!("removed:{}", i), Value::String(String::new()), None)
+                .unwrap();
+        }
+
+        let keys = scan_all(&db, 10, |page| {
+            db.unlink(&(0..300).map(|i| format!("removed:{}", i)).collect::<Vec<_>>())
+                .unwrap();
+            for i in 0..20 {
+                db.set(format!("added:{}:{}", page, i), Value::String(String::new()), None)
+                    .unwrap();
+            }
+        });
+
+        let keys: HashSet<_> = keys.into_iter().collect();
+        for i in 0..300 {
+            assert!(keys.contains(&format!("stable:{}", i)), "stable:{} missing", i);
+        }
+    }
+
+    #[test]
+    fn hscan_returns_every_field_once() {
+        let db = DB::new();
+        let fields = (0..200).map(|i| (format!("f{}", i), i.to_string())).collect();
+        db.hset(String::from("h"), fields).unwrap();
+
+        let mut seen = Vec::new();
+        let mut cursor = 0;
+        loop {
+            let (next, page) = db.hscan("h", cursor, None, 9).unwrap();
+            assert!(page.len() <= 9);
+            seen.extend(page);
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        seen.sort();
+        let mut expected: Vec<_> = (0..200).map(|i| (format!("f{}", i), i.to_string())).collect();
+        expected.sort();
+        assert_eq!(seen, expected);
+    }
+
+    /// Successive expiry cycles walk the whole keyspace, and only remove the expired keys.
+    #[test]
+    fn expire_cycles_remove_expired_keys() {
+        let db = DB::new();
+        for i in 0..100 {
+            let ttl = if i % 2 == 0 { Duration::ZERO } else { Duration::from_secs(60) };
+            db.set(format!("key:{}", i), Value::String(String::new()), Some(ttl))
+                .unwrap();
+        }
+
+        let (mut cursor, mut visited, mut removed) = (0, 0, 0);
+        loop {
+            let (next, v, r) = db.expire_cycle(cursor, 7).unwrap();
+            visited += v;
+            removed += r;
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        assert_eq!((visited, removed), (100, 50));
+        assert_eq!(db.data.read().unwrap().len(), 50);
+        assert_eq!(db.dbsize().unwrap(), 50);
+    }
+
+    #[test]
+    fn lfu_counter_grows_with_accesses() {
+        let entry = Entry::new(Value::String(String::new()));
+        assert_eq!(entry.lfu_counter(), LFU_INIT_VAL);
+
+        for _ in 0..10_000 {
+            entry.touch();
+        }
+        // the increments get less likely as the counter grows, like in Redis
+        let counter = entry.lfu_counter();
+        assert!(counter > LFU_INIT_VAL + 5 && counter < 100, "{}", counter);
+    }
+
+    #[test]
+    fn lfu_counter_decays_over_time() {
+        let lfu = lfu_pack(10, 20);
+        assert_eq!(lfu_decayed(lfu, 10), 20);
+        assert_eq!(lfu_decayed(lfu, 13), 17);
+        assert_eq!(lfu_decayed(lfu, 1000), 0);
+        // a clock going backwards doesn't increase the counter
+        assert_eq!(lfu_decayed(lfu, 5), 20);
+    }
+
+    #[test]
+    fn round_list_indices_handles_extreme_indices() {
+        let (min, max) = (i64::MIN, i64::MAX);
+        assert_eq!(DB::round_list_indices(5, min, max), (0, 5));
+        assert_eq!(DB::round_list_indices(5, min, min), (0, 0));
+        assert_eq!(DB::round_list_indices(5, max, max), (0, 0));
+        assert_eq!(DB::round_list_indices(5, max, min), (0, 0));
+        assert_eq!(DB::round_list_indices(5, min, 0), (0, 1));
+        assert_eq!(DB::round_list_indices(5, -2, max), (3, 5));
+        assert_eq!(DB::round_list_indices(5, 0, -100), (0, 0));
+        assert_eq!(DB::round_list_indices(0, min, max), (0, 0));
+    }
+
+    #[test]
+    fn lrange_with_extreme_indices() {
+        let db = DB::new();
+        let elems: Vec<String> = ["a", "b", "c"].iter().map(|e| e.to_string()).collect();
+        db.rpush(String::from("l"), elems.clone()).unwrap();
+
+        let lrange = |start, stop| db.lrange(String::from("l"), start, stop).unwrap();
+        assert_eq!(lrange(i64::MIN, i64::MAX), elems);
+        assert_eq!(lrange(i64::MIN, 0), ["a"]);
+        assert!(lrange(i64::MIN, i64::MIN).is_empty());
+        assert!(lrange(i64::MAX, i64::MAX).is_empty());
+        assert_eq!(lrange(-1, i64::MAX), ["c"]);
+    }
+
+    /// GET never returns a value past its expiry time, and removes the expired entry.
+    #[test]
+    fn get_removes_expired_keys() {
+        let db = DB::new();
+        db.set(String::from("k"), Value::String(String::from("v")), Some(Duration::ZERO))
+            .unwrap();
+        assert_eq!(db.get("k").unwrap(), None);
+        assert_eq!(db.data.read().unwrap().len(), 0);
+
+        db.set(
+            String::from("k"),
+            Value::String(String::from("v")),
+            Some(Duration::from_millis(1)),
+        )
+        .unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(db.get("k").unwrap(), None);
+        assert_eq!(db.data.read().unwrap().len(), 0);
+
+        // the key can be set again afterwards
+        db.set(String::from("k"), Value::String(String::from("new")), None)
+            .unwrap();
+        assert_eq!(db.get("k").unwrap().as_deref(), Some("new"));
+    }
+
+    /// A panic while the notifiers are locked doesn't break the list commands afterwards.
+    #[tokio::test]
+    async fn list_commands_survive_a_poisoned_notifiers_lock() {
+        let db = DB::new();
+        std::thread::scope(|s| {
+            let poisoner = s.spawn(|| {
+                let _notifiers = db.list_notifiers.lock().unwrap();
+                panic!("poisoning the lock");
+            });
+            assert!(poisoner.join().is_err());
+        });
+        assert!(db.list_notifiers.is_poisoned());
+
+        assert_eq!(db.rpush(String::from("l"), vec![String::from("a")]).unwrap(), 1);
+        assert_eq!(
+            db.blocking_pop(&[String::from("l")], ListEnd::Head, None)
+                .await
+                .unwrap(),
+            Some((String::from("l"), String::from("a")))
+        );
+        assert!(db.lock_list_notifiers().is_empty());
+    }
+}
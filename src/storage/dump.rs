@@ -0,0 +1,202 @@
+// src/storage/dump.rs
+
+use std::collections::{HashSet, VecDeque};
+
+use super::{db::Value, hash::Hash, list::List, sorted_set::SortedSet};
+
+/// Version of the serialization format, stored in the trailer of every payload.
+const DUMP_VERSION: u16 = 1;
+
+/// Type tags identifying the kind of value at the start of a payload.
+const TYPE_STRING: u8 = 0;
+const TYPE_LIST: u8 = 1;
+const TYPE_HASH: u8 = 2;
+const TYPE_SET: u8 = 3;
+const TYPE_SORTED_SET: u8 = 4;
+
+/// Serialize a value, as returned by the DUMP command.
+///
+/// The value is encoded as a type tag followed by its elements, each string being prefixed
+/// with its length. A trailer with the format version and a CRC32 checksum of the preceding
+/// bytes is appended, and the result is hex encoded so that it can be sent as a bulk string.
+pub fn serialize(value: &Value) -> String {
+    let mut buf: Vec<u8> = vec![];
+    match value {
+        Value::String(s) => {
+            buf.push(TYPE_STRING);
+            write_str(&mut buf, s);
+        }
+        Value::List(l) => {
+            buf.push(TYPE_LIST);
+            write_len(&mut buf, l.len());
+            l.iter().for_each(|e| write_str(&mut buf, e));
+        }
+        Value::Hash(h) => {
+            // fields can expire while the hash is written, so the length is taken from the
+            // fields actually written. Expiry times of the fields aren't dumped.
+            let fields: Vec<(&String, &String)> = h.iter().collect();
+            buf.push(TYPE_HASH);
+            write_len(&mut buf, fields.len());
+            for (field, value) in fields {
+                write_str(&mut buf, field);
+                write_str(&mut buf, value);
+            }
+        }
+        Value::Set(set) => {
+            buf.push(TYPE_SET);
+            write_len(&mut buf, set.len());
+            set.iter().for_each(|m| write_str(&mut buf, m));
+        }
+        Value::SortedSet(zset) => {
+            buf.push(TYPE_SORTED_SET);
+            write_len(&mut buf, zset.len());
+            for (member, score) in zset.iter() {
+                write_str(&mut buf, member);
+                buf.extend_from_slice(&score.to_le_bytes());
+            }
+        }
+    }
+
+    buf.extend_from_slice(&DUMP_VERSION.to_le_bytes());
+    let checksum = crc32(&buf);
+    buf.extend_from_slice(&checksum.to_le_bytes());
+
+    buf.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Deserialize a payload produced by `serialize`, as given to the RESTORE command.
+///
+/// # Returns
+///
+/// `None` if the payload is malformed, was produced by another version of the format,
+/// or doesn't match its checksum.
+pub fn deserialize(payload: &str) -> Option<Value> {
+    let buf = decode_hex(payload)?;
+
+    // check the trailer: 2 bytes of version, followed by 4 bytes of checksum
+    if buf.len() < 7 {
+        return None;
+    }
+    let (body, checksum) = buf.split_at(buf.len() - 4);
+    if crc32(body).to_le_bytes() != checksum {
+        return None;
+    }
+    let (body, version) = body.split_at(body.len() - 2);
+    if version != DUMP_VERSION.to_le_bytes() {
+        return None;
+    }
+
+    let mut reader = Reader { buf: body, pos: 0 };
+    let value = match reader.read_u8()? {
+        TYPE_STRING => Value::String(reader.read_str()?),
+        TYPE_LIST => {
+            let len = reader.read_len()?;
+            let mut list = VecDeque::new();
+            for _ in 0..len {
+                list.push_back(reader.read_str()?);
+            }
+            Value::List(List::Quicklist(list))
+        }
+        TYPE_HASH => {
+            let len = reader.read_len()?;
+            let mut hash = Hash::new(false);
+            for _ in 0..len {
+                hash.insert(reader.read_str()?, reader.read_str()?);
+            }
+            Value::Hash(hash)
+        }
+        TYPE_SET => {
+            let len = reader.read_len()?;
+            let mut set = HashSet::new();
+            for _ in 0..len {
+                set.insert(reader.read_str()?);
+            }
+            Value::Set(set)
+        }
+        TYPE_SORTED_SET => {
+            let len = reader.read_len()?;
+            let mut zset = SortedSet::new();
+            for _ in 0..len {
+                let member = reader.read_str()?;
+                let score = f64::from_le_bytes(reader.read_bytes(8)?.try_into().ok()?);
+                if score.is_nan() {
+                    return None;
+                }
+                zset.insert(member, score);
+            }
+            Value::SortedSet(zset)
+        }
+        _ => return None,
+    };
+
+    // the whole body must have been consumed
+    if reader.pos != body.len() {
+        return None;
+    }
+
+    Some(value)
+}
+
+/// Appends a length as 4 little-endian bytes.
+fn write_len(buf: &mut Vec<u8>, len: usize) {
+    buf.extend_from_slice(&(len as u32).to_le_bytes());
+}
+
+/// Appends a string, prefixed with its length.
+fn write_str(buf: &mut Vec<u8>, s: &str) {
+    write_len(buf, s.len());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+/// Reads the values written by `write_len` and `write_str` from a buffer.
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn read_bytes(&mut self, n: usize) -> Option<&[u8]> {
+        let bytes = self.buf.get(self.pos..self.pos.checked_add(n)?)?;
+        self.pos += n;
+        Some(bytes)
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        Some(self.read_bytes(1)?[0])
+    }
+
+    fn read_len(&mut self) -> Option<usize> {
+        let bytes = self.read_bytes(4)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?) as usize)
+    }
+
+    fn read_str(&mut self) -> Option<String> {
+        let len = self.read_len()?;
+        String::from_utf8(self.read_bytes(len)?.to_vec()).ok()
+    }
+}
+
+/// Decodes a string of hex digits into bytes.
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Computes the CRC-32 (IEEE) checksum of the given bytes.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for b in bytes {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
@@ -0,0 +1,102 @@
+// src/storage/glob.rs
+
+/// Returns whether a string matches a Redis-style glob pattern, as used by the MATCH option
+/// of the scan commands.
+///
+/// The pattern supports:
+///
+/// * `*` - any sequence of characters, including an empty one.
+/// * `?` - any single character.
+/// * `[abc]`, `[a-z]`, `[^a]` - a character from (or, with `^`, not from) a set or a range.
+/// * `\x` - the character `x`, even if it is one of the special characters above.
+pub fn glob_match(pattern: &str, s: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let s: Vec<char> = s.chars().collect();
+    match_from(&pattern, &s)
+}
+
+fn match_from(pattern: &[char], s: &[char]) -> bool {
+    let (mut p, mut i) = (0, 0);
+    while p < pattern.len() {
+        match pattern[p] {
+            '*' => {
+                // Consecutive stars match the same as a single one.
+                while p + 1 < pattern.len() && pattern[p + 1] == '*' {
+                    p += 1;
+                }
+                if p + 1 == pattern.len() {
+                    return true;
+                }
+                return (i..=s.len()).any(|start| match_from(&pattern[p + 1..], &s[start..]));
+            }
+            '?' => {
+                if i == s.len() {
+                    return false;
+                }
+                i += 1;
+            }
+            '[' => {
+                if i == s.len() {
+                    return false;
+                }
+                let (matched, end) = match_class(pattern, p + 1, s[i]);
+                if !matched {
+                    return false;
+                }
+                p = end;
+                i += 1;
+            }
+            c => {
+                // An escaped character is matched literally. A trailing backslash matches itself.
+                let c = if c == '\\' && p + 1 < pattern.len() {
+                    p += 1;
+                    pattern[p]
+                } else {
+                    c
+                };
+                if i == s.len() || s[i] != c {
+                    return false;
+                }
+                i += 1;
+            }
+        }
+        p += 1;
+    }
+    i == s.len()
+}
+
+/// Matches a character against the class starting at `start`, just after its `[`.
+///
+/// # Returns
+///
+/// Whether the character is in the class, and the position of the closing `]`. An unterminated
+/// class extends to the end of the pattern, like in Redis.
+fn match_class(pattern: &[char], start: usize, c: char) -> (bool, usize) {
+    let mut p = start;
+    let negate = pattern.get(p) == Some(&'^');
+    if negate {
+        p += 1;
+    }
+
+    let mut matched = false;
+    while p < pattern.len() && pattern[p] != ']' {
+        if pattern[p] == '\\' && p + 1 < pattern.len() {
+            p += 1;
+            matched |= pattern[p] == c;
+        } else if p + 2 < pattern.len() && pattern[p + 1] == '-' && pattern[p + 2] != ']' {
+            let (lo, hi) = if pattern[p] <= pattern[p + 2] {
+                (pattern[p], pattern[p + 2])
+            } else {
+                (pattern[p + 2], pattern[p])
+            };
+            matched |= lo <= c && c <= hi;
+            p += 2;
+        } else {
+            matched |= pattern[p] == c;
+        }
+        p += 1;
+    }
+
+    // Stop at the last character of the pattern if the class isn't terminated.
+    (matched != negate, p.min(pattern.len() - 1))
+}
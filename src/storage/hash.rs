@@ -0,0 +1,248 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+
+/// The Hash struct stores the fields of a hash along with their values.
+///
+/// Fields are kept in a HashMap by default. Hashes created while the server runs with
+/// `--hash-ordered` keep them in a BTreeMap instead, so that they are iterated in sorted
+/// field order, at the cost of slower lookups.
+///
+/// Fields can be given an expiry time with HEXPIRE. Like keys, they are expired lazily:
+/// an expired field is treated as missing, and is removed the next time the hash is modified.
+#[derive(Debug, Clone)]
+pub struct Hash {
+    fields: Fields,
+    /// The time after which each field is considered deleted, for the fields which expire.
+    expires: HashMap<String, Instant>,
+    /// The latest time in `expires`, so that checking whether every field expired, which is
+    /// done on every access to the key, doesn't have to go through all of them.
+    latest_expiry: Option<Instant>,
+}
+
+/// The fields of a hash along with their values.
+#[derive(Debug, Clone)]
+enum Fields {
+    /// Fields in no particular order.
+    Unordered(HashMap<String, String>),
+    /// Fields in sorted order.
+    Ordered(BTreeMap<String, String>),
+}
+
+impl Hash {
+    /// Create a new, empty `Hash`, keeping its fields in sorted order if `ordered` is set.
+    pub fn new(ordered: bool) -> Hash {
+        let fields = if ordered {
+            Fields::Ordered(BTreeMap::new())
+        } else {
+            Fields::Unordered(HashMap::new())
+        };
+        Hash {
+            fields,
+            expires: HashMap::new(),
+            latest_expiry: None,
+        }
+    }
+
+    /// Returns the same hash, with its fields kept in sorted order if `ordered` is set.
+    pub fn into_ordered(self, ordered: bool) -> Hash {
+        let fields = match self.fields {
+            Fields::Unordered(h) if ordered => Fields::Ordered(h.into_iter().collect()),
+            Fields::Ordered(h) if !ordered => Fields::Unordered(h.into_iter().collect()),
+            h => h,
+        };
+        Hash {
+            fields,
+            expires: self.expires,
+            latest_expiry: self.latest_expiry,
+        }
+    }
+
+    /// Returns whether the given field has an expiry time which has passed.
+    fn is_expired(&self, field: &str) -> bool {
+        self.expires
+            .get(field)
+            .is_some_and(|t| *t <= Instant::now())
+    }
+
+    /// Returns whether the hash only had fields with an expiry time, and all of them expired.
+    /// The key of such a hash is considered deleted.
+    pub fn all_fields_expired(&self) -> bool {
+        // Every field has an expiry time only if there are as many as fields, and then they
+        // have all expired if the latest one has.
+        self.expires.len() == self.total_len()
+            && self.latest_expiry.is_some_and(|t| t <= Instant::now())
+    }
+
+    /// Returns the number of fields stored, including the expired ones.
+    fn total_len(&self) -> usize {
+        match &self.fields {
+            Fields::Unordered(h) => h.len(),
+            Fields::Ordered(h) => h.len(),
+        }
+    }
+
+    /// Removes the expiry time of a field, if it has one.
+    fn remove_expiry(&mut self, field: &str) {
+        if self
+            .expires
+            .remove(field)
+            .is_some_and(|t| Some(t) == self.latest_expiry)
+        {
+            self.latest_expiry = self.expires.values().max().copied();
+        }
+    }
+
+    /// Removes the fields whose expiry time has passed.
+    fn remove_expired(&mut self) {
+        let now = Instant::now();
+        let expired: Vec<String> = self
+            .expires
+            .iter()
+            .filter(|(_, t)| **t <= now)
+            .map(|(f, _)| f.clone())
+            .collect();
+        for field in expired {
+            self.remove(&field);
+        }
+    }
+
+    /// Returns the number of fields in the hash.
+    pub fn len(&self) -> usize {
+        let now = Instant::now();
+        self.total_len() - self.expires.values().filter(|t| **t <= now).count()
+    }
+
+    /// Returns whether the hash has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value of the given field, or `None` if it isn't in the hash.
+    pub fn get(&self, field: &str) -> Option<&String> {
+        if self.is_expired(field) {
+            return None;
+        }
+        match &self.fields {
+            Fields::Unordered(h) => h.get(field),
+            Fields::Ordered(h) => h.get(field),
+        }
+    }
+
+    /// Returns whether the given field is in the hash.
+    pub fn contains_key(&self, field: &str) -> bool {
+        self.get(field).is_some()
+    }
+
+    /// Set a field to the given value. Like in Redis, this removes the expiry time of the field.
+    ///
+    /// # Returns
+    ///
+    /// The previous value of the field, or `None` if the field was added.
+    pub fn insert(&mut self, field: String, value: String) -> Option<String> {
+        self.remove_expired();
+        self.remove_expiry(&field);
+        match &mut self.fields {
+            Fields::Unordered(h) => h.insert(field, value),
+            Fields::Ordered(h) => h.insert(field, value),
+        }
+    }
+
+    /// Removes a field from the hash, along with its expiry time.
+    ///
+    /// # Returns
+    ///
+    /// The value of the field, or `None` if it wasn't in the hash.
+    pub fn remove(&mut self, field: &str) -> Option<String> {
+        let expired = self.is_expired(field);
+        self.remove_expiry(field);
+        let value = match &mut self.fields {
+            Fields::Unordered(h) => h.remove(field),
+            Fields::Ordered(h) => h.remove(field),
+        };
+        value.filter(|_| !expired)
+    }
+
+    /// Returns the time left until the given field expires.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the field isn't in the hash, `Some(None)` if the field doesn't expire, else
+    /// `Some(Some(Duration))` with the time left.
+    pub fn ttl(&self, field: &str) -> Option<Option<Duration>> {
+        if !self.contains_key(field) {
+            return None;
+        }
+        Some(
+            self.expires
+                .get(field)
+                .map(|t| t.saturating_duration_since(Instant::now())),
+        )
+    }
+
+    /// Set the time after which the given field is considered deleted.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the field isn't in the hash, else `true`.
+    pub fn set_expiry(&mut self, field: &str, expires_at: Instant) -> bool {
+        self.remove_expired();
+        if !self.contains_key(field) {
+            return false;
+        }
+        self.remove_expiry(field);
+        self.expires.insert(field.to_string(), expires_at);
+        self.latest_expiry = self.latest_expiry.max(Some(expires_at));
+        true
+    }
+
+    /// Returns an iterator over the fields and their values, in sorted field order for
+    /// an ordered hash, else in no particular order.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        let now = Instant::now();
+        let live = move |(f, _): &(&String, &String)| self.expires.get(*f).is_none_or(|t| *t > now);
+        match &self.fields {
+            Fields::Unordered(h) => Box::new(h.iter().filter(live)),
+            Fields::Ordered(h) => Box::new(h.iter().filter(live)),
+        }
+    }
+
+    /// Returns an iterator over the fields, in the same order as `iter`.
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.iter().map(|(f, _)| f)
+    }
+
+    /// Returns an iterator over the values, in the same order as `iter`.
+    pub fn values(&self) -> impl Iterator<Item = &String> {
+        self.iter().map(|(_, v)| v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_fields_expired_follows_the_latest_expiry() {
+        let mut hash = Hash::new(false);
+        assert!(!hash.all_fields_expired());
+        for field in ["a", "b", "c"] {
+            hash.insert(field.to_string(), "v".to_string());
+        }
+        assert!(hash.set_expiry("a", Instant::now() + Duration::from_secs(3600)));
+        assert!(hash.set_expiry("b", Instant::now() + Duration::from_secs(7200)));
+
+        // a field without an expiry time keeps the hash alive
+        assert!(!hash.all_fields_expired());
+        assert_eq!(hash.remove("c"), Some("v".to_string()));
+        assert!(!hash.all_fields_expired());
+
+        // removing the field which expires last leaves the other one's expiry as the latest
+        assert!(hash.set_expiry("a", Instant::now()));
+        assert!(!hash.all_fields_expired());
+        assert_eq!(hash.remove("b"), Some("v".to_string()));
+        assert!(hash.all_fields_expired());
+        assert!(hash.is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    collections::{hash_map, BTreeSet, HashMap},
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use super::db::Entry;
+
+/// The Keyspace struct stores the entries of the DB against their keys.
+///
+/// Besides the map of the entries, it keeps the keys sorted by their scan position, so that
+/// SCAN can resume an iteration from its cursor without visiting the keys which come before
+/// it. Each page then costs `O(log n + count)`, rather than a walk over the whole keyspace.
+#[derive(Debug, Default)]
+pub struct Keyspace {
+    entries: HashMap<String, Entry>,
+    /// The keys of `entries`, ordered by `scan_position`.
+    order: BTreeSet<(u64, String)>,
+}
+
+/// Returns the position of an item in the order in which SCAN, HSCAN, SSCAN and ZSCAN visit
+/// a collection, which is also the value of the cursor pointing to the item.
+///
+/// The position is a hash of the item, so it doesn't change as other items are added or
+/// removed. An iteration therefore returns every item which is present for its whole duration,
+/// like in Redis. The position is never 0, since a cursor of 0 marks the end of an iteration.
+pub fn scan_position(item: &str) -> u64 {
+    // `DefaultHasher::new` always uses the same keys, so positions are stable across calls.
+    let mut hasher = DefaultHasher::new();
+    item.hash(&mut hasher);
+    hasher.finish().max(1)
+}
+
+impl Keyspace {
+    /// Create a new, empty `Keyspace`.
+    pub fn new() -> Keyspace {
+        Keyspace::default()
+    }
+
+    /// Returns the number of keys, including the expired ones which haven't been removed yet.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether there are no keys.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entry stored against the key.
+    pub fn get(&self, k: &str) -> Option<&Entry> {
+        self.entries.get(k)
+    }
+
+    /// Returns the entry stored against the key, for modifying it in place.
+    pub fn get_mut(&mut self, k: &str) -> Option<&mut Entry> {
+        self.entries.get_mut(k)
+    }
+
+    /// Returns whether an entry is stored against the key.
+    pub fn contains_key(&self, k: &str) -> bool {
+        self.entries.contains_key(k)
+    }
+
+    /// Store an entry against the key.
+    ///
+    /// # Returns
+    ///
+    /// The entry previously stored against the key, if any.
+    pub fn insert(&mut self, k: String, entry: Entry) -> Option<Entry> {
+        match self.entries.entry(k) {
+            hash_map::Entry::Occupied(mut e) => Some(e.insert(entry)),
+            hash_map::Entry::Vacant(e) => {
+                self.order.insert((scan_position(e.key()), e.key().clone()));
+                e.insert(entry);
+                None
+            }
+        }
+    }
+
+    /// Returns the entry stored against the key, storing the one returned by `default` first
+    /// if there is none.
+    pub fn get_or_insert_with(&mut self, k: String, default: impl FnOnce() -> Entry) -> &mut Entry {
+        match self.entries.entry(k) {
+            hash_map::Entry::Occupied(e) => e.into_mut(),
+            hash_map::Entry::Vacant(e) => {
+                self.order.insert((scan_position(e.key()), e.key().clone()));
+                e.insert(default())
+            }
+        }
+    }
+
+    /// Removes the entry stored against the key.
+    ///
+    /// # Returns
+    ///
+    /// The removed entry, or `None` if there was none.
+    pub fn remove(&mut self, k: &str) -> Option<Entry> {
+        let entry = self.entries.remove(k)?;
+        self.order.remove(&(scan_position(k), k.to_string()));
+        Some(entry)
+    }
+
+    /// Returns an iterator over the keys and their entries, in no particular order.
+    pub fn iter(&self) -> hash_map::Iter<'_, String, Entry> {
+        self.entries.iter()
+    }
+
+    /// Returns an iterator over the entries, in no particular order.
+    pub fn values(&self) -> hash_map::Values<'_, String, Entry> {
+        self.entries.values()
+    }
+
+    /// Returns the keys visited by a page of a SCAN iteration.
+    ///
+    /// # Arguments
+    ///
+    /// * `cursor` - The cursor returned by the previous call, or 0 to start a new iteration.
+    ///
+    /// * `count` - The number of keys visited.
+    ///
+    /// # Returns
+    ///
+    /// The cursor for the next call, 0 once the iteration is complete, and the keys of the page
+    /// in scan order, including the expired ones which haven't been removed yet.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<&String>) {
+        let mut keys = self
+            .order
+            .range((cursor, String::new())..)
+            .take(count.saturating_add(1));
+
+        let page = keys.by_ref().take(count).map(|(_, k)| k).collect();
+        let next = keys.next().map_or(0, |(position, _)| *position);
+        (next, page)
+    }
+}
@@ -0,0 +1,179 @@
+use std::{
+    collections::{vec_deque, VecDeque},
+    ops::Range,
+    slice,
+};
+
+/// Lists whose elements are all at most this many bytes long can be stored as a listpack.
+const LIST_MAX_LISTPACK_VALUE: usize = 64;
+
+/// The List struct stores the elements of a list, in one of two representations named
+/// after the Redis encodings.
+///
+/// Small lists are stored in a contiguous Vec ("listpack"), which has no per-element overhead
+/// beyond the strings themselves. Once the list has more elements than the configured
+/// `list-max-listpack-size`, or an element is longer than 64 bytes, it is converted to a
+/// VecDeque ("quicklist"), which pushes and pops at both ends in constant time. Like in Redis
+/// before 7.2, a list never converts back to a listpack when it shrinks.
+#[derive(Debug, Clone)]
+pub enum List {
+    /// Elements of a small list, in order.
+    Listpack(Vec<String>),
+    /// Elements of a large list, in order.
+    Quicklist(VecDeque<String>),
+}
+
+/// An iterator over the elements of a `List`, from head to tail.
+pub enum Iter<'a> {
+    Listpack(slice::Iter<'a, String>),
+    Quicklist(vec_deque::Iter<'a, String>),
+}
+
+impl List {
+    /// Create a list holding the given elements, stored as a listpack if it is small enough.
+    ///
+    /// # Arguments
+    ///
+    /// * `elems` - The elements of the list, from head to tail.
+    ///
+    /// * `max_listpack_size` - The maximum number of elements of a listpack.
+    pub fn from_vec(elems: Vec<String>, max_listpack_size: usize) -> List {
+        if Self::fits_listpack(&elems, max_listpack_size) {
+            List::Listpack(elems)
+        } else {
+            List::Quicklist(VecDeque::from(elems))
+        }
+    }
+
+    /// Returns the same list, stored as a listpack if it is small enough and as a quicklist
+    /// otherwise. Used for lists which weren't built by pushing, such as restored ones.
+    pub fn reencode(self, max_listpack_size: usize) -> List {
+        let elems = match self {
+            List::Listpack(l) => l,
+            List::Quicklist(l) => Vec::from(l),
+        };
+        Self::from_vec(elems, max_listpack_size)
+    }
+
+    /// Returns whether the given elements can be stored as a listpack.
+    fn fits_listpack(elems: &[String], max_listpack_size: usize) -> bool {
+        elems.len() <= max_listpack_size && elems.iter().all(|e| e.len() <= LIST_MAX_LISTPACK_VALUE)
+    }
+
+    /// Returns the name of the representation of the list, as reported by OBJECT ENCODING.
+    pub fn encoding(&self) -> &'static str {
+        match self {
+            List::Listpack(_) => "listpack",
+            List::Quicklist(_) => "quicklist",
+        }
+    }
+
+    /// Returns the number of elements in the list.
+    pub fn len(&self) -> usize {
+        match self {
+            List::Listpack(l) => l.len(),
+            List::Quicklist(l) => l.len(),
+        }
+    }
+
+    /// Returns whether the list has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add an element to the head of the list, converting it to a quicklist if it outgrows
+    /// a listpack.
+    pub fn push_front(&mut self, elem: String, max_listpack_size: usize) {
+        self.make_room(&elem, max_listpack_size);
+        match self {
+            List::Listpack(l) => l.insert(0, elem),
+            List::Quicklist(l) => l.push_front(elem),
+        }
+    }
+
+    /// Add an element to the tail of the list, converting it to a quicklist if it outgrows
+    /// a listpack.
+    pub fn push_back(&mut self, elem: String, max_listpack_size: usize) {
+        self.make_room(&elem, max_listpack_size);
+        match self {
+            List::Listpack(l) => l.push(elem),
+            List::Quicklist(l) => l.push_back(elem),
+        }
+    }
+
+    /// Converts a listpack to a quicklist if it can't hold one more element `elem`.
+    fn make_room(&mut self, elem: &str, max_listpack_size: usize) {
+        if let List::Listpack(l) = self {
+            if l.len() >= max_listpack_size || elem.len() > LIST_MAX_LISTPACK_VALUE {
+                *self = List::Quicklist(VecDeque::from(std::mem::take(l)));
+            }
+        }
+    }
+
+    /// Removes and returns the element at the head of the list, or `None` if it is empty.
+    pub fn pop_front(&mut self) -> Option<String> {
+        match self {
+            List::Listpack(l) if l.is_empty() => None,
+            List::Listpack(l) => Some(l.remove(0)),
+            List::Quicklist(l) => l.pop_front(),
+        }
+    }
+
+    /// Removes and returns the element at the tail of the list, or `None` if it is empty.
+    pub fn pop_back(&mut self) -> Option<String> {
+        match self {
+            List::Listpack(l) => l.pop(),
+            List::Quicklist(l) => l.pop_back(),
+        }
+    }
+
+    /// Removes the elements in the given range of indices and returns them, from head to tail.
+    pub fn drain(&mut self, range: Range<usize>) -> Vec<String> {
+        match self {
+            List::Listpack(l) => l.drain(range).collect(),
+            List::Quicklist(l) => l.drain(range).collect(),
+        }
+    }
+
+    /// Returns an iterator over the elements, from head to tail.
+    pub fn iter(&self) -> Iter<'_> {
+        self.range(0..self.len())
+    }
+
+    /// Returns an iterator over the elements in the given range of indices, from head to tail.
+    pub fn range(&self, range: Range<usize>) -> Iter<'_> {
+        match self {
+            List::Listpack(l) => Iter::Listpack(l[range].iter()),
+            List::Quicklist(l) => Iter::Quicklist(l.range(range)),
+        }
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a String;
+
+    fn next(&mut self) -> Option<&'a String> {
+        match self {
+            Iter::Listpack(it) => it.next(),
+            Iter::Quicklist(it) => it.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Iter::Listpack(it) => it.size_hint(),
+            Iter::Quicklist(it) => it.size_hint(),
+        }
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            Iter::Listpack(it) => it.next_back(),
+            Iter::Quicklist(it) => it.next_back(),
+        }
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
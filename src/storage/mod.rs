@@ -1,4 +1,12 @@
 pub mod db;
+pub mod dump;
+pub mod glob;
+pub mod hash;
+pub mod keyspace;
+pub mod list;
+pub mod sorted_set;
+
+use crate::resp::has_error_code;
 
 /// Represents errors that can occur during DB operations.
 #[derive(Debug)]
@@ -17,7 +25,9 @@ impl std::fmt::Display for DBError {
             DBError::WrongType => {
                 "WRONGTYPE Operation against a key holding the wrong kind of value".fmt(f)
             }
-            DBError::Other(msg) => msg.as_str().fmt(f),
+            // Like command errors, messages without an error code get the generic `ERR` code.
+            DBError::Other(msg) if has_error_code(msg) => msg.as_str().fmt(f),
+            DBError::Other(msg) => write!(f, "ERR {}", msg),
         }
     }
 }
\ No newline at end of file
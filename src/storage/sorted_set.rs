@@ -0,0 +1,269 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, HashMap},
+};
+
+/// The SortedSet struct stores unique members, each associated with a score.
+///
+/// Members are kept in two parallel structures: a HashMap for looking up the score of a member,
+/// and a BTreeSet ordered by (score, member) for iterating the members in score order.
+/// Members with equal scores are ordered lexicographically.
+#[derive(Debug, Clone, Default)]
+pub struct SortedSet {
+    scores: HashMap<String, f64>,
+    index: BTreeSet<ScoredMember>,
+}
+
+/// A member along with its score, ordered by score and then by member.
+#[derive(Debug, Clone)]
+struct ScoredMember {
+    score: f64,
+    member: String,
+}
+
+/// A bound of a score range.
+#[derive(Debug, Clone, Copy)]
+pub enum ScoreBound {
+    /// The bound value is included in the range.
+    Inclusive(f64),
+    /// The bound value is excluded from the range.
+    Exclusive(f64),
+}
+
+/// A bound of a lexicographic range of members.
+#[derive(Debug, Clone)]
+pub enum LexBound {
+    /// Lower than any member (`-`).
+    Min,
+    /// Greater than any member (`+`).
+    Max,
+    /// The bound value is included in the range.
+    Inclusive(String),
+    /// The bound value is excluded from the range.
+    Exclusive(String),
+}
+
+impl SortedSet {
+    /// Create a new, empty `SortedSet`.
+    pub fn new() -> SortedSet {
+        SortedSet::default()
+    }
+
+    /// Returns the number of members in the sorted set.
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// Returns whether the sorted set has no members.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the members and their scores, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &f64)> {
+        self.scores.iter()
+    }
+
+    /// Returns the score of the given member, or `None` if it isn't in the sorted set.
+    pub fn score(&self, member: &str) -> Option<f64> {
+        self.scores.get(member).copied()
+    }
+
+    /// Returns the zero-based position of the given member when ordered by ascending score,
+    /// or `None` if it isn't in the sorted set.
+    pub fn rank(&self, member: &str) -> Option<usize> {
+        let score = self.score(member)?;
+        let end = ScoredMember {
+            score,
+            member: member.to_string(),
+        };
+        Some(self.index.range(..end).count())
+    }
+
+    /// Returns the zero-based position of the given member when ordered by descending score,
+    /// or `None` if it isn't in the sorted set.
+    pub fn rev_rank(&self, member: &str) -> Option<usize> {
+        self.rank(member).map(|rank| self.len() - 1 - rank)
+    }
+
+    /// Add a member with the given score, or update the score of an existing member.
+    /// The score must not be NaN.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the member was added, `false` if it already existed.
+    pub fn insert(&mut self, member: String, score: f64) -> bool {
+        // -0.0 and 0.0 are equal scores, but not in `f64::total_cmp`'s ordering.
+        let score = if score == 0.0 { 0.0 } else { score };
+
+        let added = match self.scores.insert(member.clone(), score) {
+            Some(prev) => {
+                self.index.remove(&ScoredMember {
+                    score: prev,
+                    member: member.clone(),
+                });
+                false
+            }
+            None => true,
+        };
+        self.index.insert(ScoredMember { score, member });
+
+        added
+    }
+
+    /// Remove a member from the sorted set, keeping the score index in sync.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the member was removed, `false` if it wasn't in the sorted set.
+    pub fn remove(&mut self, member: &str) -> bool {
+        match self.scores.remove_entry(member) {
+            Some((member, score)) => {
+                self.index.remove(&ScoredMember { score, member });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the members whose score falls between `min` and `max`, with their scores,
+    /// ordered by ascending score.
+    pub fn range_by_score(&self, min: ScoreBound, max: ScoreBound) -> Vec<(String, f64)> {
+        let start = ScoredMember {
+            score: min.value(),
+            member: String::new(),
+        };
+
+        self.index
+            .range(start..)
+            .skip_while(|m| !min.allows_above(m.score))
+            .take_while(|m| max.allows_below(m.score))
+            .map(|m| (m.member.clone(), m.score))
+            .collect()
+    }
+
+    /// Returns the members which fall between `min` and `max` when compared lexicographically,
+    /// in the order of the sorted set.
+    ///
+    /// Members are only ordered lexicographically among members with equal scores, so the result
+    /// is only meaningful when all the members have the same score.
+    pub fn range_by_lex(&self, min: &LexBound, max: &LexBound) -> Vec<String> {
+        self.index
+            .iter()
+            .skip_while(|m| !min.allows_above(&m.member))
+            .take_while(|m| max.allows_below(&m.member))
+            .map(|m| m.member.clone())
+            .collect()
+    }
+}
+
+impl ScoreBound {
+    /// Parse a score bound as accepted by range commands: a score such as `1.5`, `-inf` or `+inf`,
+    /// optionally prefixed with `(` to make the bound exclusive.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the bound is not a valid float, or is NaN.
+    pub fn parse(s: &str) -> Option<ScoreBound> {
+        let (exclusive, score) = match s.strip_prefix('(') {
+            Some(score) => (true, score),
+            None => (false, s),
+        };
+
+        let score = score.parse::<f64>().ok().filter(|v| !v.is_nan())?;
+
+        if exclusive {
+            Some(ScoreBound::Exclusive(score))
+        } else {
+            Some(ScoreBound::Inclusive(score))
+        }
+    }
+
+    /// Returns the score value of the bound.
+    fn value(&self) -> f64 {
+        match self {
+            ScoreBound::Inclusive(v) | ScoreBound::Exclusive(v) => *v,
+        }
+    }
+
+    /// Returns `true` if `score` satisfies the bound when used as the minimum of a range.
+    fn allows_above(&self, score: f64) -> bool {
+        match self {
+            ScoreBound::Inclusive(v) => score >= *v,
+            ScoreBound::Exclusive(v) => score > *v,
+        }
+    }
+
+    /// Returns `true` if `score` satisfies the bound when used as the maximum of a range.
+    fn allows_below(&self, score: f64) -> bool {
+        match self {
+            ScoreBound::Inclusive(v) => score <= *v,
+            ScoreBound::Exclusive(v) => score < *v,
+        }
+    }
+}
+
+impl LexBound {
+    /// Parse a lexicographic bound as accepted by range commands: `-` or `+` for an unbounded
+    /// range, or a member prefixed with `[` (inclusive) or `(` (exclusive).
+    ///
+    /// # Returns
+    ///
+    /// `None` if the bound doesn't follow this syntax.
+    pub fn parse(s: &str) -> Option<LexBound> {
+        match s {
+            "-" => Some(LexBound::Min),
+            "+" => Some(LexBound::Max),
+            _ => {
+                if let Some(member) = s.strip_prefix('[') {
+                    Some(LexBound::Inclusive(member.to_string()))
+                } else {
+                    s.strip_prefix('(')
+                        .map(|member| LexBound::Exclusive(member.to_string()))
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if `member` satisfies the bound when used as the minimum of a range.
+    fn allows_above(&self, member: &str) -> bool {
+        match self {
+            LexBound::Min => true,
+            LexBound::Max => false,
+            LexBound::Inclusive(v) => member >= v.as_str(),
+            LexBound::Exclusive(v) => member > v.as_str(),
+        }
+    }
+
+    /// Returns `true` if `member` satisfies the bound when used as the maximum of a range.
+    fn allows_below(&self, member: &str) -> bool {
+        match self {
+            LexBound::Min => false,
+            LexBound::Max => true,
+            LexBound::Inclusive(v) => member <= v.as_str(),
+            LexBound::Exclusive(v) => member < v.as_str(),
+        }
+    }
+}
+
+impl PartialEq for ScoredMember {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScoredMember {}
+
+impl PartialOrd for ScoredMember {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredMember {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| self.member.cmp(&other.member))
+    }
+}
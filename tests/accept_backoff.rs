@@ -0,0 +1,86 @@
+// tests/accept_backoff.rs
+//
+// This test lowers the file descriptor limit of the whole process, so it is kept in its own
+// file, which cargo runs as a separate process.
+#![cfg(unix)]
+
+mod common;
+
+use std::{fs::File, io, time::Duration};
+
+use common::start_server;
+use mudb::config::Config;
+use socket2::{Domain, Socket, Type};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// Lowers the soft limit on the number of open files of the process.
+fn set_open_files_limit(limit: u64) {
+    let mut rlim = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    unsafe {
+        assert_eq!(libc::getrlimit(libc::RLIMIT_NOFILE, &mut rlim), 0);
+        rlim.rlim_cur = rlim.rlim_cur.min(limit as libc::rlim_t);
+        assert_eq!(libc::setrlimit(libc::RLIMIT_NOFILE, &rlim), 0);
+    }
+}
+
+/// When the process runs out of file descriptors, accepting connections fails with EMFILE
+/// until some are closed. The server must keep retrying rather than stop, and serve the
+/// waiting clients once descriptors are available again.
+#[tokio::test]
+async fn server_recovers_when_out_of_file_descriptors() {
+    set_open_files_limit(256);
+    let addr = start_server(Config::default()).await;
+
+    // The sockets of the clients are created before the descriptors run out, so that they
+    // can still connect afterwards.
+    let sockets: Vec<Socket> = (0..3)
+        .map(|_| Socket::new(Domain::IPV4, Type::STREAM, None).unwrap())
+        .collect();
+
+    // Use up every remaining descriptor.
+    let mut files = Vec::new();
+    loop {
+        match File::open("/dev/null") {
+            Ok(file) => files.push(file),
+            Err(e) if e.raw_os_error() == Some(libc::EMFILE) => break,
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+
+    // The connections complete in the listen backlog, but the server fails to accept them.
+    let mut clients = Vec::new();
+    for socket in sockets {
+        socket.connect(&addr.into()).unwrap();
+        let stream: std::net::TcpStream = socket.into();
+        stream.set_nonblocking(true).unwrap();
+        clients.push(TcpStream::from_std(stream).unwrap());
+    }
+    for client in clients.iter_mut() {
+        client.write_all(b"*1\r\n$4\r\nPING\r\n").await.unwrap();
+    }
+    // Give the server time to fail a few times and back off.
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(
+        matches!(
+            clients[0].try_read(&mut [0; 16]),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock
+        ),
+        "the connection shouldn't have been served yet"
+    );
+
+    drop(files);
+    for client in clients.iter_mut() {
+        let mut reply = [0; 7];
+        tokio::time::timeout(Duration::from_secs(5), client.read_exact(&mut reply))
+            .await
+            .expect("the server didn't recover")
+            .unwrap();
+        assert_eq!(&reply, b"+PONG\r\n");
+    }
+}
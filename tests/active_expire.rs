@@ -0,0 +1,62 @@
+// tests/active_expire.rs
+
+mod common;
+
+use std::time::Duration;
+
+use common::{start_server_with_db, Client};
+use mudb::{config::Config, resp::types::RespType, storage::db::DB};
+
+fn debug_config() -> Config {
+    Config {
+        enable_debug_command: true,
+        ..Config::default()
+    }
+}
+
+/// Returns the number of keys stored in the DB, including the expired ones which haven't been
+/// removed yet.
+fn stored_keys(db: &DB) -> usize {
+    let (_, visited, _) = db.expire_cycle(0, usize::MAX).unwrap();
+    visited
+}
+
+/// A key which expires is removed by the server even if it is never accessed again.
+#[tokio::test]
+async fn expired_keys_are_removed_in_the_background() {
+    let (addr, db) = start_server_with_db(debug_config()).await;
+    let mut client = Client::connect(addr).await;
+
+    client.cmd(&["SET", "short", "v", "PX", "50"]).await;
+    client.cmd(&["SET", "long", "v"]).await;
+    assert_eq!(stored_keys(&db), 2);
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(stored_keys(&db), 1);
+}
+
+/// With active expiry turned off, expired keys stay in memory until they are accessed.
+#[tokio::test]
+async fn set_active_expire_turns_the_background_removal_off() {
+    let (addr, db) = start_server_with_db(debug_config()).await;
+    let mut client = Client::connect(addr).await;
+
+    assert_eq!(
+        client.cmd(&["DEBUG", "SET-ACTIVE-EXPIRE", "0"]).await,
+        RespType::SimpleString(String::from("OK"))
+    );
+    assert!(!db.active_expire());
+    client.cmd(&["SET", "short", "v", "PX", "50"]).await;
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    // the key is still stored, and only this call removes it
+    let (_, visited, removed) = db.expire_cycle(0, usize::MAX).unwrap();
+    assert_eq!((visited, removed), (1, 1));
+
+    // turning it back on removes the keys which expire from then on
+    client.cmd(&["DEBUG", "SET-ACTIVE-EXPIRE", "1"]).await;
+    assert!(db.active_expire());
+    client.cmd(&["SET", "short", "v", "PX", "50"]).await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(stored_keys(&db), 0);
+}
@@ -0,0 +1,83 @@
+// tests/blocking_pop.rs
+
+mod common;
+
+use std::time::Duration;
+
+use common::{bulk, start_server, Client};
+use mudb::{config::Config, resp::types::RespType};
+
+#[tokio::test]
+async fn blpop_returns_element_pushed_by_another_client() {
+    let addr = start_server(Config::default()).await;
+    let mut blocked = Client::connect(addr).await;
+    let mut pusher = Client::connect(addr).await;
+
+    blocked.send(&["BLPOP", "queue", "0"]).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(
+        pusher.cmd(&["RPUSH", "queue", "job"]).await,
+        RespType::Integer(1)
+    );
+
+    assert_eq!(
+        blocked.read_reply().await,
+        RespType::Array(vec![bulk("queue"), bulk("job")])
+    );
+    assert_eq!(
+        pusher.cmd(&["LRANGE", "queue", "0", "-1"]).await,
+        RespType::Array(vec![])
+    );
+}
+
+#[tokio::test]
+async fn blpop_times_out_with_null() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    assert_eq!(
+        client.cmd(&["BLPOP", "queue", "0.1"]).await,
+        RespType::NullBulkString
+    );
+}
+
+/// A client which disconnects while blocked must not pop the elements pushed afterwards.
+#[tokio::test]
+async fn disconnected_client_does_not_pop() {
+    let addr = start_server(Config::default()).await;
+    let mut blocked = Client::connect(addr).await;
+    blocked.send(&["BRPOP", "queue", "0"]).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    drop(blocked);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut pusher = Client::connect(addr).await;
+    pusher.cmd(&["RPUSH", "queue", "job"]).await;
+    assert_eq!(
+        pusher.cmd(&["LRANGE", "queue", "0", "-1"]).await,
+        RespType::Array(vec![bulk("job")])
+    );
+}
+
+/// Commands pipelined after a blocking command are executed once it completes.
+#[tokio::test]
+async fn commands_sent_while_blocked_run_afterwards() {
+    let addr = start_server(Config::default()).await;
+    let mut blocked = Client::connect(addr).await;
+    let mut pusher = Client::connect(addr).await;
+
+    blocked.send(&["BLPOP", "queue", "0"]).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    blocked.send(&["PING"]).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    pusher.cmd(&["RPUSH", "queue", "job"]).await;
+
+    assert_eq!(
+        blocked.read_reply().await,
+        RespType::Array(vec![bulk("queue"), bulk("job")])
+    );
+    assert_eq!(
+        blocked.read_reply().await,
+        RespType::SimpleString(String::from("PONG"))
+    );
+}
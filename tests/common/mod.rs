@@ -0,0 +1,115 @@
+// tests/common/mod.rs
+
+// Not every test file uses every helper.
+#![allow(dead_code)]
+
+use std::{net::SocketAddr, sync::Arc};
+
+use bytes::BytesMut;
+use mudb::{
+    config::Config,
+    resp::types::RespType,
+    server::Server,
+    storage::db::{Storage, DB},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// Starts a server with the given options on an ephemeral port, and returns its address.
+/// The server runs until the end of the test.
+pub async fn start_server(config: Config) -> SocketAddr {
+    start_server_with_db(config).await.0
+}
+
+/// Like `start_server`, but also returns the DB of the server, so that tests can check its
+/// state directly.
+pub async fn start_server_with_db(config: Config) -> (SocketAddr, Arc<DB>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let storage = Storage::new(DB::new());
+    let db = storage.db();
+    let mut server = Server::new(listener, storage, config);
+    tokio::spawn(async move { server.run().await });
+    (addr, db)
+}
+
+/// A minimal RESP2 client connected to a test server.
+pub struct Client {
+    stream: TcpStream,
+    /// Bytes read from the server which don't make up a complete reply yet.
+    buf: BytesMut,
+}
+
+impl Client {
+    /// Opens a new connection to the server.
+    pub async fn connect(addr: SocketAddr) -> Client {
+        Client {
+            stream: TcpStream::connect(addr).await.unwrap(),
+            buf: BytesMut::new(),
+        }
+    }
+
+    /// Sends a command and returns its reply.
+    pub async fn cmd(&mut self, args: &[&str]) -> RespType {
+        self.send(args).await;
+        self.read_reply().await
+    }
+
+    /// Sends a command without waiting for its reply.
+    pub async fn send(&mut self, args: &[&str]) {
+        self.send_raw(&encode_command(args)).await;
+    }
+
+    /// Sends the given bytes as is.
+    pub async fn send_raw(&mut self, bytes: &[u8]) {
+        self.stream.write_all(bytes).await.unwrap();
+    }
+
+    /// Reads the next reply. Panics if the connection is closed before a complete reply.
+    pub async fn read_reply(&mut self) -> RespType {
+        loop {
+            if let Some((reply, len)) = RespType::parse(&self.buf).unwrap() {
+                let _ = self.buf.split_to(len);
+                return reply;
+            }
+            let n = self.stream.read_buf(&mut self.buf).await.unwrap();
+            assert!(n > 0, "connection closed before a complete reply");
+        }
+    }
+
+    /// Reads from the connection until the server closes it.
+    ///
+    /// # Returns
+    ///
+    /// The replies sent before the connection was closed.
+    pub async fn read_until_closed(&mut self) -> Vec<RespType> {
+        let mut replies = Vec::new();
+        loop {
+            while let Some((reply, len)) = RespType::parse(&self.buf).unwrap() {
+                let _ = self.buf.split_to(len);
+                replies.push(reply);
+            }
+            match self.stream.read_buf(&mut self.buf).await {
+                Ok(0) | Err(_) => return replies,
+                Ok(_) => {}
+            }
+        }
+    }
+}
+
+/// Encodes a command as a RESP array of bulk strings.
+pub fn encode_command(args: &[&str]) -> Vec<u8> {
+    let parts = args
+        .iter()
+        .map(|arg| RespType::BulkString(arg.to_string()))
+        .collect();
+    RespType::Array(parts).to_bytes().to_vec()
+}
+
+/// Shorthand for a bulk string reply.
+pub fn bulk(s: &str) -> RespType {
+    RespType::BulkString(s.to_string())
+}
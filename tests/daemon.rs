@@ -0,0 +1,73 @@
+// tests/daemon.rs
+
+#![cfg(unix)]
+
+use std::{
+    fs,
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Runs the server with `--daemonize` on the given port, writing its PID to the given file.
+fn daemonize(port: u16, pidfile: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_mudb"))
+        .args(["--port", &port.to_string(), "--daemonize", "--pidfile"])
+        .arg(pidfile)
+        .output()
+        .unwrap()
+}
+
+fn pidfile(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("mudb-{}-{}.pid", name, std::process::id()))
+}
+
+/// The process started with `--daemonize` only exits once the server listens, and the PID file
+/// is removed when the server is terminated.
+#[test]
+fn daemonize_waits_for_the_server_and_removes_the_pidfile() {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let pidfile = pidfile("daemon");
+
+    let output = daemonize(port, &pidfile);
+    assert!(output.status.success(), "{:?}", output);
+    let pid: i32 = fs::read_to_string(&pidfile)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+    std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+
+    // SAFETY: kill only sends a signal to the server.
+    assert_eq!(unsafe { libc::kill(pid, libc::SIGTERM) }, 0);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while pidfile.exists() {
+        assert!(Instant::now() < deadline, "the PID file wasn't removed");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// A server which fails to start makes the process started with `--daemonize` fail, with the
+/// error on its stderr.
+#[test]
+fn daemonize_reports_startup_failures() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let pidfile = pidfile("in-use");
+
+    let output = daemonize(port, &pidfile);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Could not bind the TCP listener"),
+        "{}",
+        stderr
+    );
+    assert!(!pidfile.exists());
+}
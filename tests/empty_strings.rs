@@ -0,0 +1,47 @@
+// tests/empty_strings.rs
+
+mod common;
+
+use common::{bulk, start_server, Client};
+use mudb::{config::Config, resp::types::RespType};
+
+#[tokio::test]
+async fn empty_values_are_stored_and_read_back() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    let reply = client.cmd(&["SET", "k", ""]).await;
+    assert!(!matches!(reply, RespType::SimpleError(_)), "{:?}", reply);
+    assert_eq!(client.cmd(&["GET", "k"]).await, bulk(""));
+
+    assert_eq!(
+        client.cmd(&["RPUSH", "l", "", "a", ""]).await,
+        RespType::Integer(3)
+    );
+    assert_eq!(
+        client.cmd(&["LRANGE", "l", "0", "-1"]).await,
+        RespType::Array(vec![bulk(""), bulk("a"), bulk("")])
+    );
+    // the connection is still in sync after the empty values
+    assert_eq!(
+        client.cmd(&["PING"]).await,
+        RespType::SimpleString(String::from("PONG"))
+    );
+}
+
+/// An empty bulk string split across several writes is only decoded once its CRLF arrives.
+#[tokio::test]
+async fn empty_value_split_across_writes() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    client
+        .send_raw(b"*3\r\n$3\r\nSET\r\n$1\r\nk\r\n$0\r\n")
+        .await;
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    client.send_raw(b"\r\n").await;
+    let reply = client.read_reply().await;
+    assert!(!matches!(reply, RespType::SimpleError(_)), "{:?}", reply);
+
+    assert_eq!(client.cmd(&["GET", "k"]).await, bulk(""));
+}
@@ -0,0 +1,70 @@
+// tests/error_prefixes.rs
+
+mod common;
+
+use common::{start_server, Client};
+use mudb::{config::Config, resp::types::RespType};
+
+const WRONGTYPE: &str = "WRONGTYPE Operation against a key holding the wrong kind of value";
+
+/// Commands run against a key holding another type fail with the `WRONGTYPE` error code,
+/// which client libraries branch on, and leave the key untouched.
+#[tokio::test]
+async fn type_errors_use_the_wrongtype_prefix() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+    client.cmd(&["SET", "string", "v"]).await;
+    client.cmd(&["RPUSH", "list", "a"]).await;
+    client.cmd(&["HSET", "hash", "f", "v"]).await;
+
+    let cases: &[&[&str]] = &[
+        &["GET", "list"],
+        &["LPUSH", "string", "a"],
+        &["HSET", "string", "f", "v"],
+        &["APPEND", "list", "a"],
+        &["LRANGE", "string", "0", "-1"],
+        &["HMGET", "list", "f"],
+        &["SADD", "hash", "m"],
+        &["ZADD", "string", "1", "m"],
+        &["GETEX", "hash", "PERSIST"],
+    ];
+    for args in cases {
+        assert_eq!(
+            client.cmd(args).await,
+            RespType::SimpleError(String::from(WRONGTYPE)),
+            "{:?}",
+            args
+        );
+    }
+
+    assert_eq!(
+        client.cmd(&["GET", "string"]).await,
+        RespType::BulkString(String::from("v"))
+    );
+    assert_eq!(
+        client.cmd(&["LRANGE", "list", "0", "-1"]).await,
+        RespType::Array(vec![RespType::BulkString(String::from("a"))])
+    );
+}
+
+/// Errors in the arguments of a command keep the `ERR` prefix, even on a key of another type.
+#[tokio::test]
+async fn argument_errors_use_the_err_prefix() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+    client.cmd(&["RPUSH", "list", "a"]).await;
+
+    let cases: &[&[&str]] = &[
+        &["GET"],
+        &["LRANGE", "list", "zero", "-1"],
+        &["SET", "list", "v", "EX", "0"],
+        &["HSET", "list", "f"],
+        &["NOSUCHCOMMAND"],
+    ];
+    for args in cases {
+        match client.cmd(args).await {
+            RespType::SimpleError(e) => assert!(e.starts_with("ERR "), "{:?}: {}", args, e),
+            other => panic!("{:?}: unexpected reply {:?}", args, other),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// tests/hashes.rs
+
+mod common;
+
+use common::{bulk, start_server, Client};
+use mudb::{config::Config, resp::types::RespType};
+
+/// Huge counts don't make the server allocate more than the reply needs.
+#[tokio::test]
+async fn hrandfield_with_huge_counts() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+    client.cmd(&["HSET", "h", "f", "v"]).await;
+
+    // a positive count returns at most every field
+    assert_eq!(
+        client
+            .cmd(&["HRANDFIELD", "h", &i64::MAX.to_string()])
+            .await,
+        RespType::Array(vec![bulk("f")])
+    );
+    assert_eq!(
+        client
+            .cmd(&["HRANDFIELD", "h", &i64::MAX.to_string(), "WITHVALUES"])
+            .await,
+        RespType::Array(vec![bulk("f"), bulk("v")])
+    );
+
+    // a negative count repeats fields, so it is bounded
+    match client
+        .cmd(&["HRANDFIELD", "h", &(-(i64::MAX / 2)).to_string()])
+        .await
+    {
+        RespType::SimpleError(e) => assert!(e.starts_with("ERR count is out of range"), "{}", e),
+        other => panic!("unexpected reply {:?}", other),
+    }
+    match client.cmd(&["HRANDFIELD", "h", "-3"]).await {
+        RespType::Array(fields) => assert_eq!(fields, vec![bulk("f"); 3]),
+        other => panic!("unexpected reply {:?}", other),
+    }
+
+    // the connection is still served, and a missing key still gives an empty array
+    assert_eq!(
+        client
+            .cmd(&["HRANDFIELD", "missing", &(-(i64::MAX / 2)).to_string()])
+            .await,
+        RespType::Array(vec![])
+    );
+}
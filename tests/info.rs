@@ -0,0 +1,59 @@
+// tests/info.rs
+
+mod common;
+
+use common::{start_server, Client};
+use mudb::{
+    config::{Config, MaxmemoryPolicy},
+    resp::types::RespType,
+};
+
+#[tokio::test]
+async fn memory_section_reports_the_maxmemory_options() {
+    let addr = start_server(Config {
+        maxmemory: Some(100 * 1024 * 1024),
+        maxmemory_policy: MaxmemoryPolicy::AllkeysLfu,
+        ..Config::default()
+    })
+    .await;
+    let mut client = Client::connect(addr).await;
+    client.cmd(&["SET", "k", "v"]).await;
+
+    let info = match client.cmd(&["INFO", "memory"]).await {
+        RespType::BulkString(info) => info,
+        other => panic!("unexpected reply {:?}", other),
+    };
+    let lines: Vec<&str> = info.split("\r\n").collect();
+    assert_eq!(lines[0], "# Memory");
+    assert!(lines.contains(&"maxmemory:104857600"), "{}", info);
+    assert!(lines.contains(&"maxmemory_policy:allkeys-lfu"), "{}", info);
+    assert!(
+        lines.iter().any(|l| l
+            .strip_prefix("used_memory:")
+            .is_some_and(|n| n.parse::<usize>().unwrap() > 0)),
+        "{}",
+        info
+    );
+    // the other sections are left out
+    assert!(!info.contains("# Keyspace"), "{}", info);
+}
+
+#[tokio::test]
+async fn maxmemory_defaults_to_no_limit() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    match client.cmd(&["INFO"]).await {
+        RespType::BulkString(info) => {
+            assert!(info.starts_with("# Memory\r\n"), "{}", info);
+            assert!(info.contains("\r\nmaxmemory:0\r\n"), "{}", info);
+            assert!(
+                info.contains("\r\nmaxmemory_policy:noeviction\r\n"),
+                "{}",
+                info
+            );
+            assert!(info.contains("\r\n\r\n# Replication\r\n"), "{}", info);
+        }
+        other => panic!("unexpected reply {:?}", other),
+    }
+}
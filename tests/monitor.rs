@@ -0,0 +1,32 @@
+// tests/monitor.rs
+
+mod common;
+
+use common::{start_server, Client};
+use mudb::{config::Config, resp::types::RespType};
+
+/// A connection in MONITOR mode receives the commands executed by the other connections,
+/// and they stop being described once it disconnects.
+#[tokio::test]
+async fn monitor_receives_the_commands_of_other_connections() {
+    let addr = start_server(Config::default()).await;
+    let mut monitor = Client::connect(addr).await;
+    let mut client = Client::connect(addr).await;
+
+    assert_eq!(
+        monitor.cmd(&["MONITOR"]).await,
+        RespType::SimpleString(String::from("OK"))
+    );
+    client.cmd(&["PING"]).await;
+    match monitor.read_reply().await {
+        RespType::SimpleString(line) => assert!(line.ends_with("\"ping\""), "{}", line),
+        other => panic!("unexpected reply {:?}", other),
+    }
+
+    drop(monitor);
+    // the server keeps serving the other connections once the monitor is gone
+    assert_eq!(
+        client.cmd(&["PING"]).await,
+        RespType::SimpleString(String::from("PONG"))
+    );
+}
@@ -0,0 +1,57 @@
+// tests/object_freq.rs
+
+mod common;
+
+use common::{start_server, Client};
+use mudb::{
+    config::{Config, MaxmemoryPolicy},
+    resp::types::RespType,
+};
+
+#[tokio::test]
+async fn freq_requires_an_lfu_policy() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    client.cmd(&["SET", "k", "v"]).await;
+    assert_eq!(
+        client.cmd(&["OBJECT", "FREQ", "k"]).await,
+        RespType::SimpleError(String::from(
+            "ERR An LFU maxmemory policy is not selected, access frequency not tracked."
+        ))
+    );
+}
+
+#[tokio::test]
+async fn freq_counts_accesses_with_an_lfu_policy() {
+    let addr = start_server(Config {
+        maxmemory_policy: MaxmemoryPolicy::AllkeysLfu,
+        ..Config::default()
+    })
+    .await;
+    let mut client = Client::connect(addr).await;
+
+    client.cmd(&["SET", "hot", "v"]).await;
+    client.cmd(&["SET", "cold", "v"]).await;
+    for _ in 0..1000 {
+        client.cmd(&["GET", "hot"]).await;
+    }
+
+    let freq = |reply| match reply {
+        RespType::Integer(n) => n,
+        other => panic!("unexpected reply {:?}", other),
+    };
+    let hot = freq(client.cmd(&["OBJECT", "FREQ", "hot"]).await);
+    let cold = freq(client.cmd(&["OBJECT", "FREQ", "cold"]).await);
+    assert!(hot > cold, "hot: {}, cold: {}", hot, cold);
+    // OBJECT FREQ itself doesn't count as an access
+    assert_eq!(
+        client.cmd(&["OBJECT", "FREQ", "cold"]).await,
+        RespType::Integer(cold)
+    );
+
+    assert_eq!(
+        client.cmd(&["OBJECT", "FREQ", "missing"]).await,
+        RespType::SimpleError(String::from("ERR no such key"))
+    );
+}
@@ -0,0 +1,56 @@
+// tests/pipelining.rs
+
+mod common;
+
+use common::{bulk, encode_command, start_server, Client};
+use mudb::{config::Config, resp::types::RespType};
+
+/// Commands sent in a single write are all executed, and their replies come back in order.
+#[tokio::test]
+async fn pipelined_replies_are_in_order() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    // RPUSH replies with the new length of the list, so the replies count up.
+    let pipeline: Vec<u8> = (1..=1000)
+        .flat_map(|i| encode_command(&["RPUSH", "list", &i.to_string()]))
+        .collect();
+    client.send_raw(&pipeline).await;
+
+    for i in 1..=1000 {
+        assert_eq!(client.read_reply().await, RespType::Integer(i));
+    }
+}
+
+/// Replies larger than the pipelining buffer are flushed along the way, without being
+/// reordered or lost.
+#[tokio::test]
+async fn large_pipelined_replies_are_in_order() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    let elems: Vec<String> = (0..100).map(|i| format!("element-{}", i)).collect();
+    let mut rpush = vec!["RPUSH", "list"];
+    rpush.extend(elems.iter().map(String::as_str));
+    client.cmd(&rpush).await;
+
+    // about 1.5kb per reply, so the replies don't fit in the buffer at once
+    let pipeline: Vec<u8> = (0..500)
+        .flat_map(|i| {
+            let mut cmd = encode_command(&["LRANGE", "list", "0", "-1"]);
+            cmd.extend(encode_command(&[
+                "LPOS",
+                "list",
+                &format!("element-{}", i % 100),
+            ]));
+            cmd
+        })
+        .collect();
+    client.send_raw(&pipeline).await;
+
+    let expected = RespType::Array(elems.iter().map(|e| bulk(e)).collect());
+    for i in 0..500 {
+        assert_eq!(client.read_reply().await, expected);
+        assert_eq!(client.read_reply().await, RespType::Integer(i % 100));
+    }
+}
@@ -0,0 +1,64 @@
+// tests/protocol_limits.rs
+
+mod common;
+
+use common::{start_server, Client};
+use mudb::{config::Config, resp::types::RespType};
+
+/// Sends a malformed request, and checks that the server replies with a protocol error
+/// and closes the connection, while still serving the other connections.
+async fn assert_rejected(request: &[u8], expected_error: &str) {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    client.send_raw(request).await;
+    let replies = client.read_until_closed().await;
+    assert_eq!(
+        replies,
+        vec![RespType::SimpleError(format!(
+            "ERR Protocol error: {}",
+            expected_error
+        ))]
+    );
+
+    let mut other = Client::connect(addr).await;
+    assert_eq!(
+        other.cmd(&["PING"]).await,
+        RespType::SimpleString(String::from("PONG"))
+    );
+}
+
+#[tokio::test]
+async fn huge_bulk_string_len_closes_the_connection() {
+    assert_rejected(
+        b"*2\r\n$3\r\nGET\r\n$1000000000000\r\n",
+        "invalid bulk length",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn huge_array_len_closes_the_connection() {
+    assert_rejected(b"*99999999999\r\n", "invalid multibulk length").await;
+}
+
+#[tokio::test]
+async fn bulk_string_len_over_configured_limit_closes_the_connection() {
+    let config = Config {
+        proto_max_bulk_len: 16,
+        ..Config::default()
+    };
+    let addr = start_server(config).await;
+    let mut client = Client::connect(addr).await;
+
+    client
+        .send_raw(b"*3\r\n$3\r\nSET\r\n$1\r\nk\r\n$17\r\n")
+        .await;
+    let replies = client.read_until_closed().await;
+    assert_eq!(
+        replies,
+        vec![RespType::SimpleError(String::from(
+            "ERR Protocol error: invalid bulk length"
+        ))]
+    );
+}
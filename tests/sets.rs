@@ -0,0 +1,80 @@
+// tests/sets.rs
+
+mod common;
+
+use common::{start_server, Client};
+use mudb::{config::Config, resp::types::RespType};
+
+/// Returns the members of the set stored at key, sorted.
+async fn members(client: &mut Client, key: &str, candidates: &[&str]) -> Vec<String> {
+    let mut args = vec!["SMISMEMBER", key];
+    args.extend(candidates);
+    match client.cmd(&args).await {
+        RespType::Array(found) => candidates
+            .iter()
+            .zip(found)
+            .filter(|(_, f)| *f == RespType::Integer(1))
+            .map(|(m, _)| m.to_string())
+            .collect(),
+        reply => panic!("unexpected reply {:?}", reply),
+    }
+}
+
+/// Like in Redis, SINTERSTORE overwrites a destination holding another type of value.
+#[tokio::test]
+async fn sinterstore_overwrites_a_list() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    client.cmd(&["RPUSH", "dest", "a", "b"]).await;
+    client.cmd(&["SADD", "s1", "a", "b", "c"]).await;
+    client.cmd(&["SADD", "s2", "b", "c", "d"]).await;
+
+    assert_eq!(
+        client.cmd(&["SINTERSTORE", "dest", "s1", "s2"]).await,
+        RespType::Integer(2)
+    );
+    assert_eq!(
+        members(&mut client, "dest", &["a", "b", "c", "d"]).await,
+        vec!["b", "c"]
+    );
+    // the list is gone, so list commands now see the wrong type
+    assert!(matches!(
+        client.cmd(&["LRANGE", "dest", "0", "-1"]).await,
+        RespType::SimpleError(e) if e.starts_with("WRONGTYPE")
+    ));
+}
+
+#[tokio::test]
+async fn sinterstore_with_empty_result_deletes_destination() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    client.cmd(&["SADD", "dest", "x"]).await;
+    client.cmd(&["SADD", "s1", "a"]).await;
+
+    assert_eq!(
+        client.cmd(&["SINTERSTORE", "dest", "s1", "missing"]).await,
+        RespType::Integer(0)
+    );
+    assert_eq!(
+        client.cmd(&["DUMP", "dest"]).await,
+        RespType::NullBulkString
+    );
+}
+
+#[tokio::test]
+async fn sinterstore_rejects_non_set_source() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    client.cmd(&["SADD", "dest", "x"]).await;
+    client.cmd(&["SET", "str", "v"]).await;
+
+    assert!(matches!(
+        client.cmd(&["SINTERSTORE", "dest", "str"]).await,
+        RespType::SimpleError(e) if e.starts_with("WRONGTYPE")
+    ));
+    // the destination is left untouched
+    assert_eq!(members(&mut client, "dest", &["x"]).await, vec!["x"]);
+}
@@ -0,0 +1,26 @@
+// tests/shared_storage.rs
+
+mod common;
+
+use common::{bulk, start_server, Client};
+use mudb::{config::Config, resp::types::RespType};
+
+/// All the connections work on the same DB, so a key written on one connection can be
+/// read right away from another one.
+#[tokio::test]
+async fn write_is_visible_from_another_connection() {
+    let addr = start_server(Config::default()).await;
+    let mut writer = Client::connect(addr).await;
+    let mut reader = Client::connect(addr).await;
+
+    let reply = writer.cmd(&["SET", "shared", "value"]).await;
+    assert!(!matches!(reply, RespType::SimpleError(_)), "{:?}", reply);
+    assert_eq!(reader.cmd(&["GET", "shared"]).await, bulk("value"));
+
+    // and the other way round
+    reader.cmd(&["RPUSH", "list", "a", "b"]).await;
+    assert_eq!(
+        writer.cmd(&["LRANGE", "list", "0", "-1"]).await,
+        RespType::Array(vec![bulk("a"), bulk("b")])
+    );
+}
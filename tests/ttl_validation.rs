@@ -0,0 +1,96 @@
+// tests/ttl_validation.rs
+
+mod common;
+
+use common::{bulk, start_server, Client};
+use mudb::{config::Config, resp::types::RespType};
+
+fn invalid_expire_time(cmd: &str) -> RespType {
+    RespType::SimpleError(format!("ERR invalid expire time in '{}' command", cmd))
+}
+
+/// SET with a time to live which isn't positive is rejected, and doesn't write the value.
+#[tokio::test]
+async fn set_rejects_non_positive_ttls() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    for (option, ttl) in [("EX", "0"), ("EX", "-1"), ("PX", "0"), ("PX", "-100")] {
+        assert_eq!(
+            client.cmd(&["SET", "new", "v", option, ttl]).await,
+            invalid_expire_time("set"),
+            "{} {}",
+            option,
+            ttl
+        );
+        assert_eq!(client.cmd(&["GET", "new"]).await, RespType::NullBulkString);
+
+        client.cmd(&["SET", "old", "before"]).await;
+        assert_eq!(
+            client.cmd(&["SET", "old", "after", option, ttl]).await,
+            invalid_expire_time("set")
+        );
+        assert_eq!(client.cmd(&["GET", "old"]).await, bulk("before"));
+        assert_eq!(
+            client.cmd(&["EXPIRETIME", "old"]).await,
+            RespType::Integer(-1)
+        );
+    }
+
+    // a number of seconds which overflows once converted to milliseconds is rejected as well
+    assert_eq!(
+        client
+            .cmd(&["SET", "new", "v", "EX", &(i64::MAX / 10).to_string()])
+            .await,
+        invalid_expire_time("set")
+    );
+    assert_eq!(client.cmd(&["GET", "new"]).await, RespType::NullBulkString);
+}
+
+/// GETEX with a time to live which isn't positive is rejected, and leaves the key untouched.
+#[tokio::test]
+async fn getex_rejects_non_positive_ttls() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+    client.cmd(&["SET", "k", "v"]).await;
+
+    for (option, ttl) in [("EX", "0"), ("EX", "-1"), ("PX", "0"), ("PX", "-1")] {
+        assert_eq!(
+            client.cmd(&["GETEX", "k", option, ttl]).await,
+            invalid_expire_time("getex"),
+            "{} {}",
+            option,
+            ttl
+        );
+        assert_eq!(client.cmd(&["GET", "k"]).await, bulk("v"));
+        assert_eq!(
+            client.cmd(&["EXPIRETIME", "k"]).await,
+            RespType::Integer(-1)
+        );
+    }
+}
+
+/// Unlike SET, EXPIRE and PEXPIRE accept a time to live which isn't positive, and delete the
+/// key right away, like in Redis.
+#[tokio::test]
+async fn expire_with_non_positive_ttl_deletes_the_key() {
+    let addr = start_server(Config::default()).await;
+    let mut client = Client::connect(addr).await;
+
+    for (cmd, ttl) in [
+        ("EXPIRE", "0"),
+        ("EXPIRE", "-1"),
+        ("PEXPIRE", "0"),
+        ("PEXPIRE", "-1"),
+    ] {
+        client.cmd(&["SET", "k", "v"]).await;
+        assert_eq!(
+            client.cmd(&[cmd, "k", ttl]).await,
+            RespType::Integer(1),
+            "{} {}",
+            cmd,
+            ttl
+        );
+        assert_eq!(client.cmd(&["GET", "k"]).await, RespType::NullBulkString);
+    }
+}